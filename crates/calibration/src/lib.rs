@@ -1,7 +1,8 @@
 use corrections::Corrections;
-use levenberg_marquardt::LevenbergMarquardt;
+use levenberg_marquardt::{LeastSquaresProblem, LevenbergMarquardt, MinimizationReport};
 use measurement::Measurement;
 use problem::CalibrationProblem;
+use residuals::Residual;
 use types::field_dimensions::FieldDimensions;
 
 pub mod corrections;
@@ -11,15 +12,23 @@ pub mod measurement;
 pub mod problem;
 pub mod residuals;
 
+pub struct Solution {
+    pub corrections: Corrections,
+    /// Residuals of all measurements at the solution, `None` if a measurement is not projectable
+    pub residuals: Option<Residual>,
+    pub report: MinimizationReport<f32>,
+}
+
 pub fn solve(
     initial_corrections: Corrections,
     measurements: Vec<Measurement>,
     field_dimensions: FieldDimensions,
-) -> Corrections {
+) -> Solution {
     let problem = CalibrationProblem::new(initial_corrections, measurements, field_dimensions);
     let (result, report) = LevenbergMarquardt::new().minimize(problem);
-    println!("Report: {report:?}");
-    let corrections = result.get_corrections();
-    println!("Corrections: {corrections:?}");
-    corrections
+    Solution {
+        corrections: result.get_corrections(),
+        residuals: result.residuals(),
+        report,
+    }
 }
@@ -39,17 +39,14 @@ impl LeastSquaresProblem<f32, Dyn, Const<AMOUNT_OF_PARAMETERS>> for CalibrationP
     type ParameterStorage = Owned<f32, Const<AMOUNT_OF_PARAMETERS>>;
 
     fn set_params(&mut self, parameters: &SVector<f32, AMOUNT_OF_PARAMETERS>) {
-        println!("set_params({parameters:?})");
         self.parameters = parameters.into();
     }
 
     fn params(&self) -> SVector<f32, AMOUNT_OF_PARAMETERS> {
-        println!("params()");
         (&self.parameters).into()
     }
 
     fn residuals(&self) -> Option<Residual> {
-        println!("residuals()");
         calculate_residuals_from_parameters(
             &self.parameters,
             &self.measurements,
@@ -58,7 +55,6 @@ impl LeastSquaresProblem<f32, Dyn, Const<AMOUNT_OF_PARAMETERS>> for CalibrationP
     }
 
     fn jacobian(&self) -> Option<Jacobian> {
-        println!("jacobian()");
         calculate_jacobian_from_parameters(
             &self.parameters,
             &self.measurements,
@@ -1,9 +1,9 @@
-use std::{collections::HashSet, iter::once};
+use std::iter::once;
 
 use convert_case::{Case, Casing};
 use itertools::Itertools;
 use proc_macro2::{Ident, TokenStream};
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, ToTokens};
 use source_analyzer::{
     contexts::Field,
     cyclers::{Cycler, CyclerKind, Cyclers},
@@ -17,6 +17,7 @@ pub fn generate_cyclers(cyclers: &Cyclers) -> TokenStream {
         let instance_name = format_ident!("{}", instance);
         quote! {
             #instance_name {
+                timestamp: std::time::SystemTime,
                 data: std::vec::Vec<u8>,
             },
         }
@@ -101,6 +102,7 @@ fn generate_struct(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
             own_writer: framework::Writer<Database>,
             own_changed: std::sync::Arc<tokio::sync::Notify>,
             own_subscribed_outputs_reader: framework::Reader<std::collections::HashSet<String>>,
+            own_injections_reader: framework::Reader<communication::server::Injections>,
             parameters_reader: framework::Reader<crate::structs::Parameters>,
             cycler_state: crate::structs::#module_name::CyclerState,
             #realtime_inputs
@@ -179,6 +181,7 @@ fn generate_implementation(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
     let new_method = generate_new_method(cycler, cyclers);
     let start_method = generate_start_method();
     let cycle_method = generate_cycle_method(cycler, cyclers);
+    let replay_method = generate_replay_method(cycler);
 
     quote! {
         impl<HardwareInterface> Cycler<HardwareInterface>
@@ -188,6 +191,7 @@ fn generate_implementation(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
             #new_method
             #start_method
             #cycle_method
+            #replay_method
         }
     }
 }
@@ -208,6 +212,7 @@ fn generate_new_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
             own_writer: framework::Writer<Database>,
             own_changed: std::sync::Arc<tokio::sync::Notify>,
             own_subscribed_outputs_reader: framework::Reader<std::collections::HashSet<String>>,
+            own_injections_reader: framework::Reader<communication::server::Injections>,
             parameters_reader: framework::Reader<crate::structs::Parameters>,
             #input_output_fields
             recording_sender: std::sync::mpsc::SyncSender<crate::cyclers::RecordingFrame>,
@@ -222,6 +227,7 @@ fn generate_new_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
                 own_writer,
                 own_changed,
                 own_subscribed_outputs_reader,
+                own_injections_reader,
                 parameters_reader,
                 cycler_state,
                 #input_output_identifiers
@@ -371,17 +377,19 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
     let setup_node_executions = cycler
         .setup_nodes
         .iter()
-        .map(|node| generate_node_execution(node, cycler, RecordingGeneration::Generate));
+        .map(|node| generate_node_execution(node, cycler, CyclerMode::Run));
     let cycle_node_executions = cycler
         .cycle_nodes
         .iter()
-        .map(|node| generate_node_execution(node, cycler, RecordingGeneration::Skip));
+        .map(|node| generate_node_execution(node, cycler, CyclerMode::Run));
+    let setup_output_recordings = generate_setup_outputs_recording(cycler);
     let cross_inputs = get_cross_inputs(cycler);
-    let cross_input_recordings = generate_cross_inputs_recording(cycler, cross_inputs);
+    let cross_input_recordings = generate_cross_inputs_recording(cycler, &cross_inputs);
 
     let post_setup = match cycler.kind {
         CyclerKind::Perception => quote! {
             self.own_producer.announce();
+            let now = <HardwareInterface as hardware::TimeInterface>::get_now(&*self.hardware_interface);
         },
         CyclerKind::RealTime => {
             let perception_cycler_updates = generate_perception_cycler_updates(cyclers);
@@ -392,7 +400,10 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
                     #perception_cycler_updates
                 });
                 if enable_recording {
-                    bincode::serialize_into(&mut recording_frame, &now).wrap_err("failed to record time")?;
+                    bincode::serialize_into(
+                        &mut recording_frame,
+                        &self.perception_databases.get_first_timestamp_of_temporary_databases(),
+                    ).wrap_err("failed to record first timestamp of temporary databases")?;
                 }
             }
         }
@@ -423,10 +434,14 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
             );
         },
     };
+    let apply_injections = generate_injections_application();
     let recording_variants = cycler.instances.iter().map(|instance| {
         let instance_name = format_ident!("{}", instance);
         quote! {
-            CyclerInstance::#instance_name => crate::cyclers::RecordingFrame::#instance_name { data: recording_frame },
+            CyclerInstance::#instance_name => crate::cyclers::RecordingFrame::#instance_name {
+                timestamp: now,
+                data: recording_frame,
+            },
         }
     });
 
@@ -453,6 +468,7 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
                     #(#setup_node_executions)*
                 }
 
+                #setup_output_recordings
                 #post_setup
 
                 {
@@ -463,6 +479,7 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
                     #(#cycle_node_executions)*
                 }
 
+                #apply_injections
                 #after_remaining_nodes
 
                 if enable_recording {
@@ -477,7 +494,142 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
     }
 }
 
-fn get_cross_inputs(cycler: &Cycler) -> HashSet<Field> {
+fn generate_injections_application() -> TokenStream {
+    quote! {
+        {
+            let own_injections = self.own_injections_reader.next();
+            for (path, value) in own_injections.iter() {
+                // the communication server only accepts injections that deserialize into the database
+                let _ = serialize_hierarchy::SerializeHierarchy::deserialize_path(
+                    &mut *own_database_reference,
+                    path,
+                    value.clone(),
+                );
+            }
+        }
+    }
+}
+
+fn generate_replay_method(cycler: &Cycler) -> TokenStream {
+    let cross_inputs = get_cross_inputs(cycler);
+    let cycle_node_executions = cycler.cycle_nodes.iter().map(|node| {
+        generate_node_execution(
+            node,
+            cycler,
+            CyclerMode::Replay {
+                cross_inputs: &cross_inputs,
+            },
+        )
+    });
+    let setup_output_replays = generate_setup_outputs_replay(cycler);
+    let apply_injections = generate_injections_application();
+    let cross_input_replays = generate_cross_inputs_replay(cycler, &cross_inputs);
+
+    let post_setup = match cycler.kind {
+        CyclerKind::Perception => quote! {},
+        CyclerKind::RealTime => quote! {
+            let first_timestamp_of_temporary_databases: Option<std::time::SystemTime> =
+                bincode::deserialize_from(&mut recording_frame)
+                    .wrap_err("failed to replay first timestamp of temporary databases")?;
+        },
+    };
+    let after_remaining_nodes = match cycler.kind {
+        CyclerKind::Perception => quote! {},
+        CyclerKind::RealTime => quote! {
+            self.historic_databases.update(
+                now,
+                first_timestamp_of_temporary_databases,
+                &own_database_reference.main_outputs,
+            );
+        },
+    };
+
+    quote! {
+        #[allow(clippy::nonminimal_bool)]
+        pub(crate) fn replay(&mut self, now: std::time::SystemTime, mut recording_frame: &[u8]) -> color_eyre::Result<()> {
+            {
+                let instance = self.instance;
+                let instance_name = format!("{instance:?}");
+                let itt_domain = ittapi::Domain::new(&instance_name);
+
+                let mut own_database = self.own_writer.next();
+                let own_database_reference = {
+                    use std::ops::DerefMut;
+                    own_database.deref_mut()
+                };
+
+                #setup_output_replays
+                #post_setup
+
+                {
+                    let own_subscribed_outputs = self.own_subscribed_outputs_reader.next();
+                    let parameters = self.parameters_reader.next();
+                    #cross_input_replays
+                    #(#cycle_node_executions)*
+                }
+
+                #apply_injections
+                #after_remaining_nodes
+
+                if !recording_frame.is_empty() {
+                    color_eyre::eyre::bail!(
+                        "{} bytes left after replaying recording frame, recording does not match this build",
+                        recording_frame.len(),
+                    );
+                }
+            }
+            self.own_changed.notify_one();
+            Ok(())
+        }
+    }
+}
+
+fn get_setup_outputs(cycler: &Cycler) -> Vec<&Ident> {
+    cycler
+        .setup_nodes
+        .iter()
+        .flat_map(|node| {
+            node.contexts
+                .main_outputs
+                .iter()
+                .filter_map(|field| match field {
+                    Field::MainOutput { name, .. } => Some(name),
+                    _ => None,
+                })
+        })
+        .sorted()
+        .collect()
+}
+
+fn generate_setup_outputs_recording(cycler: &Cycler) -> TokenStream {
+    let recordings = get_setup_outputs(cycler).into_iter().map(|name| {
+        let error_message = format!("failed to record {name}");
+        quote! {
+            bincode::serialize_into(&mut recording_frame, &own_database_reference.main_outputs.#name).wrap_err(#error_message)?;
+        }
+    }).collect::<Vec<_>>();
+
+    if recordings.is_empty() {
+        return Default::default();
+    }
+
+    quote! {
+        if enable_recording {
+            #(#recordings)*
+        }
+    }
+}
+
+fn generate_setup_outputs_replay(cycler: &Cycler) -> TokenStream {
+    get_setup_outputs(cycler).into_iter().map(|name| {
+        let error_message = format!("failed to replay {name}");
+        quote! {
+            own_database_reference.main_outputs.#name = bincode::deserialize_from(&mut recording_frame).wrap_err(#error_message)?;
+        }
+    }).collect()
+}
+
+fn get_cross_inputs(cycler: &Cycler) -> Vec<Field> {
     cycler
         .setup_nodes
         .iter()
@@ -503,15 +655,64 @@ fn get_cross_inputs(cycler: &Cycler) -> HashSet<Field> {
                 })
                 .cloned()
         })
+        .unique()
+        // recordings have to be replayable by other builds, therefore the order must not depend on
+        // the (unstable) node order
+        .sorted_by_cached_key(cross_input_sort_key)
         .collect()
 }
 
-fn generate_cross_inputs_recording(
-    cycler: &Cycler,
-    cross_inputs: impl IntoIterator<Item = Field>,
-) -> TokenStream {
-    let recordings = cross_inputs.into_iter().map(|field| {
-        let error_message = match &field {
+fn cross_input_sort_key(field: &Field) -> (u8, Option<String>, String, String, String) {
+    let (kind, cycler_instance, data_type, name, path) = match field {
+        Field::CyclerState {
+            data_type,
+            name,
+            path,
+        } => (0, None, data_type, name, path),
+        Field::Input {
+            cycler_instance,
+            data_type,
+            name,
+            path,
+        } => (1, cycler_instance.clone(), data_type, name, path),
+        Field::PerceptionInput {
+            cycler_instance,
+            data_type,
+            name,
+            path,
+        } => (2, Some(cycler_instance.clone()), data_type, name, path),
+        Field::RequiredInput {
+            cycler_instance,
+            data_type,
+            name,
+            path,
+        } => (3, cycler_instance.clone(), data_type, name, path),
+        _ => panic!("unexpected field {field:?}"),
+    };
+    let path = path
+        .segments
+        .iter()
+        .map(|segment| {
+            format!(
+                "{}{}{}",
+                if segment.is_variable { "$" } else { "" },
+                segment.name,
+                if segment.is_optional { "?" } else { "" },
+            )
+        })
+        .join(".");
+    (
+        kind,
+        cycler_instance,
+        name.to_string(),
+        path,
+        data_type.to_token_stream().to_string(),
+    )
+}
+
+fn generate_cross_inputs_recording(cycler: &Cycler, cross_inputs: &[Field]) -> TokenStream {
+    let recordings = cross_inputs.iter().map(|field| {
+        let error_message = match field {
             Field::CyclerState { name, .. } => format!("failed to record cycler state {name}"),
             Field::Input { cycler_instance: Some(_), name, .. } => format!("failed to record input {name}"),
             Field::PerceptionInput { name, .. } => format!("failed to record perception input {name}"),
@@ -522,7 +723,7 @@ fn generate_cross_inputs_recording(
             Field::CyclerState { path, .. } => {
                 let accessor = path_to_accessor_token_stream(
                     quote! { self.cycler_state },
-                    &path,
+                    path,
                     ReferenceKind::Immutable,
                     cycler,
                 );
@@ -539,7 +740,7 @@ fn generate_cross_inputs_recording(
                 let database_prefix = quote! { #identifier.main_outputs };
                 let accessor = path_to_accessor_token_stream(
                     database_prefix,
-                    &path,
+                    path,
                     ReferenceKind::Immutable,
                     cycler,
                 );
@@ -552,7 +753,7 @@ fn generate_cross_inputs_recording(
                     format_ident!("{}", cycler_instance.to_case(Case::Snake));
                 let accessor = path_to_accessor_token_stream(
                     quote! { database },
-                    &path,
+                    path,
                     ReferenceKind::Immutable,
                     cycler,
                 );
@@ -596,12 +797,12 @@ fn generate_cross_inputs_recording(
                 let database_prefix = quote! { #identifier.main_outputs };
                 let accessor = path_to_accessor_token_stream(
                     database_prefix,
-                    &path,
+                    path,
                     ReferenceKind::Immutable,
                     cycler,
                 );
                 quote! {
-                    &#accessor .unwrap()
+                    &#accessor
                 }
             }
             _ => panic!("unexpected field {field:?}"),
@@ -622,6 +823,77 @@ fn generate_cross_inputs_recording(
     }
 }
 
+fn generate_cross_inputs_replay(cycler: &Cycler, cross_inputs: &[Field]) -> TokenStream {
+    cross_inputs
+        .iter()
+        .map(|field| {
+            let identifier = replayed_cross_input_identifier(cross_inputs, field);
+            match field {
+                Field::CyclerState {
+                    data_type,
+                    name,
+                    path,
+                } => {
+                    let error_message = format!("failed to replay cycler state {name}");
+                    let accessor = path_to_accessor_token_stream(
+                        quote! { self.cycler_state },
+                        path,
+                        ReferenceKind::Mutable,
+                        cycler,
+                    );
+                    if path.contains_optional() {
+                        quote! {
+                            let #identifier: #data_type = bincode::deserialize_from(&mut recording_frame).wrap_err(#error_message)?;
+                            if let (Some(cycler_state), Some(value)) = (#accessor, #identifier) {
+                                *cycler_state = value;
+                            }
+                        }
+                    } else {
+                        quote! {
+                            *#accessor = bincode::deserialize_from(&mut recording_frame).wrap_err(#error_message)?;
+                        }
+                    }
+                }
+                Field::Input {
+                    cycler_instance: Some(_),
+                    data_type,
+                    name,
+                    ..
+                }
+                | Field::RequiredInput {
+                    cycler_instance: Some(_),
+                    data_type,
+                    name,
+                    ..
+                } => {
+                    let error_message = format!("failed to replay input {name}");
+                    quote! {
+                        let #identifier: #data_type = bincode::deserialize_from(&mut recording_frame).wrap_err(#error_message)?;
+                    }
+                }
+                Field::PerceptionInput {
+                    data_type, name, ..
+                } => {
+                    let error_message = format!("failed to replay perception input {name}");
+                    quote! {
+                        let #identifier: [std::collections::BTreeMap<std::time::SystemTime, Vec<#data_type>>; 2] =
+                            bincode::deserialize_from(&mut recording_frame).wrap_err(#error_message)?;
+                    }
+                }
+                _ => panic!("unexpected field {field:?}"),
+            }
+        })
+        .collect()
+}
+
+fn replayed_cross_input_identifier(cross_inputs: &[Field], field: &Field) -> Ident {
+    let index = cross_inputs
+        .iter()
+        .position(|cross_input| cross_input == field)
+        .unwrap_or_else(|| panic!("unexpected field {field:?} which is not a cross input"));
+    format_ident!("replayed_cross_input_{index}")
+}
+
 fn generate_perception_cycler_updates(cyclers: &Cyclers) -> TokenStream {
     cyclers
         .instances_with(CyclerKind::Perception)
@@ -635,25 +907,17 @@ fn generate_perception_cycler_updates(cyclers: &Cyclers) -> TokenStream {
         .collect()
 }
 
-fn generate_node_execution(
-    node: &Node,
-    cycler: &Cycler,
-    recording_generation: RecordingGeneration,
-) -> TokenStream {
-    let are_required_inputs_some = generate_required_input_condition(node, cycler);
+fn generate_node_execution(node: &Node, cycler: &Cycler, mode: CyclerMode) -> TokenStream {
+    let are_required_inputs_some = generate_required_input_condition(node, cycler, mode);
     let node_name = &node.name;
     let node_module = &node.module;
     let node_member = format_ident!("{}", node.name.to_case(Case::Snake));
-    let context_initializers = generate_context_initializers(node, cycler);
-    let recording_error_message = format!("failed to record `{}`", node.name);
+    let context_initializers = generate_context_initializers(node, cycler, mode);
     let cycle_error_message = format!("failed to execute cycle of `{}`", node.name);
-    let database_updates = generate_database_updates(node, recording_generation);
+    let database_updates = generate_database_updates(node);
     let database_updates_from_defaults = generate_database_updates_from_defaults(node);
     quote! {
         {
-            if enable_recording {
-                bincode::serialize_into(&mut recording_frame, &self.#node_member).wrap_err(#recording_error_message)?;
-            }
             #[allow(clippy::needless_else)]
             if #are_required_inputs_some {
                 let main_outputs = {
@@ -674,22 +938,42 @@ fn generate_node_execution(
     }
 }
 
-enum RecordingGeneration {
-    Generate,
-    Skip,
+#[derive(Clone, Copy)]
+enum CyclerMode<'a> {
+    Run,
+    Replay { cross_inputs: &'a [Field] },
 }
 
-fn generate_required_input_condition(node: &Node, cycler: &Cycler) -> TokenStream {
+fn generate_required_input_condition(
+    node: &Node,
+    cycler: &Cycler,
+    mode: CyclerMode,
+) -> TokenStream {
     let conditions = node
         .contexts
         .cycle_context
         .iter()
-        .filter_map(|field| match field {
-            Field::RequiredInput {
-                cycler_instance,
-                path,
-                ..
-            } => {
+        .filter_map(|field| match (field, mode) {
+            (
+                Field::RequiredInput {
+                    cycler_instance: Some(_),
+                    ..
+                },
+                CyclerMode::Replay { cross_inputs },
+            ) => {
+                let identifier = replayed_cross_input_identifier(cross_inputs, field);
+                Some(quote! {
+                    #identifier.is_some()
+                })
+            }
+            (
+                Field::RequiredInput {
+                    cycler_instance,
+                    path,
+                    ..
+                },
+                _,
+            ) => {
                 let database_prefix = match cycler_instance {
                     Some(cycler_instance) => {
                         let identifier =
@@ -718,7 +1002,7 @@ fn generate_required_input_condition(node: &Node, cycler: &Cycler) -> TokenStrea
     }
 }
 
-fn generate_context_initializers(node: &Node, cycler: &Cycler) -> TokenStream {
+fn generate_context_initializers(node: &Node, cycler: &Cycler, mode: CyclerMode) -> TokenStream {
     let initializers = node
             .contexts
             .cycle_context
@@ -895,32 +1179,81 @@ fn generate_context_initializers(node: &Node, cycler: &Cycler) -> TokenStream {
                         #accessor .unwrap()
                     }
                 }
+            })
+            .zip(&node.contexts.cycle_context)
+            .map(|(initializer, field)| match mode {
+                CyclerMode::Run => initializer,
+                CyclerMode::Replay { cross_inputs } => {
+                    generate_replayed_cross_input_initializer(field, cross_inputs)
+                        .unwrap_or(initializer)
+                }
             });
     quote! {
         #(#initializers,)*
     }
 }
 
-fn generate_database_updates(
-    node: &Node,
-    recording_generation: RecordingGeneration,
-) -> TokenStream {
+fn generate_replayed_cross_input_initializer(
+    field: &Field,
+    cross_inputs: &[Field],
+) -> Option<TokenStream> {
+    match field {
+        Field::Input {
+            cycler_instance: Some(_),
+            path,
+            ..
+        } => {
+            let identifier = replayed_cross_input_identifier(cross_inputs, field);
+            Some(if path.contains_optional() {
+                quote! { #identifier.as_ref() }
+            } else {
+                quote! { &#identifier }
+            })
+        }
+        Field::PerceptionInput { path, .. } => {
+            let identifier = replayed_cross_input_identifier(cross_inputs, field);
+            let value_accessor = if path.contains_optional() {
+                quote! { value.as_ref() }
+            } else {
+                quote! { value }
+            };
+            Some(quote! {
+                framework::PerceptionInput {
+                    persistent: #identifier[0]
+                        .iter()
+                        .map(|(system_time, values)| (
+                            *system_time,
+                            values.iter().map(|value| #value_accessor).collect(),
+                        ))
+                        .collect(),
+                    temporary: #identifier[1]
+                        .iter()
+                        .map(|(system_time, values)| (
+                            *system_time,
+                            values.iter().map(|value| #value_accessor).collect(),
+                        ))
+                        .collect(),
+                }
+            })
+        }
+        Field::RequiredInput {
+            cycler_instance: Some(_),
+            ..
+        } => {
+            let identifier = replayed_cross_input_identifier(cross_inputs, field);
+            Some(quote! { #identifier.as_ref().unwrap() })
+        }
+        _ => None,
+    }
+}
+
+fn generate_database_updates(node: &Node) -> TokenStream {
     node.contexts
         .main_outputs
         .iter()
         .filter_map(|field| match field {
             Field::MainOutput { name, .. } => {
-                let error_message = format!("failed to record {name}");
-                let recording_serialization = match recording_generation {
-                    RecordingGeneration::Generate => quote! {
-                        if enable_recording {
-                            bincode::serialize_into(&mut recording_frame, &main_outputs.#name.value).wrap_err(#error_message)?;
-                        }
-                    },
-                    RecordingGeneration::Skip => Default::default(),
-                };
                 let setter = quote! {
-                    #recording_serialization
                     own_database_reference.main_outputs.#name = main_outputs.#name.value;
                 };
                 Some(setter)
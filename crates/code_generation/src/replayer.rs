@@ -0,0 +1,110 @@
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use source_analyzer::cyclers::Cyclers;
+
+use crate::run::{generate_cycler_constructors, generate_future_queues, generate_multiple_buffers};
+
+pub fn generate_replayer_struct(cyclers: &Cyclers) -> TokenStream {
+    let cycler_fields = generate_cycler_fields(cyclers);
+    let construct_multiple_buffers = generate_multiple_buffers(cyclers);
+    let construct_future_queues = generate_future_queues(cyclers);
+    // 2 communication writer slots + n reader slots for other cyclers
+    let number_of_parameter_slots = 2 + cyclers.number_of_instances();
+    let construct_cyclers = generate_cycler_constructors(cyclers);
+    let cycler_identifiers = cyclers
+        .instances()
+        .map(|(_cycler, instance)| format_ident!("{}_cycler", instance.to_case(Case::Snake)));
+    let replay_arms = generate_replay_arms(cyclers);
+
+    quote! {
+        pub struct Replayer<HardwareInterface> {
+            communication_server: communication::server::Runtime<crate::structs::Parameters>,
+            #cycler_fields
+        }
+
+        impl<HardwareInterface> Replayer<HardwareInterface>
+        where
+            HardwareInterface: crate::HardwareInterface + Send + Sync + 'static
+        {
+            #[allow(clippy::redundant_clone)]
+            pub fn new(
+                hardware_interface: std::sync::Arc<HardwareInterface>,
+                addresses: Option<framework::CommunicationAddresses>,
+                parameters_directory: impl std::convert::AsRef<std::path::Path> + std::marker::Send + std::marker::Sync + 'static,
+                body_id: String,
+                head_id: String,
+                keep_running: tokio_util::sync::CancellationToken,
+            ) -> color_eyre::Result<Self> {
+                use color_eyre::eyre::WrapErr;
+
+                #construct_multiple_buffers
+                #construct_future_queues
+                // replayed cyclers never record, the receiver is only kept to satisfy the cyclers
+                let (recording_sender, _recording_receiver) = std::sync::mpsc::sync_channel(0);
+                let cycler_instances_to_be_recorded = std::collections::HashSet::<String>::new();
+
+                let communication_server = communication::server::Runtime::start(
+                    addresses, None, parameters_directory, body_id, head_id, #number_of_parameter_slots, keep_running)
+                    .wrap_err("failed to start communication server")?;
+
+                #construct_cyclers
+
+                Ok(Self {
+                    communication_server,
+                    #(#cycler_identifiers,)*
+                })
+            }
+
+            pub fn replay(
+                &mut self,
+                instance_name: &str,
+                now: std::time::SystemTime,
+                recording_frame: &[u8],
+            ) -> color_eyre::Result<()> {
+                use color_eyre::eyre::WrapErr;
+
+                match instance_name {
+                    #replay_arms
+                    _ => color_eyre::eyre::bail!("unexpected cycler instance `{instance_name}`"),
+                }
+            }
+
+            pub fn join(self) -> color_eyre::Result<()> {
+                use color_eyre::eyre::WrapErr;
+
+                match self.communication_server.join() {
+                    Ok(result) => result.wrap_err("communication server exited with error"),
+                    Err(error) => color_eyre::eyre::bail!("failed to join communication server: {error:?}"),
+                }
+            }
+        }
+    }
+}
+
+fn generate_cycler_fields(cyclers: &Cyclers) -> TokenStream {
+    cyclers
+        .instances()
+        .map(|(cycler, instance)| {
+            let field_name = format_ident!("{}_cycler", instance.to_case(Case::Snake));
+            let cycler_module_name = format_ident!("{}", cycler.name.to_case(Case::Snake));
+            quote! {
+                #field_name: crate::cyclers::#cycler_module_name::Cycler<HardwareInterface>,
+            }
+        })
+        .collect()
+}
+
+fn generate_replay_arms(cyclers: &Cyclers) -> TokenStream {
+    cyclers
+        .instances()
+        .map(|(_cycler, instance)| {
+            let cycler_variable_identifier =
+                format_ident!("{}_cycler", instance.to_case(Case::Snake));
+            let error_message = format!("failed to replay cycler `{instance}`");
+            quote! {
+                #instance => self.#cycler_variable_identifier.replay(now, recording_frame).wrap_err(#error_message),
+            }
+        })
+        .collect()
+}
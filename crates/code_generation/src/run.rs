@@ -19,7 +19,8 @@ pub fn generate_run_function(cyclers: &Cyclers) -> TokenStream {
         #[allow(clippy::redundant_clone)]
         pub fn run(
             hardware_interface: std::sync::Arc<impl crate::HardwareInterface + Send + Sync + 'static>,
-            addresses: Option<impl tokio::net::ToSocketAddrs + std::marker::Send + std::marker::Sync + 'static>,
+            addresses: Option<framework::CommunicationAddresses>,
+            audit_log_path: Option<std::path::PathBuf>,
             parameters_directory: impl std::convert::AsRef<std::path::Path> + std::marker::Send + std::marker::Sync + 'static,
             body_id: String,
             head_id: String,
@@ -34,7 +35,7 @@ pub fn generate_run_function(cyclers: &Cyclers) -> TokenStream {
             let (recording_sender, recording_receiver) = std::sync::mpsc::sync_channel(420);
 
             let communication_server = communication::server::Runtime::start(
-                addresses, parameters_directory, body_id, head_id, #number_of_parameter_slots, keep_running.clone())
+                addresses, audit_log_path, parameters_directory, body_id, head_id, #number_of_parameter_slots, keep_running.clone())
                 .wrap_err("failed to start communication server")?;
 
             let recording_thread = #recording_thread;
@@ -79,7 +80,7 @@ pub fn generate_run_function(cyclers: &Cyclers) -> TokenStream {
     }
 }
 
-fn generate_multiple_buffers(cyclers: &Cyclers) -> TokenStream {
+pub(crate) fn generate_multiple_buffers(cyclers: &Cyclers) -> TokenStream {
     // 2 writer slots + n-1 reader slots for other cyclers + 1 reader slot for communication
     let slots_for_real_time_cyclers: TokenStream = repeat(quote! { Default::default(), })
         .take(2 + cyclers.number_of_instances())
@@ -103,7 +104,7 @@ fn generate_multiple_buffers(cyclers: &Cyclers) -> TokenStream {
     }).collect()
 }
 
-fn generate_future_queues(cyclers: &Cyclers) -> TokenStream {
+pub(crate) fn generate_future_queues(cyclers: &Cyclers) -> TokenStream {
     cyclers
         .instances_with(CyclerKind::Perception)
         .map(|(_cycler, instance)| {
@@ -130,7 +131,7 @@ fn generate_recording_thread(cyclers: &Cyclers) -> TokenStream {
         let instance_name_snake_case = format_ident!("{}", instance.to_case(Case::Snake));
         let error_message = format!("failed to write into recording file for {instance}");
         quote! {
-            crate::cyclers::RecordingFrame::#instance_name { data } => #instance_name_snake_case.write_all(data.as_slice()).wrap_err(#error_message)?,
+            crate::cyclers::RecordingFrame::#instance_name { timestamp, data } => bincode::serialize_into(&mut #instance_name_snake_case, &(timestamp, data)).wrap_err(#error_message)?,
         }
     });
 
@@ -141,7 +142,6 @@ fn generate_recording_thread(cyclers: &Cyclers) -> TokenStream {
                 .name("Recording".to_string())
                 .spawn(move || -> color_eyre::Result<()> {
                     let result = (|| {
-                        use std::io::Write;
                         let seconds = std::time::SystemTime::now().duration_since(std::time::SystemTime::UNIX_EPOCH).unwrap().as_secs();
                         #(#file_creations)*
                         for recording_frame in recording_receiver {
@@ -160,7 +160,7 @@ fn generate_recording_thread(cyclers: &Cyclers) -> TokenStream {
     }
 }
 
-fn generate_cycler_constructors(cyclers: &Cyclers) -> TokenStream {
+pub(crate) fn generate_cycler_constructors(cyclers: &Cyclers) -> TokenStream {
     cyclers.instances().map(|(cycler, instance)| {
         let instance_name_snake_case = instance.to_case(Case::Snake);
         let cycler_database_changed_identifier = format_ident!("{instance_name_snake_case}_changed");
@@ -172,6 +172,7 @@ fn generate_cycler_constructors(cyclers: &Cyclers) -> TokenStream {
         let own_reader_identifier = format_ident!("{instance_name_snake_case}_reader");
         let own_subscribed_outputs_writer_identifier = format_ident!("{instance_name_snake_case}_subscribed_outputs_writer");
         let own_subscribed_outputs_reader_identifier = format_ident!("{instance_name_snake_case}_subscribed_outputs_reader");
+        let own_injections_reader_identifier = format_ident!("{instance_name_snake_case}_injections_reader");
         let own_producer_identifier = match cycler.kind {
             CyclerKind::Perception  => {
                 let own_producer_identifier = format_ident!("{instance_name_snake_case}_producer");
@@ -201,13 +202,20 @@ fn generate_cycler_constructors(cyclers: &Cyclers) -> TokenStream {
                 Default::default(),
                 Default::default(),
             ]);
+            let #own_injections_reader_identifier = communication_server.register_cycler_instance(
+                #cycler_instance_name,
+                #cycler_database_changed_identifier.clone(),
+                #own_reader_identifier.clone(),
+                #own_subscribed_outputs_writer_identifier,
+            );
             let enable_recording = cycler_instances_to_be_recorded.contains(#cycler_instance_name);
             let #cycler_variable_identifier = crate::cyclers::#cycler_module_name::Cycler::new(
                 crate::cyclers::#cycler_module_name::CyclerInstance::#cycler_instance_name_identifier,
                 hardware_interface.clone(),
                 #own_writer_identifier,
-                #cycler_database_changed_identifier.clone(),
+                #cycler_database_changed_identifier,
                 #own_subscribed_outputs_reader_identifier,
+                #own_injections_reader_identifier,
                 communication_server.get_parameters_reader(),
                 #own_producer_identifier
                 #(#other_cycler_inputs,)*
@@ -215,12 +223,6 @@ fn generate_cycler_constructors(cyclers: &Cyclers) -> TokenStream {
                 enable_recording,
             )
             .wrap_err(#error_message)?;
-            communication_server.register_cycler_instance(
-                #cycler_instance_name,
-                #cycler_database_changed_identifier,
-                #own_reader_identifier.clone(),
-                #own_subscribed_outputs_writer_identifier,
-            );
         }
     })
     .collect()
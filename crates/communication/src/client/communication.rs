@@ -17,7 +17,7 @@ use crate::{
         parameter_subscription_manager::{self, parameter_subscription_manager},
         SubscriberMessage,
     },
-    messages::{Fields, Format, Path},
+    messages::{Fields, Format, Path, UpdatePolicy},
 };
 
 use super::{
@@ -115,6 +115,7 @@ impl Communication {
         &self,
         output: CyclerOutput,
         format: Format,
+        update_policy: UpdatePolicy,
     ) -> (Uuid, Receiver<SubscriberMessage>) {
         let (subscriber_sender, subscriber_receiver) = mpsc::channel(10);
         let (response_sender, response_receiver) = oneshot::channel();
@@ -122,6 +123,7 @@ impl Communication {
             .send(output_subscription_manager::Message::Subscribe {
                 output,
                 format,
+                update_policy,
                 subscriber: subscriber_sender,
                 response_sender,
             })
@@ -16,6 +16,7 @@ use crate::{
     messages::{
         Fields, Format, OutputsRequest, Request,
         TextualDataOrBinaryReference::{self, BinaryReference, TextualData},
+        UpdatePolicy,
     },
 };
 
@@ -30,6 +31,7 @@ pub enum Message {
     Subscribe {
         output: CyclerOutput,
         format: Format,
+        update_policy: UpdatePolicy,
         subscriber: mpsc::Sender<SubscriberMessage>,
         response_sender: oneshot::Sender<Uuid>,
     },
@@ -50,11 +52,14 @@ pub enum Message {
     },
 }
 
+/// Subscribers of the same output with different formats or update policies need separate subscriptions
+type SubscribedOutput = (CyclerOutput, Format, UpdatePolicy);
+
 #[derive(Default)]
 struct SubscriptionManager {
-    ids_to_outputs: HashMap<usize, (CyclerOutput, Format)>,
+    ids_to_outputs: HashMap<usize, SubscribedOutput>,
     outputs_to_subscribers:
-        HashMap<(CyclerOutput, Format), HashMap<Uuid, mpsc::Sender<SubscriberMessage>>>,
+        HashMap<SubscribedOutput, HashMap<Uuid, mpsc::Sender<SubscriberMessage>>>,
 }
 
 pub async fn output_subscription_manager(
@@ -68,7 +73,7 @@ pub async fn output_subscription_manager(
     let mut requester = None;
     let mut fields = None;
     let mut binary_data_waiting_for_references: HashMap<usize, Vec<u8>> = HashMap::new();
-    let mut binary_references_waiting_for_data: HashMap<usize, CyclerOutput> = HashMap::new();
+    let mut binary_references_waiting_for_data: HashMap<usize, SubscribedOutput> = HashMap::new();
 
     while let Some(message) = receiver.recv().await {
         match message {
@@ -76,11 +81,14 @@ pub async fn output_subscription_manager(
                 requester: new_requester,
             } => {
                 assert!(manager.ids_to_outputs.is_empty());
-                for ((output, format), subscribers) in &manager.outputs_to_subscribers {
+                for ((output, format, update_policy), subscribers) in
+                    &manager.outputs_to_subscribers
+                {
                     let subscribers = subscribers.values().cloned().collect();
                     if let Some(subscription_id) = subscribe(
                         output.clone(),
                         *format,
+                        *update_policy,
                         subscribers,
                         &id_tracker,
                         &responder,
@@ -90,7 +98,7 @@ pub async fn output_subscription_manager(
                     {
                         manager
                             .ids_to_outputs
-                            .insert(subscription_id, (output.clone(), *format));
+                            .insert(subscription_id, (output.clone(), *format, *update_policy));
                     }
                 }
                 match query_output_fields(sender.clone(), &id_tracker, &responder, &new_requester)
@@ -109,6 +117,7 @@ pub async fn output_subscription_manager(
             Message::Subscribe {
                 output,
                 format,
+                update_policy,
                 subscriber: output_sender,
                 response_sender,
             } => {
@@ -120,6 +129,7 @@ pub async fn output_subscription_manager(
                             uuid,
                             output,
                             format,
+                            update_policy,
                             output_sender,
                             &id_tracker,
                             &responder,
@@ -196,7 +206,7 @@ pub async fn output_subscription_manager(
                                     }
                                 } else {
                                     binary_references_waiting_for_data
-                                        .insert(reference_id, output.0.clone());
+                                        .insert(reference_id, output.clone());
                                 }
                             }
                         }
@@ -216,9 +226,7 @@ pub async fn output_subscription_manager(
             Message::UpdateBinary { referenced_items } => {
                 for (reference_id, data) in referenced_items {
                     if let Some(output) = binary_references_waiting_for_data.get(&reference_id) {
-                        let subscribers = manager
-                            .outputs_to_subscribers
-                            .get(&(output.clone(), Format::Binary));
+                        let subscribers = manager.outputs_to_subscribers.get(output);
                         if let Some(senders) = subscribers {
                             for sender in senders.values() {
                                 if let Err(error) = sender
@@ -276,6 +284,7 @@ async fn add_subscription(
     uuid: Uuid,
     output: CyclerOutput,
     format: Format,
+    update_policy: UpdatePolicy,
     output_sender: mpsc::Sender<SubscriberMessage>,
     id_tracker: &mpsc::Sender<id_tracker::Message>,
     responder: &mpsc::Sender<responder::Message>,
@@ -283,7 +292,7 @@ async fn add_subscription(
 ) {
     match manager
         .outputs_to_subscribers
-        .entry((output.clone(), format))
+        .entry((output.clone(), format, update_policy))
     {
         Entry::Occupied(mut entry) => {
             entry.get_mut().insert(uuid, output_sender);
@@ -293,6 +302,7 @@ async fn add_subscription(
                 if let Some(subscription_id) = subscribe(
                     output.clone(),
                     format,
+                    update_policy,
                     vec![output_sender.clone()],
                     id_tracker,
                     responder,
@@ -302,7 +312,7 @@ async fn add_subscription(
                 {
                     manager
                         .ids_to_outputs
-                        .insert(subscription_id, (output, format));
+                        .insert(subscription_id, (output, format, update_policy));
                 }
             };
             entry.insert(HashMap::new()).insert(uuid, output_sender);
@@ -313,6 +323,7 @@ async fn add_subscription(
 async fn subscribe(
     output: CyclerOutput,
     format: Format,
+    update_policy: UpdatePolicy,
     subscribers: Vec<mpsc::Sender<SubscriberMessage>>,
     id_tracker: &mpsc::Sender<id_tracker::Message>,
     responder: &mpsc::Sender<responder::Message>,
@@ -339,6 +350,7 @@ async fn subscribe(
         cycler_instance: output.cycler.to_string(),
         path,
         format,
+        update_policy,
     });
     if let Err(error) = requester.send(request).await {
         error!("{error}");
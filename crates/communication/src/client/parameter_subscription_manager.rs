@@ -14,7 +14,8 @@ use crate::{
         id_tracker::{self, get_message_id},
         responder, SubscriberMessage,
     },
-    messages::{ParametersRequest, Path, Request},
+    messages::{ParametersRequest, PatchOperation, Path, Request},
+    patch::apply,
 };
 
 use super::responder::Response;
@@ -37,6 +38,10 @@ pub enum Message {
         subscription_id: usize,
         data: Value,
     },
+    Patch {
+        subscription_id: usize,
+        patch: Vec<PatchOperation>,
+    },
     UpdateFields {
         fields: BTreeSet<Path>,
     },
@@ -52,6 +57,8 @@ pub enum Message {
 #[derive(Default)]
 struct SubscriptionManager {
     ids_to_paths: HashMap<usize, Path>,
+    /// Latest value of each subscription, patches from the server are applied to it
+    ids_to_values: HashMap<usize, Value>,
     paths_to_subscribers: HashMap<Path, HashMap<Uuid, mpsc::Sender<SubscriberMessage>>>,
 }
 
@@ -93,6 +100,7 @@ pub async fn parameter_subscription_manager(
             Message::Disconnect => {
                 requester = None;
                 manager.ids_to_paths.clear();
+                manager.ids_to_values.clear();
             }
             Message::Subscribe {
                 path,
@@ -137,6 +145,7 @@ pub async fn parameter_subscription_manager(
                 for subscription_id in subscriptions_to_remove {
                     if let Some(requester) = &requester {
                         manager.ids_to_paths.remove(&subscription_id);
+                        manager.ids_to_values.remove(&subscription_id);
                         unsubscribe(subscription_id, &id_tracker, &responder, requester).await;
                     }
                 }
@@ -145,22 +154,24 @@ pub async fn parameter_subscription_manager(
                 subscription_id,
                 data,
             } => {
-                let Some(path) = manager.ids_to_paths.get(&subscription_id) else {
-                    return warn!("Unknown subscription_id: {subscription_id}");
-                };
-                let Some(senders) = manager.paths_to_subscribers.get(path) else {
-                    return warn!("Unknown subscription_id: {subscription_id}");
+                manager.ids_to_values.insert(subscription_id, data.clone());
+                notify_subscribers(&manager, subscription_id, data).await;
+                let _ = update_sender.send(());
+            }
+            Message::Patch {
+                subscription_id,
+                patch,
+            } => {
+                let Some(value) = manager.ids_to_values.get_mut(&subscription_id) else {
+                    warn!("Patch for subscription_id {subscription_id} without previous value");
+                    continue;
                 };
-                for sender in senders.values() {
-                    if let Err(error) = sender
-                        .send(SubscriberMessage::Update {
-                            value: data.clone(),
-                        })
-                        .await
-                    {
-                        error!("{error}");
-                    }
+                if let Err(error) = apply(value, &patch) {
+                    error!("Failed to apply patch for subscription_id {subscription_id}: {error}");
+                    continue;
                 }
+                let value = value.clone();
+                notify_subscribers(&manager, subscription_id, value).await;
                 let _ = update_sender.send(());
             }
             Message::UpdateFields { fields: new_fields } => {
@@ -195,6 +206,25 @@ pub async fn parameter_subscription_manager(
     info!("Finished manager");
 }
 
+async fn notify_subscribers(manager: &SubscriptionManager, subscription_id: usize, value: Value) {
+    let Some(path) = manager.ids_to_paths.get(&subscription_id) else {
+        return warn!("Unknown subscription_id: {subscription_id}");
+    };
+    let Some(senders) = manager.paths_to_subscribers.get(path) else {
+        return warn!("Unknown subscription_id: {subscription_id}");
+    };
+    for sender in senders.values() {
+        if let Err(error) = sender
+            .send(SubscriberMessage::Update {
+                value: value.clone(),
+            })
+            .await
+        {
+            error!("{error}");
+        }
+    }
+}
+
 async fn query_parameter_hierarchy(
     manager: mpsc::Sender<Message>,
     id_tracker: &mpsc::Sender<id_tracker::Message>,
@@ -318,6 +348,7 @@ async fn subscribe(
     let request = Request::Parameters(ParametersRequest::Subscribe {
         id: message_id,
         path,
+        patches: true,
     });
     requester.send(request).await.unwrap();
     spawn(async move {
@@ -83,6 +83,20 @@ pub async fn receiver(
                                     error!("{error}");
                                 }
                             }
+                            ParametersResponse::SubscribedPatch {
+                                subscription_id,
+                                patch,
+                            } => {
+                                if let Err(error) = parameter_subscription_manager
+                                    .send(parameter_subscription_manager::Message::Patch {
+                                        subscription_id,
+                                        patch,
+                                    })
+                                    .await
+                                {
+                                    error!("{error}");
+                                }
+                            }
                             ParametersResponse::Update { id, result } => {
                                 respond(&responder, id, Response::Update(result)).await
                             }
@@ -1,4 +1,5 @@
 pub mod client;
 pub mod messages;
+pub mod patch;
 #[cfg(feature = "server")]
 pub mod server;
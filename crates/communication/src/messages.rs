@@ -1,4 +1,7 @@
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    time::Duration,
+};
 
 use parameters::directory::Scope;
 use serde::{Deserialize, Serialize};
@@ -81,6 +84,8 @@ pub enum OutputsRequest {
         cycler_instance: CyclerInstance,
         path: Path,
         format: Format,
+        #[serde(default)]
+        update_policy: UpdatePolicy,
     },
     Unsubscribe {
         id: usize,
@@ -89,6 +94,45 @@ pub enum OutputsRequest {
     UnsubscribeEverything,
 }
 
+/// Limits how often a subscribed output is sent, e.g. to spare the network from large outputs
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum UpdatePolicy {
+    #[default]
+    EveryCycle,
+    EveryNthCycle {
+        n: usize,
+    },
+    MaximumRate {
+        minimum_interval: Duration,
+    },
+}
+
+impl UpdatePolicy {
+    pub fn validate(&self) -> Result<(), Reason> {
+        match self {
+            UpdatePolicy::EveryNthCycle { n: 0 } => {
+                Err("update policy EveryNthCycle requires n > 0".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether an update is due, given the cycles and time passed since the last sent update
+    pub fn is_due(
+        &self,
+        cycles_since_last_update: usize,
+        time_since_last_update: Duration,
+    ) -> bool {
+        match self {
+            UpdatePolicy::EveryCycle => true,
+            UpdatePolicy::EveryNthCycle { n } => cycles_since_last_update >= *n,
+            UpdatePolicy::MaximumRate { minimum_interval } => {
+                time_since_last_update >= *minimum_interval
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum TextualOutputsResponse {
     GetFields {
@@ -131,14 +175,39 @@ pub enum BinaryOutputsResponse {
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum ParametersRequest {
-    GetFields { id: usize },
-    GetCurrent { id: usize, path: Path },
-    Subscribe { id: usize, path: Path },
-    Unsubscribe { id: usize, subscription_id: usize },
+    GetFields {
+        id: usize,
+    },
+    GetCurrent {
+        id: usize,
+        path: Path,
+    },
+    Subscribe {
+        id: usize,
+        path: Path,
+        /// Send changes as `SubscribedPatch` relative to the last sent value instead of the whole
+        /// value at `path`
+        #[serde(default)]
+        patches: bool,
+    },
+    Unsubscribe {
+        id: usize,
+        subscription_id: usize,
+    },
     UnsubscribeEverything,
-    Update { id: usize, path: Path, data: Value },
-    LoadFromDisk { id: usize },
-    StoreToDisk { id: usize, scope: Scope, path: Path },
+    Update {
+        id: usize,
+        path: Path,
+        data: Value,
+    },
+    LoadFromDisk {
+        id: usize,
+    },
+    StoreToDisk {
+        id: usize,
+        scope: Scope,
+        path: Path,
+    },
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -163,6 +232,10 @@ pub enum ParametersResponse {
         subscription_id: usize,
         data: Value,
     },
+    SubscribedPatch {
+        subscription_id: usize,
+        patch: Vec<PatchOperation>,
+    },
     Update {
         id: usize,
         result: Result<(), Reason>,
@@ -177,6 +250,14 @@ pub enum ParametersResponse {
     },
 }
 
+/// A JSON patch (RFC 6902) style operation, `path` is a JSON pointer (RFC 6901) into the value
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum Format {
     Textual,
@@ -0,0 +1,190 @@
+use serde_json::Value;
+
+use crate::messages::PatchOperation;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PatchError {
+    #[error("path {0:?} is not a JSON pointer")]
+    InvalidPath(String),
+    #[error("path {0:?} does not exist")]
+    PathNotFound(String),
+    #[error("parent of path {0:?} is not an object")]
+    ParentNotAnObject(String),
+}
+
+/// Operations that turn `old` into `new`, objects are compared field by field while all other
+/// values (including arrays) are replaced as a whole
+pub fn diff(old: &Value, new: &Value) -> Vec<PatchOperation> {
+    let mut patch = Vec::new();
+    diff_into(old, new, String::new(), &mut patch);
+    patch
+}
+
+fn diff_into(old: &Value, new: &Value, pointer: String, patch: &mut Vec<PatchOperation>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for key in old.keys().filter(|key| !new.contains_key(*key)) {
+                patch.push(PatchOperation::Remove {
+                    path: child_pointer(&pointer, key),
+                });
+            }
+            for (key, new_value) in new {
+                let path = child_pointer(&pointer, key);
+                match old.get(key) {
+                    Some(old_value) => diff_into(old_value, new_value, path, patch),
+                    None => patch.push(PatchOperation::Add {
+                        path,
+                        value: new_value.clone(),
+                    }),
+                }
+            }
+        }
+        (old, new) if old != new => patch.push(PatchOperation::Replace {
+            path: pointer,
+            value: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn child_pointer(pointer: &str, key: &str) -> String {
+    format!("{pointer}/{}", key.replace('~', "~0").replace('/', "~1"))
+}
+
+pub fn apply(value: &mut Value, patch: &[PatchOperation]) -> Result<(), PatchError> {
+    for operation in patch {
+        match operation {
+            PatchOperation::Add {
+                path,
+                value: new_value,
+            } => {
+                let (parent, key) = parent_object(value, path)?;
+                parent.insert(key, new_value.clone());
+            }
+            PatchOperation::Remove { path } => {
+                let (parent, key) = parent_object(value, path)?;
+                if parent.remove(&key).is_none() {
+                    return Err(PatchError::PathNotFound(path.clone()));
+                }
+            }
+            PatchOperation::Replace {
+                path,
+                value: new_value,
+            } => {
+                *value
+                    .pointer_mut(path)
+                    .ok_or_else(|| PatchError::PathNotFound(path.clone()))? = new_value.clone();
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parent_object<'value>(
+    value: &'value mut Value,
+    path: &str,
+) -> Result<(&'value mut serde_json::Map<String, Value>, String), PatchError> {
+    let Some((parent, key)) = path.rsplit_once('/') else {
+        return Err(PatchError::InvalidPath(path.to_string()));
+    };
+    match value.pointer_mut(parent) {
+        Some(Value::Object(object)) => Ok((object, key.replace("~1", "/").replace("~0", "~"))),
+        Some(_) => Err(PatchError::ParentNotAnObject(path.to_string())),
+        None => Err(PatchError::PathNotFound(path.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn equal_values_result_in_empty_patch() {
+        let value = json!({"a": {"b": [1, 2, 3]}, "c": "d"});
+        assert_eq!(diff(&value, &value), vec![]);
+    }
+
+    #[test]
+    fn only_changed_fields_are_patched() {
+        let old = json!({"a": {"b": 1, "c": 2}, "d": [1, 2], "removed": true});
+        let new = json!({"a": {"b": 1, "c": 3}, "d": [1, 2, 3], "e": null});
+        let patch = diff(&old, &new);
+        assert_eq!(
+            patch,
+            vec![
+                PatchOperation::Remove {
+                    path: "/removed".to_string()
+                },
+                PatchOperation::Replace {
+                    path: "/a/c".to_string(),
+                    value: json!(3)
+                },
+                PatchOperation::Replace {
+                    path: "/d".to_string(),
+                    value: json!([1, 2, 3])
+                },
+                PatchOperation::Add {
+                    path: "/e".to_string(),
+                    value: json!(null)
+                },
+            ]
+        );
+
+        let mut patched = old;
+        apply(&mut patched, &patch).unwrap();
+        assert_eq!(patched, new);
+    }
+
+    #[test]
+    fn changed_root_value_is_replaced() {
+        let mut value = json!(42);
+        let patch = diff(&value, &json!({"a": 1}));
+        assert_eq!(
+            patch,
+            vec![PatchOperation::Replace {
+                path: String::new(),
+                value: json!({"a": 1})
+            }]
+        );
+        apply(&mut value, &patch).unwrap();
+        assert_eq!(value, json!({"a": 1}));
+    }
+
+    #[test]
+    fn keys_with_special_characters_are_escaped() {
+        let old = json!({"a/b": {"c~d": 1}});
+        let new = json!({"a/b": {"c~d": 2, "e/f": 3}});
+        let patch = diff(&old, &new);
+        assert_eq!(
+            patch,
+            vec![
+                PatchOperation::Replace {
+                    path: "/a~1b/c~0d".to_string(),
+                    value: json!(2)
+                },
+                PatchOperation::Add {
+                    path: "/a~1b/e~1f".to_string(),
+                    value: json!(3)
+                },
+            ]
+        );
+
+        let mut patched = old;
+        apply(&mut patched, &patch).unwrap();
+        assert_eq!(patched, new);
+    }
+
+    #[test]
+    fn patching_missing_path_results_in_error() {
+        let mut value = json!({"a": 1});
+        let result = apply(
+            &mut value,
+            &[PatchOperation::Remove {
+                path: "/b".to_string(),
+            }],
+        );
+        assert!(matches!(result, Err(PatchError::PathNotFound(_))));
+    }
+}
@@ -1,8 +1,12 @@
 use std::{
     io,
-    sync::atomic::{AtomicUsize, Ordering},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
+use framework::ConnectionRole;
 use log::error;
 use tokio::{
     net::{TcpListener, ToSocketAddrs},
@@ -15,6 +19,7 @@ use tokio_util::sync::CancellationToken;
 use crate::messages::ParametersRequest;
 
 use super::{
+    audit::AuditMessage,
     client_request::ClientRequest,
     connection::{connection, ConnectionError},
     outputs,
@@ -30,13 +35,16 @@ pub enum AcceptError {
     ConnectionsErrored(Vec<ConnectionError>),
 }
 
+/// Accepts connections on `addresses`, all of them get the given `role`
 pub fn acceptor(
     addresses: impl ToSocketAddrs + Send + Sync + 'static,
+    role: ConnectionRole,
+    next_client_id: Arc<AtomicUsize>,
     keep_running: CancellationToken,
     outputs_sender: Sender<outputs::Request>,
     parameters_sender: Sender<ClientRequest<ParametersRequest>>,
+    audit_sender: Sender<AuditMessage>,
 ) -> JoinHandle<Result<(), AcceptError>> {
-    let next_client_id = AtomicUsize::default();
     spawn(async move {
         let (error_sender, mut error_receiver) = unbounded_channel();
 
@@ -45,19 +53,30 @@ pub fn acceptor(
             .map_err(AcceptError::TcpListenerNotBound)?;
 
         loop {
-            let (stream, _) = select! {
+            let (stream, peer_address) = select! {
                 result = listener.accept() => result.map_err(AcceptError::NotAccepted)?,
                 _ = keep_running.cancelled() => break,
             };
 
             let client_id = next_client_id.fetch_add(1, Ordering::SeqCst);
+            if let Err(error) = audit_sender
+                .send(AuditMessage::Connected {
+                    client_id,
+                    peer_address,
+                })
+                .await
+            {
+                error!("failed to send connection to audit log: {error}");
+            }
             connection(
                 stream,
                 keep_running.clone(),
                 error_sender.clone(),
                 outputs_sender.clone(),
                 parameters_sender.clone(),
+                audit_sender.clone(),
                 client_id,
+                role,
             );
         }
 
@@ -0,0 +1,193 @@
+use std::{collections::HashMap, net::SocketAddr, path::PathBuf};
+
+use log::error;
+use parameters::directory::Scope;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::{
+    fs::{create_dir_all, File, OpenOptions},
+    io::AsyncWriteExt,
+    spawn,
+    sync::mpsc::{Receiver, Sender},
+    task::JoinHandle,
+};
+
+use crate::messages::{CyclerInstance, Path};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AuditMessage {
+    Connected {
+        client_id: usize,
+        peer_address: SocketAddr,
+    },
+    Disconnected {
+        client_id: usize,
+    },
+    Action {
+        client_id: usize,
+        action: AuditAction,
+    },
+}
+
+/// Requests that modify the robot, `None` values could not be serialized
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum AuditAction {
+    UpdateParameter {
+        path: Path,
+        old_value: Option<Value>,
+        new_value: Value,
+    },
+    StoreToDisk {
+        scope: Scope,
+        path: Path,
+        value: Option<Value>,
+    },
+    SetInjection {
+        cycler_instance: CyclerInstance,
+        path: Path,
+        value: Value,
+    },
+    UnsetInjection {
+        cycler_instance: CyclerInstance,
+        path: Path,
+    },
+}
+
+#[derive(Serialize)]
+struct AuditEntry<'action> {
+    timestamp: String,
+    client_address: Option<SocketAddr>,
+    action: &'action AuditAction,
+}
+
+/// Appends one JSON line per action to the file at `path`, messages are only drained if the log
+/// is disabled or cannot be opened
+pub fn audit_log(
+    path: Option<PathBuf>,
+    mut message_receiver: Receiver<AuditMessage>,
+) -> JoinHandle<()> {
+    spawn(async move {
+        let mut file = match path {
+            Some(path) => match open(&path).await {
+                Ok(file) => Some(file),
+                Err(error) => {
+                    error!("failed to open audit log {path:?}: {error}");
+                    None
+                }
+            },
+            None => None,
+        };
+        let mut peer_addresses = HashMap::new();
+        while let Some(message) = message_receiver.recv().await {
+            match message {
+                AuditMessage::Connected {
+                    client_id,
+                    peer_address,
+                } => {
+                    peer_addresses.insert(client_id, peer_address);
+                }
+                AuditMessage::Disconnected { client_id } => {
+                    peer_addresses.remove(&client_id);
+                }
+                AuditMessage::Action { client_id, action } => {
+                    let Some(file) = &mut file else {
+                        continue;
+                    };
+                    let entry = AuditEntry {
+                        timestamp: chrono::Local::now()
+                            .format("%Y-%m-%d %H:%M:%S%.3f")
+                            .to_string(),
+                        client_address: peer_addresses.get(&client_id).copied(),
+                        action: &action,
+                    };
+                    if let Err(error) = append(file, &entry).await {
+                        error!("failed to append to audit log: {error}");
+                    }
+                }
+            }
+        }
+    })
+}
+
+async fn open(path: &std::path::Path) -> std::io::Result<File> {
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).await?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+}
+
+async fn append(file: &mut File, entry: &AuditEntry<'_>) -> std::io::Result<()> {
+    let mut line = serde_json::to_vec(entry)?;
+    line.push(b'\n');
+    file.write_all(&line).await?;
+    file.flush().await
+}
+
+/// Forwards `action` to the audit log, failures are logged since they must not affect the request
+pub async fn audit(audit_sender: &Sender<AuditMessage>, client_id: usize, action: AuditAction) {
+    if let Err(error) = audit_sender
+        .send(AuditMessage::Action { client_id, action })
+        .await
+    {
+        error!("failed to send action to audit log: {error}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs::read_to_string, process};
+
+    use tokio::sync::mpsc::channel;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn actions_are_appended_with_client_address() {
+        let path = temp_dir().join(format!("communication_audit_{}.log", process::id()));
+        let (audit_sender, audit_receiver) = channel(1);
+        let audit_log_task = audit_log(Some(path.clone()), audit_receiver);
+
+        audit_sender
+            .send(AuditMessage::Connected {
+                client_id: 1337,
+                peer_address: "10.1.24.42:4242".parse().unwrap(),
+            })
+            .await
+            .unwrap();
+        audit(
+            &audit_sender,
+            1337,
+            AuditAction::UpdateParameter {
+                path: "a.b.c".to_string(),
+                old_value: Some(Value::from(42)),
+                new_value: Value::from(1337),
+            },
+        )
+        .await;
+        drop(audit_sender);
+        audit_log_task.await.unwrap();
+
+        let contents = read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let entries: Vec<Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["client_address"], "10.1.24.42:4242");
+        assert_eq!(
+            entries[0]["action"],
+            serde_json::json!({
+                "UpdateParameter": {
+                    "path": "a.b.c",
+                    "old_value": 42,
+                    "new_value": 1337,
+                }
+            })
+        );
+    }
+}
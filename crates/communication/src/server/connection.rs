@@ -1,5 +1,6 @@
 use std::{io, net::SocketAddr};
 
+use framework::ConnectionRole;
 use futures_util::StreamExt;
 use log::error;
 use tokio::{
@@ -12,7 +13,9 @@ use tokio_util::sync::CancellationToken;
 
 use crate::messages::ParametersRequest;
 
-use super::{client_request::ClientRequest, outputs, receiver::receiver, sender::sender};
+use super::{
+    audit::AuditMessage, client_request::ClientRequest, outputs, receiver::receiver, sender::sender,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ConnectionError {
@@ -46,13 +49,16 @@ pub enum ReceiverOrSenderError {
     WebSocketMessageNotWritten(tokio_tungstenite::tungstenite::Error),
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn connection(
     stream: TcpStream,
     keep_running: CancellationToken,
     connection_error_sender: UnboundedSender<ConnectionError>,
     outputs_sender: Sender<outputs::Request>,
     parameters_sender: Sender<ClientRequest<ParametersRequest>>,
+    audit_sender: Sender<AuditMessage>,
     client_id: usize,
+    role: ConnectionRole,
 ) {
     spawn(async move {
         let peer_address = match stream.peer_addr() {
@@ -90,9 +96,11 @@ pub fn connection(
             keep_running,
             keep_only_self_running.clone(),
             client_id,
+            role,
             response_sender,
             outputs_sender,
             parameters_sender,
+            audit_sender,
         ));
 
         spawn(sender(
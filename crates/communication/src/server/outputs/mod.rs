@@ -1,21 +1,31 @@
-use std::collections::BTreeSet;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Instant,
+};
 
+use serde_json::Value;
 use tokio::sync::mpsc::Sender;
 
-use crate::messages::{Format, OutputsRequest, Path};
+use crate::messages::{Format, InjectionsRequest, OutputsRequest, Path, UpdatePolicy};
 
 use super::client_request::ClientRequest;
 
 pub mod provider;
 pub mod router;
 
+/// Values injected into the outputs of one cycler instance, they replace the values written by the
+/// nodes at the end of each cycle
+pub type Injections = BTreeMap<Path, Value>;
+
 #[derive(Debug)]
 pub enum Request {
     ClientRequest(ClientRequest<OutputsRequest>),
+    InjectionsRequest(ClientRequest<InjectionsRequest>),
     RegisterCycler {
         cycler_instance: String,
         fields: BTreeSet<Path>,
         request_sender: Sender<ClientRequest<OutputsRequest>>,
+        injections_request_sender: Sender<ClientRequest<InjectionsRequest>>,
     },
 }
 
@@ -24,4 +34,7 @@ struct Subscription {
     pub path: Path,
     pub format: Format,
     pub once: bool,
+    pub update_policy: UpdatePolicy,
+    pub cycles_since_last_update: usize,
+    pub last_update: Option<Instant>,
 }
@@ -1,7 +1,8 @@
 use std::{
-    collections::{hash_map::Entry, HashMap, HashSet},
+    collections::{hash_map::Entry, BTreeMap, HashMap, HashSet},
     num::Wrapping,
     sync::Arc,
+    time::Instant,
 };
 
 use bincode::{DefaultOptions, Options};
@@ -20,13 +21,18 @@ use tokio::{
 
 use crate::{
     messages::{
-        BinaryOutputsResponse, BinaryResponse, Format, OutputsRequest, Response,
-        TextualDataOrBinaryReference, TextualOutputsResponse, TextualResponse,
+        BinaryOutputsResponse, BinaryResponse, Format, InjectionsRequest, InjectionsResponse,
+        OutputsRequest, Path, Response, TextualDataOrBinaryReference, TextualOutputsResponse,
+        TextualResponse, UpdatePolicy,
+    },
+    server::{
+        audit::{audit, AuditAction, AuditMessage},
+        client::Client,
+        client_request::ClientRequest,
     },
-    server::{client::Client, client_request::ClientRequest},
 };
 
-use super::{Request, Subscription};
+use super::{Injections, Request, Subscription};
 
 pub fn provider<Outputs>(
     outputs_sender: Sender<Request>,
@@ -34,24 +40,29 @@ pub fn provider<Outputs>(
     outputs_changed: Arc<Notify>,
     outputs_reader: Reader<Outputs>,
     subscribed_outputs_writer: Writer<HashSet<String>>,
+    injections_writer: Writer<Injections>,
+    audit_sender: Sender<AuditMessage>,
 ) -> JoinHandle<()>
 where
-    Outputs: SerializeHierarchy + Send + Sync + 'static,
+    Outputs: Default + SerializeHierarchy + Send + Sync + 'static,
 {
     spawn(async move {
         let (request_sender, mut request_receiver) = channel(1);
+        let (injections_request_sender, mut injections_request_receiver) = channel(1);
 
         outputs_sender
             .send(Request::RegisterCycler {
                 cycler_instance: cycler_instance.to_string(),
                 fields: Outputs::get_fields(),
                 request_sender,
+                injections_request_sender,
             })
             .await
             .expect("receiver should always wait for all senders");
         drop(outputs_sender);
 
         let mut subscriptions = HashMap::new();
+        let mut injections = BTreeMap::new();
         let mut next_binary_reference_id = Wrapping(0);
         loop {
             let subscriptions_state = select! {
@@ -67,6 +78,16 @@ where
                         None => break,
                     }
                 },
+                Some(request) = injections_request_receiver.recv() => {
+                    handle_injections_request::<Outputs>(
+                        request,
+                        cycler_instance,
+                        &mut injections,
+                        &injections_writer,
+                        &audit_sender,
+                    ).await;
+                    SubscriptionsState::Unchanged
+                },
                 _ = outputs_changed.notified() => {
                     handle_notified_output(&outputs_reader, &mut subscriptions, &mut next_binary_reference_id).await
                 },
@@ -96,6 +117,10 @@ where
     Outputs: SerializeHierarchy,
 {
     let is_get_next = matches!(request.request, OutputsRequest::GetNext { .. });
+    let update_policy = match request.request {
+        OutputsRequest::Subscribe { update_policy, .. } => update_policy,
+        _ => UpdatePolicy::EveryCycle,
+    };
     match request.request {
         OutputsRequest::GetFields { .. } => {
             panic!("GetFields should be answered by output router");
@@ -111,6 +136,7 @@ where
             cycler_instance: received_cycler_instance,
             path,
             format,
+            ..
         } => {
             assert_eq!(cycler_instance, received_cycler_instance);
             if Outputs::exists(&path) {
@@ -142,6 +168,9 @@ where
                             path,
                             format,
                             once: is_get_next,
+                            update_policy,
+                            cycles_since_last_update: 0,
+                            last_update: None,
                         });
                         if !is_get_next {
                             request
@@ -218,6 +247,123 @@ where
     }
 }
 
+async fn handle_injections_request<Outputs>(
+    request: ClientRequest<InjectionsRequest>,
+    cycler_instance: &'static str,
+    injections: &mut BTreeMap<Path, (Client, serde_json::Value)>,
+    injections_writer: &Writer<Injections>,
+    audit_sender: &Sender<AuditMessage>,
+) where
+    Outputs: Default + SerializeHierarchy,
+{
+    match request.request {
+        InjectionsRequest::Set {
+            id,
+            cycler_instance: received_cycler_instance,
+            path,
+            data,
+        } => {
+            assert_eq!(cycler_instance, received_cycler_instance);
+            let result = if Outputs::exists(&path) {
+                Outputs::default()
+                    .deserialize_path(&path, data.clone())
+                    .map_err(|error| format!("failed to deserialize {path:?}: {error:?}"))
+            } else {
+                Err(format!("path {path:?} does not exist"))
+            };
+            if result.is_ok() {
+                injections.insert(path.clone(), (request.client.clone(), data.clone()));
+                write_injections(injections, injections_writer);
+                audit(
+                    audit_sender,
+                    request.client.id,
+                    AuditAction::SetInjection {
+                        cycler_instance: cycler_instance.to_string(),
+                        path,
+                        value: data,
+                    },
+                )
+                .await;
+            }
+            request
+                .client
+                .response_sender
+                .send(Response::Textual(TextualResponse::Injections(
+                    InjectionsResponse::Set { id, result },
+                )))
+                .await
+                .expect("receiver should always wait for all senders");
+        }
+        InjectionsRequest::Unset {
+            id,
+            cycler_instance: received_cycler_instance,
+            path,
+        } => {
+            assert_eq!(cycler_instance, received_cycler_instance);
+            let result = match injections.remove(&path) {
+                Some(_) => {
+                    write_injections(injections, injections_writer);
+                    audit(
+                        audit_sender,
+                        request.client.id,
+                        AuditAction::UnsetInjection {
+                            cycler_instance: cycler_instance.to_string(),
+                            path,
+                        },
+                    )
+                    .await;
+                    Ok(())
+                }
+                None => Err(format!("path {path:?} is not injected")),
+            };
+            request
+                .client
+                .response_sender
+                .send(Response::Textual(TextualResponse::Injections(
+                    InjectionsResponse::Unset { id, result },
+                )))
+                .await
+                .expect("receiver should always wait for all senders");
+        }
+        InjectionsRequest::UnsetEverything => {
+            let paths_of_client: Vec<_> = injections
+                .iter()
+                .filter(|(_path, (client, _value))| client == &request.client)
+                .map(|(path, _injection)| path.clone())
+                .collect();
+            if paths_of_client.is_empty() {
+                return;
+            }
+            for path in &paths_of_client {
+                injections.remove(path);
+            }
+            write_injections(injections, injections_writer);
+            for path in paths_of_client {
+                audit(
+                    audit_sender,
+                    request.client.id,
+                    AuditAction::UnsetInjection {
+                        cycler_instance: cycler_instance.to_string(),
+                        path,
+                    },
+                )
+                .await;
+            }
+        }
+    }
+}
+
+fn write_injections(
+    injections: &BTreeMap<Path, (Client, serde_json::Value)>,
+    injections_writer: &Writer<Injections>,
+) {
+    let mut injections_slot = injections_writer.next();
+    *injections_slot = injections
+        .iter()
+        .map(|(path, (_client, value))| (path.clone(), value.clone()))
+        .collect();
+}
+
 fn write_subscribed_outputs_from_subscriptions(
     subscriptions: &mut HashMap<(Client, usize), Subscription>,
     subscribed_outputs_writer: &Writer<HashSet<String>>,
@@ -245,7 +391,21 @@ async fn handle_notified_output(
     let mut subscriptions_state = SubscriptionsState::Unchanged;
     {
         let output = outputs_reader.next();
+        let now = Instant::now();
         subscriptions.retain(|(client, subscription_id), subscription| {
+            if !subscription.once {
+                subscription.cycles_since_last_update += 1;
+                if let Some(last_update) = subscription.last_update {
+                    if !subscription
+                        .update_policy
+                        .is_due(subscription.cycles_since_last_update, now - last_update)
+                    {
+                        return true;
+                    }
+                }
+                subscription.cycles_since_last_update = 0;
+                subscription.last_update = Some(now);
+            }
             let data = match subscription.format {
                 Format::Textual => {
                     let data = match output
@@ -359,7 +519,7 @@ mod tests {
     use serialize_hierarchy::Error;
     use tokio::{sync::mpsc::error::TryRecvError, task::yield_now, time::timeout};
 
-    use crate::messages::Format;
+    use crate::messages::{Format, UpdatePolicy};
 
     use super::*;
 
@@ -367,6 +527,14 @@ mod tests {
         existing_fields: HashMap<String, T>,
     }
 
+    impl<T> Default for OutputsFake<T> {
+        fn default() -> Self {
+            Self {
+                existing_fields: Default::default(),
+            }
+        }
+    }
+
     impl<T> SerializeHierarchy for OutputsFake<T>
     where
         for<'a> T: Deserialize<'a> + Serialize,
@@ -413,50 +581,87 @@ mod tests {
     async fn get_registered_request_sender_from_provider(
         cycler_instance: &'static str,
         outputs_changed: Arc<Notify>,
-        output: Reader<impl SerializeHierarchy + Send + Sync + 'static>,
+        output: Reader<impl Default + SerializeHierarchy + Send + Sync + 'static>,
     ) -> (
         JoinHandle<()>,
         BTreeSet<String>,
         Sender<ClientRequest<OutputsRequest>>,
         Reader<HashSet<String>>,
     ) {
+        let (audit_sender, _audit_receiver) = channel(1);
+        let registered_provider =
+            register_provider(cycler_instance, outputs_changed, output, audit_sender).await;
+        (
+            registered_provider.join_handle,
+            registered_provider.fields,
+            registered_provider.request_sender,
+            registered_provider.subscribed_outputs_reader,
+        )
+    }
+
+    struct RegisteredProvider {
+        join_handle: JoinHandle<()>,
+        fields: BTreeSet<String>,
+        request_sender: Sender<ClientRequest<OutputsRequest>>,
+        injections_request_sender: Sender<ClientRequest<InjectionsRequest>>,
+        subscribed_outputs_reader: Reader<HashSet<String>>,
+        injections_reader: Reader<Injections>,
+    }
+
+    async fn register_provider(
+        cycler_instance: &'static str,
+        outputs_changed: Arc<Notify>,
+        output: Reader<impl Default + SerializeHierarchy + Send + Sync + 'static>,
+        audit_sender: Sender<AuditMessage>,
+    ) -> RegisteredProvider {
         let (outputs_sender, mut outputs_receiver) = channel(1);
         let (subscribed_outputs_writer, subscribed_outputs_reader) = multiple_buffer_with_slots([
             Default::default(),
             Default::default(),
             Default::default(),
         ]);
+        let (injections_writer, injections_reader) = multiple_buffer_with_slots([
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        ]);
         let join_handle = provider(
             outputs_sender,
             cycler_instance,
             outputs_changed,
             output,
             subscribed_outputs_writer,
+            injections_writer,
+            audit_sender,
         );
-        let (fields, request_sender) = timeout(Duration::from_secs(1), async move {
-            let Some(request) = outputs_receiver.recv().await else {
-                panic!("expected request");
-            };
-            let Request::RegisterCycler {
-                cycler_instance: cycler_instance_to_register,
-                fields,
-                request_sender,
-            } = request
-            else {
-                panic!("expected Request::RegisterCycler");
-            };
-            assert_eq!(cycler_instance, cycler_instance_to_register);
-            assert!(outputs_receiver.recv().await.is_none());
-            (fields, request_sender)
-        })
-        .await
-        .unwrap();
-        (
+        let (fields, request_sender, injections_request_sender) =
+            timeout(Duration::from_secs(1), async move {
+                let Some(request) = outputs_receiver.recv().await else {
+                    panic!("expected request");
+                };
+                let Request::RegisterCycler {
+                    cycler_instance: cycler_instance_to_register,
+                    fields,
+                    request_sender,
+                    injections_request_sender,
+                } = request
+                else {
+                    panic!("expected Request::RegisterCycler");
+                };
+                assert_eq!(cycler_instance, cycler_instance_to_register);
+                assert!(outputs_receiver.recv().await.is_none());
+                (fields, request_sender, injections_request_sender)
+            })
+            .await
+            .unwrap();
+        RegisteredProvider {
             join_handle,
             fields,
             request_sender,
+            injections_request_sender,
             subscribed_outputs_reader,
-        )
+            injections_reader,
+        }
     }
 
     #[tokio::test]
@@ -536,6 +741,7 @@ mod tests {
                     cycler_instance: cycler_instance.clone(),
                     path: path.clone(),
                     format,
+                    update_policy: UpdatePolicy::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -573,6 +779,7 @@ mod tests {
                     cycler_instance,
                     path: path.clone(),
                     format,
+                    update_policy: UpdatePolicy::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -637,6 +844,7 @@ mod tests {
                     cycler_instance: cycler_instance.clone(),
                     path: path.clone(),
                     format,
+                    update_policy: UpdatePolicy::EveryCycle,
                 },
                 client: Client {
                     id: 1337,
@@ -674,6 +882,7 @@ mod tests {
                     cycler_instance,
                     path: path.clone(),
                     format,
+                    update_policy: UpdatePolicy::EveryCycle,
                 },
                 client: Client {
                     id: 7331,
@@ -738,6 +947,7 @@ mod tests {
                     cycler_instance: cycler_instance.clone(),
                     path: path.clone(),
                     format,
+                    update_policy: UpdatePolicy::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -775,6 +985,7 @@ mod tests {
                     cycler_instance,
                     path: path.clone(),
                     format,
+                    update_policy: UpdatePolicy::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -892,6 +1103,7 @@ mod tests {
                     cycler_instance: cycler_instance.to_string(),
                     path: path.clone(),
                     format: Format::Textual,
+                    update_policy: UpdatePolicy::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -1016,6 +1228,7 @@ mod tests {
                     cycler_instance: cycler_instance.to_string(),
                     path: path.clone(),
                     format: Format::Textual,
+                    update_policy: UpdatePolicy::EveryCycle,
                 },
                 client: Client {
                     id: 1337,
@@ -1132,6 +1345,7 @@ mod tests {
                     cycler_instance: cycler_instance.to_string(),
                     path: path.clone(),
                     format: Format::Textual,
+                    update_policy: UpdatePolicy::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -1259,6 +1473,7 @@ mod tests {
                     cycler_instance: cycler_instance.to_string(),
                     path: path.clone(),
                     format: Format::Binary,
+                    update_policy: UpdatePolicy::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -1394,6 +1609,7 @@ mod tests {
                     cycler_instance: cycler_instance.to_string(),
                     path: path.clone(),
                     format: Format::Textual,
+                    update_policy: UpdatePolicy::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -1432,6 +1648,7 @@ mod tests {
                     cycler_instance: cycler_instance.to_string(),
                     path: path.clone(),
                     format: Format::Textual,
+                    update_policy: UpdatePolicy::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -1586,6 +1803,93 @@ mod tests {
         provider_task.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn every_nth_cycle_update_policy_skips_cycles() {
+        let cycler_instance = "CyclerInstance";
+        let path = "a.b.c".to_string();
+        let value = Value::from(42);
+        let outputs_changed = Arc::new(Notify::new());
+        let (_output_writer, outputs_reader) = multiple_buffer_with_slots([OutputsFake {
+            existing_fields: [(path.clone(), value.clone())].into(),
+        }]);
+
+        let (provider_task, _fields, request_sender, _subscribed_outputs_reader) =
+            get_registered_request_sender_from_provider(
+                cycler_instance,
+                outputs_changed.clone(),
+                outputs_reader,
+            )
+            .await;
+
+        const SUBSCRIPTION_ID: usize = 42;
+        let client_id = 1337;
+
+        let (response_sender, mut response_receiver) = channel(1);
+        request_sender
+            .send(ClientRequest {
+                request: OutputsRequest::Subscribe {
+                    id: SUBSCRIPTION_ID,
+                    cycler_instance: cycler_instance.to_string(),
+                    path: path.clone(),
+                    format: Format::Textual,
+                    update_policy: UpdatePolicy::EveryNthCycle { n: 2 },
+                },
+                client: Client {
+                    id: client_id,
+                    response_sender: response_sender.clone(),
+                },
+            })
+            .await
+            .unwrap();
+        let response = response_receiver.recv().await.unwrap();
+        assert!(
+            matches!(
+                response,
+                Response::Textual(TextualResponse::Outputs(
+                    TextualOutputsResponse::Subscribe {
+                        id: SUBSCRIPTION_ID,
+                        result: Ok(()),
+                    }
+                ))
+            ),
+            "unexpected {response:?}",
+        );
+
+        let mut cycles_with_data = Vec::new();
+        for cycle in 0..5 {
+            outputs_changed.notify_one();
+            // ensure that the provider handled the notification before the next one coalesces with it
+            for _ in 0..10 {
+                yield_now().await;
+            }
+            match response_receiver.try_recv() {
+                Ok(subscribed_data) => {
+                    assert_eq!(
+                        subscribed_data,
+                        Response::Textual(TextualResponse::Outputs(
+                            TextualOutputsResponse::SubscribedData {
+                                items: [(
+                                    SUBSCRIPTION_ID,
+                                    TextualDataOrBinaryReference::TextualData {
+                                        data: value.clone()
+                                    }
+                                )]
+                                .into()
+                            }
+                        )),
+                    );
+                    cycles_with_data.push(cycle);
+                }
+                Err(TryRecvError::Empty) => {}
+                response => panic!("unexpected result from try_recv(): {response:?}"),
+            }
+        }
+        assert_eq!(cycles_with_data, [0, 2, 4]);
+
+        drop(request_sender);
+        provider_task.await.unwrap();
+    }
+
     #[tokio::test]
     async fn textual_get_next_forwards_data_once() {
         let cycler_instance = "CyclerInstance";
@@ -1748,4 +2052,199 @@ mod tests {
         drop(request_sender);
         provider_task.await.unwrap();
     }
+
+    #[tokio::test]
+    async fn injections_are_validated_written_and_audited() {
+        let cycler_instance = "CyclerInstance";
+        let outputs_changed = Arc::new(Notify::new());
+        let (_output_writer, outputs_reader) = multiple_buffer_with_slots([OutputsFake::<i32> {
+            existing_fields: Default::default(),
+        }]);
+        let (audit_sender, mut audit_receiver) = channel(10);
+
+        let registered_provider = register_provider(
+            cycler_instance,
+            outputs_changed,
+            outputs_reader,
+            audit_sender,
+        )
+        .await;
+
+        let (response_sender, mut response_receiver) = channel(1);
+        let client = Client {
+            id: 1337,
+            response_sender,
+        };
+        for (id, path, data) in [(42, "a.b", Value::from(1)), (43, "a.b.c", Value::from("1"))] {
+            registered_provider
+                .injections_request_sender
+                .send(ClientRequest {
+                    request: InjectionsRequest::Set {
+                        id,
+                        cycler_instance: cycler_instance.to_string(),
+                        path: path.to_string(),
+                        data,
+                    },
+                    client: client.clone(),
+                })
+                .await
+                .unwrap();
+            let response = response_receiver.recv().await.unwrap();
+            assert!(
+                matches!(
+                    response,
+                    Response::Textual(TextualResponse::Injections(InjectionsResponse::Set {
+                        id: response_id,
+                        result: Err(_),
+                    })) if response_id == id
+                ),
+                "unexpected {response:?}",
+            );
+        }
+        assert_eq!(
+            *registered_provider.injections_reader.next(),
+            Injections::new()
+        );
+
+        registered_provider
+            .injections_request_sender
+            .send(ClientRequest {
+                request: InjectionsRequest::Set {
+                    id: 44,
+                    cycler_instance: cycler_instance.to_string(),
+                    path: "a.b.c".to_string(),
+                    data: Value::from(42),
+                },
+                client: client.clone(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            response_receiver.recv().await.unwrap(),
+            Response::Textual(TextualResponse::Injections(InjectionsResponse::Set {
+                id: 44,
+                result: Ok(()),
+            })),
+        );
+        assert_eq!(
+            *registered_provider.injections_reader.next(),
+            [("a.b.c".to_string(), Value::from(42))].into(),
+        );
+        assert_eq!(
+            audit_receiver.recv().await.unwrap(),
+            AuditMessage::Action {
+                client_id: 1337,
+                action: AuditAction::SetInjection {
+                    cycler_instance: cycler_instance.to_string(),
+                    path: "a.b.c".to_string(),
+                    value: Value::from(42),
+                },
+            },
+        );
+
+        for result in [Ok(()), Err("path \"a.b.c\" is not injected".to_string())] {
+            registered_provider
+                .injections_request_sender
+                .send(ClientRequest {
+                    request: InjectionsRequest::Unset {
+                        id: 45,
+                        cycler_instance: cycler_instance.to_string(),
+                        path: "a.b.c".to_string(),
+                    },
+                    client: client.clone(),
+                })
+                .await
+                .unwrap();
+            assert_eq!(
+                response_receiver.recv().await.unwrap(),
+                Response::Textual(TextualResponse::Injections(InjectionsResponse::Unset {
+                    id: 45,
+                    result,
+                })),
+            );
+        }
+        assert_eq!(
+            *registered_provider.injections_reader.next(),
+            Injections::new()
+        );
+        assert_eq!(
+            audit_receiver.recv().await.unwrap(),
+            AuditMessage::Action {
+                client_id: 1337,
+                action: AuditAction::UnsetInjection {
+                    cycler_instance: cycler_instance.to_string(),
+                    path: "a.b.c".to_string(),
+                },
+            },
+        );
+
+        drop(registered_provider.request_sender);
+        registered_provider.join_handle.await.unwrap();
+        assert!(audit_receiver.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn unset_everything_removes_injections_of_client() {
+        let cycler_instance = "CyclerInstance";
+        let outputs_changed = Arc::new(Notify::new());
+        let (_output_writer, outputs_reader) = multiple_buffer_with_slots([OutputsFake::<i32> {
+            existing_fields: Default::default(),
+        }]);
+        let (audit_sender, mut audit_receiver) = channel(10);
+
+        let registered_provider = register_provider(
+            cycler_instance,
+            outputs_changed,
+            outputs_reader,
+            audit_sender,
+        )
+        .await;
+
+        let (response_sender, mut response_receiver) = channel(1);
+        let client = Client {
+            id: 1337,
+            response_sender,
+        };
+        registered_provider
+            .injections_request_sender
+            .send(ClientRequest {
+                request: InjectionsRequest::Set {
+                    id: 42,
+                    cycler_instance: cycler_instance.to_string(),
+                    path: "a.b.c".to_string(),
+                    data: Value::from(42),
+                },
+                client: client.clone(),
+            })
+            .await
+            .unwrap();
+        response_receiver.recv().await.unwrap();
+        audit_receiver.recv().await.unwrap();
+
+        registered_provider
+            .injections_request_sender
+            .send(ClientRequest {
+                request: InjectionsRequest::UnsetEverything,
+                client,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            audit_receiver.recv().await.unwrap(),
+            AuditMessage::Action {
+                client_id: 1337,
+                action: AuditAction::UnsetInjection {
+                    cycler_instance: cycler_instance.to_string(),
+                    path: "a.b.c".to_string(),
+                },
+            },
+        );
+        assert_eq!(
+            *registered_provider.injections_reader.next(),
+            Injections::new()
+        );
+
+        drop(registered_provider.request_sender);
+        registered_provider.join_handle.await.unwrap();
+    }
 }
@@ -7,7 +7,10 @@ use tokio::{
 };
 
 use crate::{
-    messages::{OutputsRequest, Path, Response, TextualOutputsResponse, TextualResponse},
+    messages::{
+        InjectionsRequest, InjectionsResponse, OutputsRequest, Path, Response,
+        TextualOutputsResponse, TextualResponse,
+    },
     server::{client::Client, client_request::ClientRequest},
 };
 
@@ -16,6 +19,7 @@ use super::Request;
 pub fn router(mut request_receiver: Receiver<Request>) -> JoinHandle<()> {
     spawn(async move {
         let mut request_channels_of_cyclers = HashMap::new();
+        let mut injections_request_channels_of_cyclers = HashMap::new();
         let mut cached_cycler_instances = HashMap::new();
 
         while let Some(request) = request_receiver.recv().await {
@@ -28,11 +32,18 @@ pub fn router(mut request_receiver: Receiver<Request>) -> JoinHandle<()> {
                     )
                     .await
                 }
+                Request::InjectionsRequest(request) => {
+                    handle_injections_request(request, &injections_request_channels_of_cyclers)
+                        .await
+                }
                 Request::RegisterCycler {
                     cycler_instance,
                     fields,
                     request_sender,
+                    injections_request_sender,
                 } => {
+                    injections_request_channels_of_cyclers
+                        .insert(cycler_instance.clone(), injections_request_sender);
                     request_channels_of_cyclers.insert(cycler_instance, (fields, request_sender));
                 }
             }
@@ -67,6 +78,21 @@ async fn handle_request(
                 .await
                 .expect("receiver should always wait for all senders");
         }
+        OutputsRequest::Subscribe {
+            id, update_policy, ..
+        } if update_policy.validate().is_err() => {
+            request
+                .client
+                .response_sender
+                .send(Response::Textual(TextualResponse::Outputs(
+                    TextualOutputsResponse::Subscribe {
+                        id: *id,
+                        result: update_policy.validate(),
+                    },
+                )))
+                .await
+                .expect("receiver should always wait for all senders");
+        }
         OutputsRequest::GetNext {
             id,
             cycler_instance,
@@ -171,11 +197,62 @@ async fn handle_request(
     }
 }
 
+async fn handle_injections_request(
+    request: ClientRequest<InjectionsRequest>,
+    injections_request_channels_of_cyclers: &HashMap<
+        String,
+        Sender<ClientRequest<InjectionsRequest>>,
+    >,
+) {
+    match &request.request {
+        InjectionsRequest::Set {
+            id,
+            cycler_instance,
+            ..
+        }
+        | InjectionsRequest::Unset {
+            id,
+            cycler_instance,
+            ..
+        } => match injections_request_channels_of_cyclers.get(cycler_instance) {
+            Some(request_channel) => {
+                request_channel
+                    .send(request)
+                    .await
+                    .expect("receiver should always wait for all senders");
+            }
+            None => {
+                let result = Err(format!("unknown cycler_instance {cycler_instance:?}"));
+                request
+                    .client
+                    .response_sender
+                    .send(Response::Textual(TextualResponse::Injections(
+                        if matches!(request.request, InjectionsRequest::Set { .. }) {
+                            InjectionsResponse::Set { id: *id, result }
+                        } else {
+                            InjectionsResponse::Unset { id: *id, result }
+                        },
+                    )))
+                    .await
+                    .expect("receiver should always wait for all senders");
+            }
+        },
+        InjectionsRequest::UnsetEverything => {
+            for request_channel in injections_request_channels_of_cyclers.values() {
+                request_channel
+                    .send(request.clone())
+                    .await
+                    .expect("receiver should always wait for all senders");
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use tokio::sync::mpsc::{channel, error::TryRecvError};
 
-    use crate::messages::Format;
+    use crate::messages::{Format, UpdatePolicy};
 
     use super::*;
 
@@ -196,11 +273,14 @@ mod tests {
         let cycler_instance = "CyclerInstance";
         let fields: BTreeSet<String> = ["a.b.c".to_string()].into();
         let (provider_request_sender, _provider_request_receiver) = channel(1);
+        let (provider_injections_request_sender, _provider_injections_request_receiver) =
+            channel(1);
         request_sender
             .send(Request::RegisterCycler {
                 cycler_instance: cycler_instance.to_string(),
                 fields: fields.clone(),
                 request_sender: provider_request_sender,
+                injections_request_sender: provider_injections_request_sender,
             })
             .await
             .unwrap();
@@ -276,6 +356,63 @@ mod tests {
         router_task.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn invalid_update_policy_results_in_error() {
+        let (request_sender, request_receiver) = channel(1);
+        let router_task = router(request_receiver);
+
+        let (provider_request_sender, mut provider_request_receiver) = channel(1);
+        let (provider_injections_request_sender, _provider_injections_request_receiver) =
+            channel(1);
+        request_sender
+            .send(Request::RegisterCycler {
+                cycler_instance: "CyclerInstance".to_string(),
+                fields: Default::default(),
+                request_sender: provider_request_sender,
+                injections_request_sender: provider_injections_request_sender,
+            })
+            .await
+            .unwrap();
+
+        let (response_sender, mut response_receiver) = channel(1);
+        request_sender
+            .send(Request::ClientRequest(ClientRequest {
+                request: OutputsRequest::Subscribe {
+                    id: 42,
+                    cycler_instance: "CyclerInstance".to_string(),
+                    path: "a.b.c".to_string(),
+                    format: Format::Textual,
+                    update_policy: UpdatePolicy::EveryNthCycle { n: 0 },
+                },
+                client: Client {
+                    id: 1337,
+                    response_sender,
+                },
+            }))
+            .await
+            .unwrap();
+        let response = response_receiver.recv().await.unwrap();
+        assert!(
+            matches!(
+                response,
+                Response::Textual(TextualResponse::Outputs(
+                    TextualOutputsResponse::Subscribe {
+                        id: 42,
+                        result: Err(_),
+                    }
+                ))
+            ),
+            "unexpected {response:?}",
+        );
+        match provider_request_receiver.try_recv() {
+            Err(TryRecvError::Empty) => {}
+            request => panic!("unexpected result from try_recv(): {request:?}"),
+        }
+
+        drop(request_sender);
+        router_task.await.unwrap();
+    }
+
     #[tokio::test]
     async fn client_request_is_forwarded() {
         let (request_sender, request_receiver) = channel(1);
@@ -283,11 +420,14 @@ mod tests {
 
         let cycler_instance = "CyclerInstance";
         let (provider_request_sender, mut provider_request_receiver) = channel(1);
+        let (provider_injections_request_sender, _provider_injections_request_receiver) =
+            channel(1);
         request_sender
             .send(Request::RegisterCycler {
                 cycler_instance: cycler_instance.to_string(),
                 fields: Default::default(),
                 request_sender: provider_request_sender,
+                injections_request_sender: provider_injections_request_sender,
             })
             .await
             .unwrap();
@@ -323,11 +463,14 @@ mod tests {
 
         let cycler_instance = "CyclerInstance";
         let (provider_request_sender, mut provider_request_receiver) = channel(1);
+        let (provider_injections_request_sender, _provider_injections_request_receiver) =
+            channel(1);
         request_sender
             .send(Request::RegisterCycler {
                 cycler_instance: cycler_instance.to_string(),
                 fields: Default::default(),
                 request_sender: provider_request_sender,
+                injections_request_sender: provider_injections_request_sender,
             })
             .await
             .unwrap();
@@ -343,6 +486,7 @@ mod tests {
                 cycler_instance: "CyclerInstance".to_string(),
                 path: "a.b.c".to_string(),
                 format: Format::Textual,
+                update_policy: UpdatePolicy::EveryCycle,
             },
             client: client.clone(),
         };
@@ -370,4 +514,87 @@ mod tests {
         drop(request_sender);
         router_task.await.unwrap();
     }
+
+    #[tokio::test]
+    async fn injections_request_is_forwarded_to_cycler_instance() {
+        let (request_sender, request_receiver) = channel(1);
+        let router_task = router(request_receiver);
+
+        let cycler_instance = "CyclerInstance";
+        let (provider_request_sender, _provider_request_receiver) = channel(1);
+        let (provider_injections_request_sender, mut provider_injections_request_receiver) =
+            channel(1);
+        request_sender
+            .send(Request::RegisterCycler {
+                cycler_instance: cycler_instance.to_string(),
+                fields: Default::default(),
+                request_sender: provider_request_sender,
+                injections_request_sender: provider_injections_request_sender,
+            })
+            .await
+            .unwrap();
+
+        let (response_sender, _response_receiver) = channel(1);
+        let sent_client_request = ClientRequest {
+            request: InjectionsRequest::Set {
+                id: 42,
+                cycler_instance: cycler_instance.to_string(),
+                path: "a.b.c".to_string(),
+                data: 1337.into(),
+            },
+            client: Client {
+                id: 1337,
+                response_sender,
+            },
+        };
+        request_sender
+            .send(Request::InjectionsRequest(sent_client_request.clone()))
+            .await
+            .unwrap();
+        let forwarded_client_request = provider_injections_request_receiver.recv().await.unwrap();
+        assert_eq!(forwarded_client_request, sent_client_request);
+
+        drop(request_sender);
+        router_task.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn injections_request_for_unknown_cycler_instance_results_in_error() {
+        let (request_sender, request_receiver) = channel(1);
+        let router_task = router(request_receiver);
+
+        let (response_sender, mut response_receiver) = channel(1);
+        request_sender
+            .send(Request::InjectionsRequest(ClientRequest {
+                request: InjectionsRequest::Unset {
+                    id: 42,
+                    cycler_instance: "CyclerInstance".to_string(),
+                    path: "a.b.c".to_string(),
+                },
+                client: Client {
+                    id: 1337,
+                    response_sender,
+                },
+            }))
+            .await
+            .unwrap();
+        let response = response_receiver.recv().await.unwrap();
+        assert!(
+            matches!(
+                response,
+                Response::Textual(TextualResponse::Injections(InjectionsResponse::Unset {
+                    id: 42,
+                    result: Err(_),
+                }))
+            ),
+            "unexpected {response:?}",
+        );
+        match response_receiver.try_recv() {
+            Err(TryRecvError::Disconnected) => {}
+            response => panic!("unexpected result from try_recv(): {response:?}"),
+        }
+
+        drop(request_sender);
+        router_task.await.unwrap();
+    }
 }
@@ -6,13 +6,19 @@ use serde::{de::DeserializeOwned, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
 use tokio::{
     spawn,
-    sync::{mpsc::Receiver, Notify},
+    sync::{
+        mpsc::{Receiver, Sender},
+        Notify,
+    },
     task::JoinHandle,
 };
 
 use crate::{
     messages::{ParametersResponse, Response, TextualResponse},
-    server::client::Client,
+    server::{
+        audit::{audit, AuditAction, AuditMessage},
+        client::Client,
+    },
 };
 
 use super::StorageRequest;
@@ -21,6 +27,7 @@ pub fn storage<Parameters>(
     parameters_writer: Writer<Parameters>,
     parameters_changed: Arc<Notify>,
     mut request_receiver: Receiver<StorageRequest>,
+    audit_sender: Sender<AuditMessage>,
     parameters_directory: impl AsRef<Path> + Send + Sync + 'static,
     body_id: String,
     head_id: String,
@@ -36,6 +43,7 @@ where
                 &mut parameters,
                 &parameters_writer,
                 &parameters_changed,
+                &audit_sender,
                 &parameters_directory,
                 &body_id,
                 &head_id,
@@ -45,11 +53,13 @@ where
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_request<Parameters>(
     request: StorageRequest,
     parameters: &mut Parameters,
     parameters_writer: &Writer<Parameters>,
     parameters_changed: &Arc<Notify>,
+    audit_sender: &Sender<AuditMessage>,
     parameters_directory: impl AsRef<Path>,
     body_id: &str,
     head_id: &str,
@@ -75,7 +85,10 @@ async fn handle_request<Parameters>(
                 return;
             }
 
-            if let Err(error) = parameters.deserialize_path(&path, data) {
+            let old_value = parameters
+                .serialize_path(&path, serde_json::value::Serializer)
+                .ok();
+            if let Err(error) = parameters.deserialize_path(&path, data.clone()) {
                 respond(
                     client,
                     ParametersResponse::Update {
@@ -93,6 +106,16 @@ async fn handle_request<Parameters>(
             }
             parameters_changed.notify_one();
 
+            audit(
+                audit_sender,
+                client.id,
+                AuditAction::UpdateParameter {
+                    path,
+                    old_value,
+                    new_value: data,
+                },
+            )
+            .await;
             respond(client, ParametersResponse::Update { id, result: Ok(()) }).await;
         }
         StorageRequest::LoadFromDisk { client, id } => {
@@ -150,6 +173,18 @@ async fn handle_request<Parameters>(
                 return;
             }
 
+            audit(
+                audit_sender,
+                client.id,
+                AuditAction::StoreToDisk {
+                    scope,
+                    value: parameters
+                        .serialize_path(&path, serde_json::value::Serializer)
+                        .ok(),
+                    path,
+                },
+            )
+            .await;
             respond(
                 client,
                 ParametersResponse::StoreToDisk { id, result: Ok(()) },
@@ -186,10 +221,12 @@ mod tests {
         let (parameters_writer, _parameters_reader) = multiple_buffer_with_slots([42usize]);
         let parameters_changed = Arc::new(Notify::new());
         let (request_sender, request_receiver) = channel(1);
+        let (audit_sender, _audit_receiver) = channel(1);
         let subscriptions_task = storage(
             parameters_writer,
             parameters_changed,
             request_receiver,
+            audit_sender,
             ".",
             Default::default(),
             Default::default(),
@@ -255,10 +292,12 @@ mod tests {
         }]);
         let parameters_changed = Arc::new(Notify::new());
         let (request_sender, request_receiver) = channel(1);
+        let (audit_sender, mut audit_receiver) = channel(1);
         let subscriptions_task = storage(
             parameters_writer,
             parameters_changed.clone(),
             request_receiver,
+            audit_sender,
             ".",
             Default::default(),
             Default::default(),
@@ -293,6 +332,17 @@ mod tests {
         let parameters = parameters_reader.next();
         assert_eq!(parameters.existing_fields.get(&path), Some(value).as_ref());
         parameters_changed.notified().await;
+        assert_eq!(
+            audit_receiver.recv().await.unwrap(),
+            AuditMessage::Action {
+                client_id: 1337,
+                action: AuditAction::UpdateParameter {
+                    path,
+                    old_value: Some(Value::from(42)),
+                    new_value: Value::from(value),
+                },
+            }
+        );
 
         drop(request_sender);
         subscriptions_task.await.unwrap();
@@ -6,6 +6,7 @@ use std::{
 use framework::Reader;
 use futures_util::{stream::FuturesUnordered, StreamExt};
 use log::error;
+use serde_json::Value;
 use serialize_hierarchy::SerializeHierarchy;
 use tokio::{
     select, spawn,
@@ -18,11 +19,18 @@ use tokio::{
 
 use crate::{
     messages::{ParametersRequest, ParametersResponse, Path, Response, TextualResponse},
+    patch::diff,
     server::{client::Client, client_request::ClientRequest},
 };
 
 use super::StorageRequest;
 
+struct Subscription {
+    path: Path,
+    /// The value patches are computed against, `None` if the client wants whole values
+    last_sent_value: Option<Value>,
+}
+
 pub fn subscriptions<Parameters>(
     mut request_receiver: Receiver<ClientRequest<ParametersRequest>>,
     parameters_reader: Reader<Parameters>,
@@ -51,7 +59,7 @@ where
                     ).await;
                 },
                 _ = parameters_changed.notified() => {
-                    handle_changed_parameters(&parameters_reader, &subscriptions).await;
+                    handle_changed_parameters(&parameters_reader, &mut subscriptions).await;
                 }
             }
         }
@@ -62,7 +70,7 @@ async fn handle_request<Parameters>(
     request: ClientRequest<ParametersRequest>,
     parameters_reader: &Reader<Parameters>,
     storage_request_sender: &Sender<StorageRequest>,
-    subscriptions: &mut HashMap<(Client, usize), Path>,
+    subscriptions: &mut HashMap<(Client, usize), Subscription>,
     fields: &BTreeSet<String>,
 ) where
     Parameters: SerializeHierarchy,
@@ -106,7 +114,11 @@ async fn handle_request<Parameters>(
             )
             .await;
         }
-        ParametersRequest::Subscribe { id, ref path } => {
+        ParametersRequest::Subscribe {
+            id,
+            ref path,
+            patches,
+        } => {
             if !Parameters::exists(path) {
                 let error_message = format!("path {path:?} does not exist");
                 respond(
@@ -120,17 +132,6 @@ async fn handle_request<Parameters>(
                 return;
             }
 
-            let response = match subscriptions.entry((request.client.clone(), id)) {
-                Entry::Occupied(_) => ParametersResponse::Subscribe {
-                    id,
-                    result: Err(format!("already subscribed with id {id}")),
-                },
-                Entry::Vacant(entry) => {
-                    entry.insert(path.to_string());
-                    ParametersResponse::Subscribe { id, result: Ok(()) }
-                }
-            };
-
             let data = {
                 let parameters = parameters_reader.next();
                 parameters.serialize_path(path, serde_json::value::Serializer)
@@ -149,7 +150,32 @@ async fn handle_request<Parameters>(
                     return;
                 }
             };
-            respond(request.clone(), response).await;
+
+            match subscriptions.entry((request.client.clone(), id)) {
+                Entry::Occupied(_) => {
+                    respond(
+                        request,
+                        ParametersResponse::Subscribe {
+                            id,
+                            result: Err(format!("already subscribed with id {id}")),
+                        },
+                    )
+                    .await;
+                    return;
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(Subscription {
+                        path: path.to_string(),
+                        last_sent_value: patches.then(|| data.clone()),
+                    });
+                }
+            };
+
+            respond(
+                request.clone(),
+                ParametersResponse::Subscribe { id, result: Ok(()) },
+            )
+            .await;
             respond(
                 request,
                 ParametersResponse::SubscribedData {
@@ -234,42 +260,52 @@ async fn respond(request: ClientRequest<ParametersRequest>, response: Parameters
 
 async fn handle_changed_parameters<Parameters>(
     parameters_reader: &Reader<Parameters>,
-    subscriptions: &HashMap<(Client, usize), Path>,
+    subscriptions: &mut HashMap<(Client, usize), Subscription>,
 ) where
     Parameters: SerializeHierarchy,
 {
-    let items: HashMap<_, _> = {
+    let items: Vec<_> = {
         let parameters = parameters_reader.next();
         subscriptions
-            .iter()
-            .filter_map(|((client, subscription_id), path)| {
-                let data = match parameters.serialize_path(path, serde_json::value::Serializer) {
+            .iter_mut()
+            .filter_map(|((client, subscription_id), subscription)| {
+                let data = match parameters
+                    .serialize_path(&subscription.path, serde_json::value::Serializer)
+                {
                     Ok(data) => data,
                     Err(error) => {
-                        error!("failed to serialize {:?}: {error:?}", path);
+                        error!("failed to serialize {:?}: {error:?}", subscription.path);
                         return None;
                     }
                 };
-                Some(((client.clone(), *subscription_id), data))
+                let response = match &mut subscription.last_sent_value {
+                    Some(last_sent_value) => {
+                        let patch = diff(last_sent_value, &data);
+                        if patch.is_empty() {
+                            return None;
+                        }
+                        *last_sent_value = data;
+                        ParametersResponse::SubscribedPatch {
+                            subscription_id: *subscription_id,
+                            patch,
+                        }
+                    }
+                    None => ParametersResponse::SubscribedData {
+                        subscription_id: *subscription_id,
+                        data,
+                    },
+                };
+                Some((client.response_sender.clone(), response))
             })
             .collect()
     };
-    let send_results: Vec<_> = FuturesUnordered::from_iter(
-        items
-            .into_iter()
-            .map(|((client, subscription_id), data)| {
-                (
-                    client.response_sender,
-                    Response::Textual(TextualResponse::Parameters(
-                        ParametersResponse::SubscribedData {
-                            subscription_id,
-                            data,
-                        },
-                    )),
-                )
-            })
-            .map(|(response_sender, data)| async move { response_sender.send(data).await }),
-    )
+    let send_results: Vec<_> = FuturesUnordered::from_iter(items.into_iter().map(
+        |(response_sender, response)| async move {
+            response_sender
+                .send(Response::Textual(TextualResponse::Parameters(response)))
+                .await
+        },
+    ))
     .collect()
     .await;
     for result in send_results.into_iter() {
@@ -291,6 +327,8 @@ mod tests {
         task::yield_now,
     };
 
+    use crate::messages::PatchOperation;
+
     use super::*;
 
     #[tokio::test]
@@ -474,6 +512,7 @@ mod tests {
                 request: ParametersRequest::Subscribe {
                     id: ID,
                     path: path.clone(),
+                    patches: false,
                 },
                 client: Client {
                     id: client_id,
@@ -513,6 +552,7 @@ mod tests {
                 request: ParametersRequest::Subscribe {
                     id: ID,
                     path: path.clone(),
+                    patches: false,
                 },
                 client: Client {
                     id: client_id,
@@ -567,6 +607,7 @@ mod tests {
                 request: ParametersRequest::Subscribe {
                     id: ID,
                     path: path.clone(),
+                    patches: false,
                 },
                 client: Client {
                     id: 1337,
@@ -606,6 +647,7 @@ mod tests {
                 request: ParametersRequest::Subscribe {
                     id: ID,
                     path: path.clone(),
+                    patches: false,
                 },
                 client: Client {
                     id: 7331,
@@ -660,6 +702,7 @@ mod tests {
                 request: ParametersRequest::Subscribe {
                     id: 42,
                     path: path.clone(),
+                    patches: false,
                 },
                 client: Client {
                     id: client_id,
@@ -699,6 +742,7 @@ mod tests {
                 request: ParametersRequest::Subscribe {
                     id: 1337,
                     path: path.clone(),
+                    patches: false,
                 },
                 client: Client {
                     id: client_id,
@@ -807,6 +851,7 @@ mod tests {
                 request: ParametersRequest::Subscribe {
                     id: SUBSCRIPTION_ID,
                     path: path.clone(),
+                    patches: false,
                 },
                 client: Client {
                     id: client_id,
@@ -934,6 +979,7 @@ mod tests {
                 request: ParametersRequest::Subscribe {
                     id: SUBSCRIPTION_ID,
                     path: path.clone(),
+                    patches: false,
                 },
                 client: Client {
                     id: client_id,
@@ -1226,6 +1272,7 @@ mod tests {
                 request: ParametersRequest::Subscribe {
                     id: SUBSCRIPTION_ID,
                     path: path.clone(),
+                    patches: false,
                 },
                 client: Client {
                     id: client_id,
@@ -1311,4 +1358,117 @@ mod tests {
         drop(request_sender);
         subscriptions_task.await.unwrap();
     }
+
+    #[tokio::test]
+    async fn patches_from_notified_parameters_are_sent_to_subscribed_client() {
+        let (request_sender, request_receiver) = channel(1);
+        let path = "a.b.c".to_string();
+        let value = serde_json::json!({"x": 1, "y": {"z": 2}});
+        let (parameters_writer, parameters_reader) = multiple_buffer_with_slots([ParametersFake {
+            existing_fields: [(path.clone(), value.clone())].into(),
+        }]);
+        let parameters_changed = Arc::new(Notify::new());
+        let (storage_request_sender, _storage_request_receiver) = channel(1);
+        let subscriptions_task = subscriptions(
+            request_receiver,
+            parameters_reader,
+            parameters_changed.clone(),
+            storage_request_sender,
+        );
+
+        const SUBSCRIPTION_ID: usize = 42;
+        let client_id = 1337;
+
+        let (response_sender, mut response_receiver) = channel(1);
+        request_sender
+            .send(ClientRequest {
+                request: ParametersRequest::Subscribe {
+                    id: SUBSCRIPTION_ID,
+                    path: path.clone(),
+                    patches: true,
+                },
+                client: Client {
+                    id: client_id,
+                    response_sender: response_sender.clone(),
+                },
+            })
+            .await
+            .unwrap();
+        let response = response_receiver.recv().await.unwrap();
+        assert!(
+            matches!(
+                response,
+                Response::Textual(TextualResponse::Parameters(ParametersResponse::Subscribe {
+                    id: SUBSCRIPTION_ID,
+                    result: Ok(()),
+                }))
+            ),
+            "unexpected {response:?}",
+        );
+        let subscribed_data = response_receiver.recv().await.unwrap();
+        assert_eq!(
+            subscribed_data,
+            Response::Textual(TextualResponse::Parameters(
+                ParametersResponse::SubscribedData {
+                    subscription_id: SUBSCRIPTION_ID,
+                    data: value,
+                }
+            )),
+        );
+
+        parameters_writer
+            .next()
+            .existing_fields
+            .insert(path.clone(), serde_json::json!({"x": 1, "y": {"z": 3}}));
+        parameters_changed.notify_one();
+        let subscribed_patch = response_receiver.recv().await.unwrap();
+        assert_eq!(
+            subscribed_patch,
+            Response::Textual(TextualResponse::Parameters(
+                ParametersResponse::SubscribedPatch {
+                    subscription_id: SUBSCRIPTION_ID,
+                    patch: vec![PatchOperation::Replace {
+                        path: "/y/z".to_string(),
+                        value: Value::from(3),
+                    }],
+                }
+            )),
+        );
+
+        // unchanged parameters result in an empty patch which is not sent
+        parameters_changed.notify_one();
+        request_sender
+            .send(ClientRequest {
+                request: ParametersRequest::Unsubscribe {
+                    id: 1337,
+                    subscription_id: SUBSCRIPTION_ID,
+                },
+                client: Client {
+                    id: client_id,
+                    response_sender: response_sender.clone(),
+                },
+            })
+            .await
+            .unwrap();
+        let response = response_receiver.recv().await.unwrap();
+        assert!(
+            matches!(
+                response,
+                Response::Textual(TextualResponse::Parameters(
+                    ParametersResponse::Unsubscribe {
+                        id: 1337,
+                        result: Ok(()),
+                    }
+                ))
+            ),
+            "unexpected {response:?}",
+        );
+
+        drop(request_sender);
+        subscriptions_task.await.unwrap();
+        match response_receiver.try_recv() {
+            Err(TryRecvError::Empty) => {}
+            response => panic!("unexpected result from try_recv(): {response:?}"),
+        }
+    }
 }
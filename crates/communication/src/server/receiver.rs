@@ -1,4 +1,6 @@
+use framework::ConnectionRole;
 use futures_util::{stream::SplitStream, StreamExt};
+use log::error;
 use serde_json::from_str;
 use tokio::{net::TcpStream, select, sync::mpsc::Sender};
 use tokio_tungstenite::{
@@ -8,11 +10,14 @@ use tokio_tungstenite::{
 use tokio_util::sync::CancellationToken;
 
 use crate::{
-    messages::{OutputsRequest, ParametersRequest, Request, Response},
+    messages::{
+        InjectionsRequest, InjectionsResponse, OutputsRequest, ParametersRequest,
+        ParametersResponse, Request, Response, TextualResponse,
+    },
     server::client_request::ClientRequest,
 };
 
-use super::{client::Client, connection::ReceiverOrSenderError, outputs};
+use super::{audit::AuditMessage, client::Client, connection::ReceiverOrSenderError, outputs};
 
 #[allow(clippy::too_many_arguments)]
 pub async fn receiver(
@@ -21,9 +26,11 @@ pub async fn receiver(
     keep_running: CancellationToken,
     keep_only_self_running: CancellationToken,
     client_id: usize,
+    role: ConnectionRole,
     response_sender: Sender<Response>,
     outputs_sender: Sender<outputs::Request>,
     parameters_sender: Sender<ClientRequest<ParametersRequest>>,
+    audit_sender: Sender<AuditMessage>,
 ) {
     select! {
         _ = async {
@@ -33,6 +40,7 @@ pub async fn receiver(
                     &error_sender,
                     &keep_only_self_running,
                     client_id,
+                    role,
                     &response_sender,
                     &outputs_sender,
                     &parameters_sender,
@@ -53,6 +61,16 @@ pub async fn receiver(
         }))
         .await
         .expect("receiver should always wait for all senders");
+    outputs_sender
+        .send(outputs::Request::InjectionsRequest(ClientRequest {
+            request: InjectionsRequest::UnsetEverything,
+            client: Client {
+                id: client_id,
+                response_sender: response_sender.clone(),
+            },
+        }))
+        .await
+        .expect("receiver should always wait for all senders");
     parameters_sender
         .send(ClientRequest {
             request: ParametersRequest::UnsubscribeEverything,
@@ -63,13 +81,21 @@ pub async fn receiver(
         })
         .await
         .expect("receiver should always wait for all senders");
+    if let Err(error) = audit_sender
+        .send(AuditMessage::Disconnected { client_id })
+        .await
+    {
+        error!("failed to send disconnection to audit log: {error}");
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_message(
     message: Result<Message, tokio_tungstenite::tungstenite::Error>,
     error_sender: &Sender<ReceiverOrSenderError>,
     keep_only_self_running: &CancellationToken,
     client_id: usize,
+    role: ConnectionRole,
     response_sender: &Sender<Response>,
     outputs_sender: &Sender<outputs::Request>,
     parameters_sender: &Sender<ClientRequest<ParametersRequest>>,
@@ -104,6 +130,16 @@ async fn handle_message(
                 }
             };
 
+            if role == ConnectionRole::Observer && modifies_robot(&request) {
+                if let Some(response) = observer_error_response(&request) {
+                    response_sender
+                        .send(Response::Textual(response))
+                        .await
+                        .expect("receiver should always wait for all senders");
+                }
+                return;
+            }
+
             let client = Client {
                 id: client_id,
                 response_sender: response_sender.clone(),
@@ -118,7 +154,15 @@ async fn handle_message(
                         .await
                         .expect("receiver should always wait for all senders");
                 }
-                Request::Injections(_) => todo!(),
+                Request::Injections(request) => {
+                    outputs_sender
+                        .send(outputs::Request::InjectionsRequest(ClientRequest {
+                            request,
+                            client,
+                        }))
+                        .await
+                        .expect("receiver should always wait for all senders");
+                }
                 Request::Parameters(request) => {
                     parameters_sender
                         .send(ClientRequest { request, client })
@@ -140,6 +184,57 @@ async fn handle_message(
     }
 }
 
+fn modifies_robot(request: &Request) -> bool {
+    matches!(
+        request,
+        Request::Injections(_)
+            | Request::Parameters(
+                ParametersRequest::Update { .. }
+                    | ParametersRequest::LoadFromDisk { .. }
+                    | ParametersRequest::StoreToDisk { .. }
+            )
+    )
+}
+
+/// Error response to a request observers are not permitted to send, `None` if the request has no
+/// response
+fn observer_error_response(request: &Request) -> Option<TextualResponse> {
+    let reason = || Err("observers are not permitted to modify the robot".to_string());
+    match *request {
+        Request::Injections(InjectionsRequest::Set { id, .. }) => {
+            Some(TextualResponse::Injections(InjectionsResponse::Set {
+                id,
+                result: reason(),
+            }))
+        }
+        Request::Injections(InjectionsRequest::Unset { id, .. }) => {
+            Some(TextualResponse::Injections(InjectionsResponse::Unset {
+                id,
+                result: reason(),
+            }))
+        }
+        Request::Parameters(ParametersRequest::Update { id, .. }) => {
+            Some(TextualResponse::Parameters(ParametersResponse::Update {
+                id,
+                result: reason(),
+            }))
+        }
+        Request::Parameters(ParametersRequest::LoadFromDisk { id }) => Some(
+            TextualResponse::Parameters(ParametersResponse::LoadFromDisk {
+                id,
+                result: reason(),
+            }),
+        ),
+        Request::Parameters(ParametersRequest::StoreToDisk { id, .. }) => Some(
+            TextualResponse::Parameters(ParametersResponse::StoreToDisk {
+                id,
+                result: reason(),
+            }),
+        ),
+        _ => None,
+    }
+}
+
 async fn send_error(
     error: ReceiverOrSenderError,
     error_sender: &Sender<ReceiverOrSenderError>,
@@ -158,3 +253,71 @@ async fn send_error(
         .await
         .expect("receiver should always wait for all senders");
 }
+
+#[cfg(test)]
+mod tests {
+    use parameters::directory::{Id, Location, Scope};
+
+    use super::*;
+
+    #[test]
+    fn reading_requests_do_not_modify_robot() {
+        assert!(!modifies_robot(&Request::Outputs(
+            OutputsRequest::GetFields { id: 42 }
+        )));
+        assert!(!modifies_robot(&Request::Parameters(
+            ParametersRequest::GetCurrent {
+                id: 42,
+                path: "a.b.c".to_string(),
+            }
+        )));
+        assert!(!modifies_robot(&Request::Parameters(
+            ParametersRequest::Subscribe {
+                id: 42,
+                path: "a.b.c".to_string(),
+                patches: false,
+            }
+        )));
+    }
+
+    #[test]
+    fn writing_requests_result_in_errors_for_observers() {
+        let store_to_disk = Request::Parameters(ParametersRequest::StoreToDisk {
+            id: 42,
+            scope: Scope {
+                location: Location::All,
+                id: Id::All,
+            },
+            path: "a.b.c".to_string(),
+        });
+        assert!(modifies_robot(&store_to_disk));
+        assert!(matches!(
+            observer_error_response(&store_to_disk),
+            Some(TextualResponse::Parameters(
+                ParametersResponse::StoreToDisk {
+                    id: 42,
+                    result: Err(_),
+                }
+            ))
+        ));
+
+        let set_injection = Request::Injections(InjectionsRequest::Set {
+            id: 1337,
+            cycler_instance: "Control".to_string(),
+            path: "main.ball_position".to_string(),
+            data: serde_json::Value::Null,
+        });
+        assert!(modifies_robot(&set_injection));
+        assert!(matches!(
+            observer_error_response(&set_injection),
+            Some(TextualResponse::Injections(InjectionsResponse::Set {
+                id: 1337,
+                result: Err(_),
+            }))
+        ));
+
+        let unset_everything = Request::Injections(InjectionsRequest::UnsetEverything);
+        assert!(modifies_robot(&unset_everything));
+        assert_eq!(observer_error_response(&unset_everything), None);
+    }
+}
@@ -3,17 +3,16 @@ use std::{
     fmt::Debug,
     io,
     iter::repeat_with,
-    path::Path,
-    sync::Arc,
+    path::{Path, PathBuf},
+    sync::{atomic::AtomicUsize, Arc},
     thread::{self, JoinHandle},
 };
 
-use framework::{multiple_buffer_with_slots, Reader, Writer};
+use framework::{multiple_buffer_with_slots, CommunicationAddresses, Reader, Writer};
 use parameters::directory::{deserialize, DirectoryError};
 use serde::{de::DeserializeOwned, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
 use tokio::{
-    net::ToSocketAddrs,
     runtime::{self, Runtime as TokioRuntime},
     sync::{
         mpsc::{channel, Sender},
@@ -26,7 +25,8 @@ use crate::server::outputs::router::router;
 
 use super::{
     acceptor::{acceptor, AcceptError},
-    outputs::{provider::provider, Request},
+    audit::{audit_log, AuditMessage},
+    outputs::{provider::provider, Injections, Request},
     parameters::{storage::storage, subscriptions::subscriptions},
 };
 
@@ -48,6 +48,7 @@ pub struct Runtime<Parameters> {
     join_handle: JoinHandle<Result<(), StartError>>,
     runtime: Arc<TokioRuntime>,
     outputs_sender: Sender<Request>,
+    audit_sender: Sender<AuditMessage>,
     parameters_reader: Reader<Parameters>,
     parameters_changed: Arc<Notify>,
 }
@@ -57,7 +58,8 @@ where
     Parameters: Clone + DeserializeOwned + Send + Serialize + SerializeHierarchy + Sync + 'static,
 {
     pub fn start(
-        addresses: Option<impl ToSocketAddrs + Send + Sync + 'static>,
+        addresses: Option<CommunicationAddresses>,
+        audit_log_path: Option<PathBuf>,
         parameters_directory: impl AsRef<Path> + Send + Sync + 'static,
         body_id: String,
         head_id: String,
@@ -102,26 +104,38 @@ where
 
                     let (parameters_sender, parameters_receiver) = channel(1);
                     let (parameters_storage_sender, parameters_storage_receiver) = channel(1);
+                    let (audit_sender, audit_receiver) = channel(1);
 
                     runtime_sender
                         .send(Some((
                             inner_runtime,
                             outputs_sender.clone(),
+                            audit_sender.clone(),
                             parameters_reader.clone(),
                             parameters_changed.clone(),
                         )))
                         .ok()
                         .expect("successful thread creation should always wait for runtime_sender");
 
-                    // only start acceptor if addresses is Some
-                    let acceptor_task = addresses.map(|addresses| {
-                        acceptor(
-                            addresses,
-                            keep_running.clone(),
-                            outputs_sender,
-                            parameters_sender,
-                        )
-                    });
+                    // only start acceptors if addresses is Some
+                    let next_client_id = Arc::new(AtomicUsize::default());
+                    let acceptor_tasks: Vec<_> = addresses
+                        .iter()
+                        .flat_map(CommunicationAddresses::listeners)
+                        .map(|(address, role)| {
+                            acceptor(
+                                address,
+                                role,
+                                next_client_id.clone(),
+                                keep_running.clone(),
+                                outputs_sender.clone(),
+                                parameters_sender.clone(),
+                                audit_sender.clone(),
+                            )
+                        })
+                        .collect();
+                    drop(outputs_sender);
+                    drop(parameters_sender);
                     let outputs_task = router(outputs_receiver);
                     let parameters_subscriptions_task = subscriptions(
                         parameters_receiver,
@@ -129,10 +143,12 @@ where
                         parameters_changed.clone(),
                         parameters_storage_sender,
                     );
+                    let audit_log_task = audit_log(audit_log_path, audit_receiver);
                     let parameters_storage_task = storage(
                         parameters_writer,
                         parameters_changed.clone(),
                         parameters_storage_receiver,
+                        audit_sender,
                         parameters_directory,
                         body_id,
                         head_id,
@@ -140,16 +156,17 @@ where
 
                     keep_running.cancelled().await;
 
-                    let acceptor_task_result = match acceptor_task {
-                        Some(acceptor_task) => Some(acceptor_task.await),
-                        None => None,
-                    };
+                    let mut acceptor_task_results = vec![];
+                    for acceptor_task in acceptor_tasks {
+                        acceptor_task_results.push(acceptor_task.await);
+                    }
                     let outputs_task_result = outputs_task.await;
                     let parameters_subscriptions_task_result = parameters_subscriptions_task.await;
                     let parameters_storage_task_result = parameters_storage_task.await;
+                    let audit_log_task_result = audit_log_task.await;
 
                     let mut task_errors = vec![];
-                    if let Some(acceptor_task_result) = acceptor_task_result {
+                    for acceptor_task_result in acceptor_task_results {
                         if let Err(error) =
                             acceptor_task_result.expect("failed to join acceptor task")
                         {
@@ -159,6 +176,7 @@ where
                     outputs_task_result.expect("failed to join outputs task");
                     parameters_subscriptions_task_result.expect("failed to join outputs task");
                     parameters_storage_task_result.expect("failed to join outputs task");
+                    audit_log_task_result.expect("failed to join audit log task");
 
                     if task_errors.is_empty() {
                         Ok(())
@@ -169,7 +187,7 @@ where
             })
             .map_err(StartError::ThreadNotStarted)?;
 
-        let (runtime, outputs_sender, parameters_reader, parameters_changed) =
+        let (runtime, outputs_sender, audit_sender, parameters_reader, parameters_changed) =
             match runtime_receiver
                 .blocking_recv()
                 .expect("successful thread creation should always send into runtime_sender")
@@ -187,6 +205,7 @@ where
             join_handle,
             runtime,
             outputs_sender,
+            audit_sender,
             parameters_reader,
             parameters_changed,
         })
@@ -194,18 +213,27 @@ where
 
     pub fn join(self) -> thread::Result<Result<(), StartError>> {
         drop(self.outputs_sender);
+        drop(self.audit_sender);
         self.join_handle.join()
     }
 
+    /// Serves the outputs of a cycler instance, the returned reader contains the injections the
+    /// cycler needs to apply to its outputs after each cycle
     pub fn register_cycler_instance<Outputs>(
         &self,
         cycler_instance: &'static str,
         outputs_changed: Arc<Notify>,
         outputs_reader: Reader<Outputs>,
         subscribed_outputs_writer: Writer<HashSet<String>>,
-    ) where
-        Outputs: SerializeHierarchy + Send + Sync + 'static,
+    ) -> Reader<Injections>
+    where
+        Outputs: Default + SerializeHierarchy + Send + Sync + 'static,
     {
+        let (injections_writer, injections_reader) = multiple_buffer_with_slots([
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        ]);
         let _guard = self.runtime.enter();
         provider(
             self.outputs_sender.clone(),
@@ -213,7 +241,10 @@ where
             outputs_changed,
             outputs_reader,
             subscribed_outputs_writer,
+            injections_writer,
+            self.audit_sender.clone(),
         );
+        injections_reader
     }
 
     pub fn get_parameters_reader(&self) -> Reader<Parameters> {
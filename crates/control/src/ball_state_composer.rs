@@ -64,7 +64,7 @@ impl BallStateComposer {
             (None, Some(ball_position), Some(robot_to_field)) => Some(create_ball_state(
                 robot_to_field.inverse() * ball_position.position,
                 ball_position.position,
-                ball_position.velocity,
+                robot_to_field.inverse() * ball_position.velocity,
                 ball_position.last_seen,
                 &mut self.last_ball_field_side,
                 context.penalty_shot_direction.copied(),
@@ -1,15 +1,16 @@
 use types::{
-    motion_command::{HeadMotion::Unstiff, MotionCommand},
+    decision_trace::Rejection,
+    motion_command::{HeadMotion::Calibrate, MotionCommand},
     primary_state::PrimaryState,
     world_state::WorldState,
 };
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+pub fn execute(world_state: &WorldState) -> Result<MotionCommand, Rejection> {
     match world_state.robot.primary_state {
-        PrimaryState::Calibration => Some(MotionCommand::Stand {
-            head: Unstiff,
+        PrimaryState::Calibration => Ok(MotionCommand::Stand {
+            head: Calibrate,
             is_energy_saving: false,
         }),
-        _ => None,
+        primary_state => Err(Rejection::UnsuitablePrimaryState { primary_state }),
     }
 }
@@ -0,0 +1,180 @@
+use spl_network_messages::GamePhase;
+use types::{
+    decision_trace::Rejection, field_dimensions::FieldDimensions,
+    game_controller_state::GameControllerState, motion_command::MotionCommand,
+    parameters::CatchParameters, primary_state::PrimaryState, support_foot::Side,
+    world_state::WorldState,
+};
+
+pub fn execute(
+    world_state: &WorldState,
+    field_dimensions: &FieldDimensions,
+    parameters: &CatchParameters,
+) -> Result<MotionCommand, Rejection> {
+    match world_state.robot.primary_state {
+        PrimaryState::Playing => {}
+        primary_state => return Err(Rejection::UnsuitablePrimaryState { primary_state }),
+    }
+    if let Some(GameControllerState {
+        game_phase: GamePhase::PenaltyShootout { .. },
+        ..
+    }) = world_state.game_controller_state
+    {
+        return Err(Rejection::PenaltyShootout);
+    }
+    let ball = world_state.ball.ok_or(Rejection::NoBall)?;
+    let robot_to_field = world_state
+        .robot
+        .robot_to_field
+        .ok_or(Rejection::NoLocalization)?;
+
+    let ball_in_field_velocity = robot_to_field * ball.ball_in_ground_velocity;
+    if ball_in_field_velocity.x > -parameters.minimum_ball_velocity_towards_goal {
+        return Err(Rejection::BallIsNotApproachingGoal);
+    }
+    let own_goal_line_x = -field_dimensions.length / 2.0;
+    let time_to_goal_line = (own_goal_line_x - ball.ball_in_field.x) / ball_in_field_velocity.x;
+    let goal_line_crossing_y = ball.ball_in_field.y + ball_in_field_velocity.y * time_to_goal_line;
+    if goal_line_crossing_y.abs() > field_dimensions.goal_inner_width / 2.0 + parameters.goal_margin
+    {
+        return Err(Rejection::BallMissesGoal);
+    }
+
+    let ball_velocity = ball.ball_in_ground_velocity;
+    if ball.ball_in_ground.x <= 0.0 || ball_velocity.x >= 0.0 {
+        return Err(Rejection::BallPassesOutOfReach);
+    }
+    let time_to_reach_robot = -ball.ball_in_ground.x / ball_velocity.x;
+    let passing_y = ball.ball_in_ground.y + ball_velocity.y * time_to_reach_robot;
+    if passing_y.abs() > parameters.maximum_catch_distance {
+        return Err(Rejection::BallPassesOutOfReach);
+    }
+    if time_to_reach_robot > parameters.maximum_time_to_reach_robot {
+        return Err(Rejection::BallArrivesTooLate);
+    }
+
+    if passing_y.abs() < parameters.squat_catch_width {
+        Ok(MotionCommand::SquatCatch)
+    } else if passing_y > 0.0 {
+        Ok(MotionCommand::StationaryCatch { side: Side::Left })
+    } else {
+        Ok(MotionCommand::StationaryCatch { side: Side::Right })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use nalgebra::{point, vector, Isometry2, Point2, Vector2};
+    use types::world_state::{BallState, RobotState};
+
+    use super::*;
+
+    const PARAMETERS: CatchParameters = CatchParameters {
+        minimum_ball_velocity_towards_goal: 0.5,
+        goal_margin: 0.2,
+        squat_catch_width: 0.15,
+        maximum_catch_distance: 0.6,
+        maximum_time_to_reach_robot: 0.8,
+    };
+
+    fn field_dimensions() -> FieldDimensions {
+        FieldDimensions {
+            length: 9.0,
+            goal_inner_width: 1.5,
+            ..Default::default()
+        }
+    }
+
+    /// Keeper standing in front of the own goal facing the opponent goal
+    fn world_state(
+        ball_in_ground: Point2<f32>,
+        ball_in_ground_velocity: Vector2<f32>,
+    ) -> WorldState {
+        let robot_to_field = Isometry2::translation(-4.3, 0.0);
+        WorldState {
+            ball: Some(BallState {
+                ball_in_ground,
+                ball_in_field: robot_to_field * ball_in_ground,
+                ball_in_ground_velocity,
+                last_seen_ball: UNIX_EPOCH,
+                penalty_shot_direction: None,
+                field_side: Side::Left,
+            }),
+            robot: RobotState {
+                robot_to_field: Some(robot_to_field),
+                primary_state: PrimaryState::Playing,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ball_approaching_robot_is_caught_with_squat() {
+        let world_state = world_state(point![2.0, 0.05], vector![-4.0, 0.0]);
+
+        assert!(matches!(
+            execute(&world_state, &field_dimensions(), &PARAMETERS),
+            Ok(MotionCommand::SquatCatch)
+        ));
+    }
+
+    #[test]
+    fn ball_passing_beside_robot_is_caught_sideways() {
+        let world_state = world_state(point![2.0, 0.4], vector![-4.0, 0.0]);
+        assert!(matches!(
+            execute(&world_state, &field_dimensions(), &PARAMETERS),
+            Ok(MotionCommand::StationaryCatch { side: Side::Left })
+        ));
+
+        let world_state = world_state(point![2.0, -0.4], vector![-4.0, 0.0]);
+        assert!(matches!(
+            execute(&world_state, &field_dimensions(), &PARAMETERS),
+            Ok(MotionCommand::StationaryCatch { side: Side::Right })
+        ));
+    }
+
+    #[test]
+    fn slow_or_receding_ball_is_not_caught() {
+        for velocity in [vector![-0.3, 0.0], vector![2.0, 0.0]] {
+            let world_state = world_state(point![2.0, 0.0], velocity);
+
+            assert!(matches!(
+                execute(&world_state, &field_dimensions(), &PARAMETERS),
+                Err(Rejection::BallIsNotApproachingGoal)
+            ));
+        }
+    }
+
+    #[test]
+    fn ball_missing_goal_is_not_caught() {
+        let world_state = world_state(point![2.0, 0.0], vector![-4.0, 2.0]);
+
+        assert!(matches!(
+            execute(&world_state, &field_dimensions(), &PARAMETERS),
+            Err(Rejection::BallMissesGoal)
+        ));
+    }
+
+    #[test]
+    fn ball_out_of_reach_is_not_caught() {
+        let world_state = world_state(point![2.0, 0.8], vector![-4.0, 0.0]);
+
+        assert!(matches!(
+            execute(&world_state, &field_dimensions(), &PARAMETERS),
+            Err(Rejection::BallPassesOutOfReach)
+        ));
+    }
+
+    #[test]
+    fn ball_arriving_too_late_is_not_caught_yet() {
+        let world_state = world_state(point![4.0, 0.0], vector![-4.0, 0.0]);
+
+        assert!(matches!(
+            execute(&world_state, &field_dimensions(), &PARAMETERS),
+            Err(Rejection::BallArrivesTooLate)
+        ));
+    }
+}
@@ -5,6 +5,7 @@ use geometry::look_at::LookAt;
 use nalgebra::{distance, point, vector, Isometry2, Point2};
 use spl_network_messages::{GamePhase, SubState, Team};
 use types::{
+    decision_trace::Rejection,
     field_dimensions::FieldDimensions,
     game_controller_state::GameControllerState,
     line::Line,
@@ -46,7 +47,7 @@ impl<'cycle> Defend<'cycle> {
         &self,
         pose: Isometry2<f32>,
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
-    ) -> Option<MotionCommand> {
+    ) -> Result<MotionCommand, Rejection> {
         self.walk_and_stand
             .execute(pose, self.look_action.execute(), path_obstacles_output)
     }
@@ -54,7 +55,7 @@ impl<'cycle> Defend<'cycle> {
     pub fn left(
         &self,
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
-    ) -> Option<MotionCommand> {
+    ) -> Result<MotionCommand, Rejection> {
         let pose = defend_left_pose(self.world_state, self.field_dimensions, self.role_positions)?;
         self.with_pose(pose, path_obstacles_output)
     }
@@ -62,7 +63,7 @@ impl<'cycle> Defend<'cycle> {
     pub fn right(
         &self,
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
-    ) -> Option<MotionCommand> {
+    ) -> Result<MotionCommand, Rejection> {
         let pose = defend_right_pose(self.world_state, self.field_dimensions, self.role_positions)?;
         self.with_pose(pose, path_obstacles_output)
     }
@@ -70,7 +71,7 @@ impl<'cycle> Defend<'cycle> {
     pub fn penalty_kick(
         &self,
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
-    ) -> Option<MotionCommand> {
+    ) -> Result<MotionCommand, Rejection> {
         let pose =
             defend_penalty_kick(self.world_state, self.field_dimensions, self.role_positions)?;
         self.with_pose(pose, path_obstacles_output)
@@ -79,7 +80,7 @@ impl<'cycle> Defend<'cycle> {
     pub fn goal(
         &self,
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
-    ) -> Option<MotionCommand> {
+    ) -> Result<MotionCommand, Rejection> {
         let pose = defend_goal_pose(self.world_state, self.field_dimensions, self.role_positions)?;
         self.with_pose(pose, path_obstacles_output)
     }
@@ -87,7 +88,7 @@ impl<'cycle> Defend<'cycle> {
     pub fn kick_off(
         &self,
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
-    ) -> Option<MotionCommand> {
+    ) -> Result<MotionCommand, Rejection> {
         let pose =
             defend_kick_off_pose(self.world_state, self.field_dimensions, self.role_positions)?;
         self.with_pose(pose, path_obstacles_output)
@@ -98,8 +99,11 @@ fn defend_left_pose(
     world_state: &WorldState,
     field_dimensions: &FieldDimensions,
     role_positions: &RolePositionsParameters,
-) -> Option<Isometry2<f32>> {
-    let robot_to_field = world_state.robot.robot_to_field?;
+) -> Result<Isometry2<f32>, Rejection> {
+    let robot_to_field = world_state
+        .robot
+        .robot_to_field
+        .ok_or(Rejection::NoLocalization)?;
     let ball = world_state
         .rule_ball
         .or(world_state.ball)
@@ -120,15 +124,18 @@ fn defend_left_pose(
         field_dimensions,
     );
     let defend_pose = block_on_circle(ball.ball_in_field, position_to_defend, distance_to_target);
-    Some(robot_to_field.inverse() * defend_pose)
+    Ok(robot_to_field.inverse() * defend_pose)
 }
 
 fn defend_right_pose(
     world_state: &WorldState,
     field_dimensions: &FieldDimensions,
     role_positions: &RolePositionsParameters,
-) -> Option<Isometry2<f32>> {
-    let robot_to_field = world_state.robot.robot_to_field?;
+) -> Result<Isometry2<f32>, Rejection> {
+    let robot_to_field = world_state
+        .robot
+        .robot_to_field
+        .ok_or(Rejection::NoLocalization)?;
     let ball = world_state
         .rule_ball
         .or(world_state.ball)
@@ -149,15 +156,18 @@ fn defend_right_pose(
         field_dimensions,
     );
     let defend_pose = block_on_circle(ball.ball_in_field, position_to_defend, distance_to_target);
-    Some(robot_to_field.inverse() * defend_pose)
+    Ok(robot_to_field.inverse() * defend_pose)
 }
 
 fn defend_penalty_kick(
     world_state: &WorldState,
     field_dimensions: &FieldDimensions,
     role_positions: &RolePositionsParameters,
-) -> Option<Isometry2<f32>> {
-    let robot_to_field = world_state.robot.robot_to_field?;
+) -> Result<Isometry2<f32>, Rejection> {
+    let robot_to_field = world_state
+        .robot
+        .robot_to_field
+        .ok_or(Rejection::NoLocalization)?;
     let ball = world_state
         .rule_ball
         .or(world_state.ball)
@@ -179,15 +189,18 @@ fn defend_penalty_kick(
     );
 
     let defend_pose = block_on_circle(ball.ball_in_field, position_to_defend, distance_to_target);
-    Some(robot_to_field.inverse() * defend_pose)
+    Ok(robot_to_field.inverse() * defend_pose)
 }
 
 fn defend_goal_pose(
     world_state: &WorldState,
     field_dimensions: &FieldDimensions,
     role_positions: &RolePositionsParameters,
-) -> Option<Isometry2<f32>> {
-    let robot_to_field = world_state.robot.robot_to_field?;
+) -> Result<Isometry2<f32>, Rejection> {
+    let robot_to_field = world_state
+        .robot
+        .robot_to_field
+        .ok_or(Rejection::NoLocalization)?;
     let ball = world_state
         .rule_ball
         .or(world_state.ball)
@@ -218,15 +231,18 @@ fn defend_goal_pose(
         -field_dimensions.length / 2.0 + keeper_x_offset,
         -0.7..0.7,
     );
-    Some(robot_to_field.inverse() * defend_pose)
+    Ok(robot_to_field.inverse() * defend_pose)
 }
 
 fn defend_kick_off_pose(
     world_state: &WorldState,
     field_dimensions: &FieldDimensions,
     role_positions: &RolePositionsParameters,
-) -> Option<Isometry2<f32>> {
-    let robot_to_field = world_state.robot.robot_to_field?;
+) -> Result<Isometry2<f32>, Rejection> {
+    let robot_to_field = world_state
+        .robot
+        .robot_to_field
+        .ok_or(Rejection::NoLocalization)?;
     let absolute_ball_position = match world_state.ball {
         Some(ball) => ball.ball_in_field,
         None => Point2::origin(),
@@ -241,7 +257,7 @@ fn defend_kick_off_pose(
         position_to_defend,
         distance_to_target,
     );
-    Some(robot_to_field.inverse() * defend_pose)
+    Ok(robot_to_field.inverse() * defend_pose)
 }
 
 pub fn block_on_circle(
@@ -2,8 +2,12 @@ use geometry::look_at::LookAt;
 use nalgebra::{Isometry2, Point2};
 
 use types::{
+    decision_trace::Rejection,
+    kick_decision::KickType,
     motion_command::{HeadMotion, MotionCommand, OrientationMode},
-    parameters::{DribblingParameters, InWalkKickInfoParameters, InWalkKicksParameters},
+    parameters::{
+        DribblingParameters, InWalkKicksParameters, KickInfoParameters, StandingKicksParameters,
+    },
     planned_path::PathSegment,
     world_state::WorldState,
 };
@@ -15,36 +19,52 @@ pub fn execute(
     world_state: &WorldState,
     walk_path_planner: &WalkPathPlanner,
     in_walk_kicks: &InWalkKicksParameters,
+    standing_kicks: &StandingKicksParameters,
     parameters: &DribblingParameters,
     dribble_path: Option<Vec<PathSegment>>,
-) -> Option<MotionCommand> {
-    let ball_position = world_state.ball?.ball_in_ground;
+) -> Result<MotionCommand, Rejection> {
+    let ball_position = world_state.ball.ok_or(Rejection::NoBall)?.ball_in_ground;
     let head = HeadMotion::LookLeftAndRightOf {
         target: ball_position,
     };
-    let kick_decisions = world_state.kick_decisions.as_ref()?;
-    let instant_kick_decisions = world_state.instant_kick_decisions.as_ref()?;
+    let kick_decisions = world_state
+        .kick_decisions
+        .as_ref()
+        .ok_or(Rejection::NoKickDecisions)?;
+    let instant_kick_decisions = world_state
+        .instant_kick_decisions
+        .as_ref()
+        .ok_or(Rejection::NoKickDecisions)?;
 
     let available_kick = kick_decisions
         .iter()
         .chain(instant_kick_decisions.iter())
         .find(|decision| {
-            is_kick_pose_reached(decision.kick_pose, &in_walk_kicks[decision.variant])
+            is_kick_pose_reached(
+                decision.kick_pose,
+                decision.kick.info(in_walk_kicks, standing_kicks),
+            )
         });
     if let Some(kick) = available_kick {
-        let command = MotionCommand::InWalkKick {
-            head,
-            kick: kick.variant,
-            kicking_side: kick.kicking_side,
-            strength: kick.strength,
+        let command = match kick.kick {
+            KickType::InWalk { variant } => MotionCommand::InWalkKick {
+                head,
+                kick: variant,
+                kicking_side: kick.kicking_side,
+                strength: kick.strength,
+            },
+            KickType::Standing { variant } => MotionCommand::Kick {
+                variant,
+                side: kick.kicking_side,
+            },
         };
-        return Some(command);
+        return Ok(command);
     }
 
     let best_kick_decision = match kick_decisions.first() {
         Some(decision) => decision,
         None => {
-            return Some(MotionCommand::Stand {
+            return Ok(MotionCommand::Stand {
                 head,
                 is_energy_saving: false,
             })
@@ -68,9 +88,9 @@ pub fn execute(
     };
     match dribble_path {
         Some(path) => {
-            Some(walk_path_planner.walk_with_obstacle_avoiding_arms(head, orientation_mode, path))
+            Ok(walk_path_planner.walk_with_obstacle_avoiding_arms(head, orientation_mode, path))
         }
-        None => Some(MotionCommand::Stand {
+        None => Ok(MotionCommand::Stand {
             head,
             is_energy_saving: false,
         }),
@@ -79,7 +99,7 @@ pub fn execute(
 
 fn is_kick_pose_reached(
     kick_pose_to_robot: Isometry2<f32>,
-    kick_info: &InWalkKickInfoParameters,
+    kick_info: &KickInfoParameters,
 ) -> bool {
     let is_x_reached = kick_pose_to_robot.translation.x.abs() < kick_info.reached_thresholds.x;
     let is_y_reached = kick_pose_to_robot.translation.y.abs() < kick_info.reached_thresholds.y;
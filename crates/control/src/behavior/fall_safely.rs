@@ -1,10 +1,15 @@
-use types::{fall_state::FallState, motion_command::MotionCommand, world_state::WorldState};
+use types::{
+    decision_trace::Rejection, fall_state::FallState, motion_command::MotionCommand,
+    world_state::WorldState,
+};
 
-pub fn execute(world_state: &WorldState, has_ground_contact: bool) -> Option<MotionCommand> {
+pub fn execute(
+    world_state: &WorldState,
+    has_ground_contact: bool,
+) -> Result<MotionCommand, Rejection> {
     match (world_state.robot.fall_state, has_ground_contact) {
-        (FallState::Falling { direction }, true) => {
-            Some(MotionCommand::FallProtection { direction })
-        }
-        _ => None,
+        (FallState::Falling { direction }, true) => Ok(MotionCommand::FallProtection { direction }),
+        (FallState::Falling { .. }, false) => Err(Rejection::NoGroundContact),
+        _ => Err(Rejection::NotFalling),
     }
 }
@@ -1,8 +1,11 @@
-use types::{motion_command::MotionCommand, primary_state::PrimaryState, world_state::WorldState};
+use types::{
+    decision_trace::Rejection, motion_command::MotionCommand, primary_state::PrimaryState,
+    world_state::WorldState,
+};
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+pub fn execute(world_state: &WorldState) -> Result<MotionCommand, Rejection> {
     match world_state.robot.primary_state {
-        PrimaryState::Initial => Some(MotionCommand::Initial),
-        _ => None,
+        PrimaryState::Initial => Ok(MotionCommand::Initial),
+        primary_state => Err(Rejection::UnsuitablePrimaryState { primary_state }),
     }
 }
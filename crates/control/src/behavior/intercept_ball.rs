@@ -2,6 +2,7 @@ use geometry::line_segment::LineSegment;
 use nalgebra::{Isometry2, Point2, UnitComplex};
 use spl_network_messages::{GamePhase, SubState};
 use types::{
+    decision_trace::Rejection,
     filtered_game_state::FilteredGameState,
     game_controller_state::GameControllerState,
     line::Line,
@@ -16,72 +17,68 @@ pub fn execute(
     world_state: &WorldState,
     parameters: InterceptBallParameters,
     maximum_step_size: Step,
-) -> Option<MotionCommand> {
-    if let Some(
-        GameControllerState {
+) -> Result<MotionCommand, Rejection> {
+    match world_state.game_controller_state {
+        Some(GameControllerState {
             game_phase: GamePhase::PenaltyShootout { .. },
             ..
-        }
-        | GameControllerState {
+        }) => return Err(Rejection::PenaltyShootout),
+        Some(GameControllerState {
             sub_state: Some(SubState::PenaltyKick),
             ..
-        },
-    ) = world_state.game_controller_state
-    {
-        return None;
+        }) => return Err(Rejection::PenaltyKick),
+        _ => {}
     }
-    match (
+    if !matches!(
         world_state.filtered_game_state,
-        world_state.ball,
-        world_state.robot.robot_to_field,
+        Some(FilteredGameState::Playing { ball_is_free: true }) | None
     ) {
-        (
-            Some(FilteredGameState::Playing { ball_is_free: true }) | None,
-            Some(ball),
-            Some(robot_to_field),
-        ) => {
-            if !ball_is_interception_candidate(ball, robot_to_field, &parameters) {
-                return None;
-            }
+        return Err(Rejection::BallIsNotFree);
+    }
+    let ball = world_state.ball.ok_or(Rejection::NoBall)?;
+    let robot_to_field = world_state
+        .robot
+        .robot_to_field
+        .ok_or(Rejection::NoLocalization)?;
+    if !ball_is_interception_candidate(ball, robot_to_field, &parameters) {
+        return Err(Rejection::BallIsNoInterceptionCandidate);
+    }
 
-            let Step {
-                forward,
-                left,
-                turn: _,
-            } = maximum_step_size;
+    let Step {
+        forward,
+        left,
+        turn: _,
+    } = maximum_step_size;
 
-            if forward == 0.0 || left == 0.0 {
-                return None;
-            }
+    if forward == 0.0 || left == 0.0 {
+        return Err(Rejection::MaximumStepSizeIsZero);
+    }
 
-            let ball_line = Line(
-                ball.ball_in_ground,
-                ball.ball_in_ground + ball.ball_in_ground_velocity,
-            );
-            let interception_point = ball_line.project_point(Point2::origin());
+    let ball_line = Line(
+        ball.ball_in_ground,
+        ball.ball_in_ground + ball.ball_in_ground_velocity,
+    );
+    let interception_point = ball_line.project_point(Point2::origin());
 
-            if interception_point.coords.norm() > parameters.maximum_intercept_distance {
-                return None;
-            }
+    if interception_point.coords.norm() > parameters.maximum_intercept_distance {
+        return Err(Rejection::InterceptionPointTooFar);
+    }
 
-            let path = vec![PathSegment::LineSegment(LineSegment(
-                Point2::origin(),
-                interception_point,
-            ))];
+    let path = vec![PathSegment::LineSegment(LineSegment(
+        Point2::origin(),
+        interception_point,
+    ))];
 
-            Some(MotionCommand::Walk {
-                head: HeadMotion::LookAt {
-                    target: ball.ball_in_ground,
-                    camera: None,
-                },
-                path,
-                left_arm: types::motion_command::ArmMotion::Swing,
-                right_arm: types::motion_command::ArmMotion::Swing,
-                orientation_mode: OrientationMode::Override(UnitComplex::default()),
-            })
-        }
-        _ => None,
-    }
+    Ok(MotionCommand::Walk {
+        head: HeadMotion::LookAt {
+            target: ball.ball_in_ground,
+            camera: None,
+        },
+        path,
+        left_arm: types::motion_command::ArmMotion::Swing,
+        right_arm: types::motion_command::ArmMotion::Swing,
+        orientation_mode: OrientationMode::Override(UnitComplex::default()),
+    })
 }
 
 fn ball_is_interception_candidate(
@@ -1,10 +1,10 @@
 use spl_network_messages::GamePhase;
 use types::{
-    game_controller_state::GameControllerState, motion_command::MotionCommand,
-    primary_state::PrimaryState, world_state::WorldState,
+    decision_trace::Rejection, game_controller_state::GameControllerState,
+    motion_command::MotionCommand, primary_state::PrimaryState, world_state::WorldState,
 };
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+pub fn execute(world_state: &WorldState) -> Result<MotionCommand, Rejection> {
     match (
         world_state.game_controller_state,
         world_state.robot.primary_state,
@@ -15,11 +15,11 @@ pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
                 ..
             }),
             _,
-        ) => None,
-        (_, PrimaryState::Ready | PrimaryState::Playing) => Some(MotionCommand::Stand {
+        ) => Err(Rejection::PenaltyShootout),
+        (_, PrimaryState::Ready | PrimaryState::Playing) => Ok(MotionCommand::Stand {
             head: types::motion_command::HeadMotion::LookAround,
             is_energy_saving: false,
         }),
-        _ => None,
+        (_, primary_state) => Err(Rejection::UnsuitablePrimaryState { primary_state }),
     }
 }
@@ -2,6 +2,7 @@ use framework::AdditionalOutput;
 use geometry::look_at::LookAt;
 use nalgebra::Point2;
 use types::{
+    decision_trace::Rejection,
     motion_command::HeadMotion,
     motion_command::{MotionCommand, OrientationMode},
     parameters::LostBallParameters,
@@ -17,8 +18,11 @@ pub fn execute(
     walk_path_planner: &WalkPathPlanner,
     lost_ball_parameters: &LostBallParameters,
     path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
-) -> Option<MotionCommand> {
-    let robot_to_field = world_state.robot.robot_to_field?;
+) -> Result<MotionCommand, Rejection> {
+    let robot_to_field = world_state
+        .robot
+        .robot_to_field
+        .ok_or(Rejection::NoLocalization)?;
     let walk_target = robot_to_field.inverse()
         * (absolute_last_known_ball_position - lost_ball_parameters.offset_to_last_ball_location);
     let relative_last_known_ball_position =
@@ -34,7 +38,7 @@ pub fn execute(
         &world_state.rule_obstacles,
         path_obstacles_output,
     );
-    Some(walk_path_planner.walk_with_obstacle_avoiding_arms(
+    Ok(walk_path_planner.walk_with_obstacle_avoiding_arms(
         HeadMotion::SearchForLostBall,
         OrientationMode::Override(orientation),
         path,
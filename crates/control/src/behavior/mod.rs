@@ -1,4 +1,5 @@
 mod calibrate;
+mod catch;
 mod defend;
 mod dribble;
 mod fall_safely;
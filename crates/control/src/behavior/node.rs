@@ -1,20 +1,24 @@
 use std::time::SystemTime;
 
-use color_eyre::Result;
+use color_eyre::{eyre::bail, Result};
 use context_attribute::context;
+use enum_iterator::all;
 use framework::{AdditionalOutput, MainOutput};
+use log::warn;
 use nalgebra::{point, Point2, Vector2};
 use serde::{Deserialize, Serialize};
 use spl_network_messages::{GamePhase, GameState, SubState, Team};
 use types::{
     action::Action,
     cycle_time::CycleTime,
+    decision_trace::{ActionDecision, ActionOutcome, DecisionTrace, Rejection},
     field_dimensions::FieldDimensions,
     filtered_game_state::FilteredGameState,
     game_controller_state::GameControllerState,
-    motion_command::MotionCommand,
+    motion_command::{HeadMotion, MotionCommand},
     parameters::{
-        BehaviorParameters, InWalkKicksParameters, InterceptBallParameters, LostBallParameters,
+        ActionListsParameters, BehaviorParameters, GameSituation, InWalkKicksParameters,
+        InterceptBallParameters, LostBallParameters, SituationalActions, StandingKicksParameters,
     },
     path_obstacles::PathObstacle,
     planned_path::PathSegment,
@@ -28,7 +32,7 @@ use types::{
 use crate::dribble_path_planner;
 
 use super::{
-    calibrate,
+    calibrate, catch,
     defend::Defend,
     dribble, fall_safely,
     head::LookAction,
@@ -42,16 +46,22 @@ pub struct Behavior {
     last_motion_command: MotionCommand,
     absolute_last_known_ball_position: Point2<f32>,
     active_since: Option<SystemTime>,
+    action_lists: ActionListsParameters,
+    rejected_action_lists: Option<ActionListsParameters>,
 }
 
 #[context]
-pub struct CreationContext {}
+pub struct CreationContext {
+    action_lists: Parameter<ActionListsParameters, "behavior.action_lists">,
+}
 
 #[context]
 pub struct CycleContext {
     path_obstacles: AdditionalOutput<Vec<PathObstacle>, "path_obstacles">,
     dribble_path_obstacles: AdditionalOutput<Vec<PathObstacle>, "dribble_path_obstacles">,
     active_action: AdditionalOutput<Action, "active_action">,
+    decision_trace: AdditionalOutput<DecisionTrace, "decision_trace">,
+    walk_target: CyclerState<Option<Point2<f32>>, "walk_target">,
 
     has_ground_contact: Input<bool, "has_ground_contact">,
     world_state: Input<WorldState, "world_state">,
@@ -59,6 +69,7 @@ pub struct CycleContext {
 
     parameters: Parameter<BehaviorParameters, "behavior">,
     in_walk_kicks: Parameter<InWalkKicksParameters, "in_walk_kicks">,
+    standing_kicks: Parameter<StandingKicksParameters, "standing_kicks">,
     field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
     lost_ball_parameters: Parameter<LostBallParameters, "behavior.lost_ball">,
     intercept_ball_parameters: Parameter<InterceptBallParameters, "behavior.intercept_ball">,
@@ -74,11 +85,14 @@ pub struct MainOutputs {
 }
 
 impl Behavior {
-    pub fn new(_context: CreationContext) -> Result<Self> {
+    pub fn new(context: CreationContext) -> Result<Self> {
+        validate_action_lists(context.action_lists)?;
         Ok(Self {
             last_motion_command: MotionCommand::Unstiff,
             absolute_last_known_ball_position: point![0.0, 0.0],
             active_since: None,
+            action_lists: context.action_lists.clone(),
+            rejected_action_lists: None,
         })
     }
 
@@ -109,67 +123,29 @@ impl Behavior {
             (Some(_), _) => self.active_since = None,
         }
 
-        let mut actions = vec![
-            Action::Unstiff,
-            Action::SitDown,
-            Action::Penalize,
-            Action::Initial,
-            Action::FallSafely,
-            Action::StandUp,
-            Action::Stand,
-            Action::InterceptBall,
-            Action::Calibrate,
-        ];
-
-        if let Some(active_since) = self.active_since {
-            if now.duration_since(active_since)? < context.parameters.initial_lookaround_duration {
-                actions.push(Action::LookAround);
+        let is_initial_lookaround_active = match self.active_since {
+            Some(active_since) => {
+                now.duration_since(active_since)? < context.parameters.initial_lookaround_duration
             }
-        }
-
-        match world_state.robot.role {
-            Role::DefenderLeft => actions.push(Action::DefendLeft),
-            Role::DefenderRight => actions.push(Action::DefendRight),
-            Role::Keeper => match world_state.game_controller_state {
-                Some(GameControllerState {
-                    game_phase: GamePhase::PenaltyShootout { .. },
-                    ..
-                }) => {
-                    actions.push(Action::Jump);
-                    actions.push(Action::PrepareJump);
-                }
-                _ => actions.push(Action::DefendGoal),
-            },
-            Role::Loser => actions.push(Action::SearchForLostBall),
-            Role::MidfielderLeft => actions.push(Action::SupportLeft),
-            Role::MidfielderRight => actions.push(Action::SupportRight),
-            Role::ReplacementKeeper => actions.push(Action::DefendGoal),
-            Role::Searcher => actions.push(Action::Search),
-            Role::Striker => match world_state.filtered_game_state {
-                None | Some(FilteredGameState::Playing { ball_is_free: true }) => {
-                    actions.push(Action::Dribble);
-                }
-                Some(FilteredGameState::Ready {
-                    kicking_team: Team::Hulks,
-                }) => match world_state.game_controller_state {
-                    Some(GameControllerState {
-                        sub_state: Some(SubState::PenaltyKick),
-                        ..
-                    }) => actions.push(Action::WalkToPenaltyKick),
-                    _ => actions.push(Action::WalkToKickOff),
-                },
-                _ => match world_state.game_controller_state {
-                    Some(GameControllerState {
-                        game_state: GameState::Ready,
-                        sub_state: Some(SubState::PenaltyKick),
-                        kicking_team: Team::Opponent,
-                        ..
-                    }) => actions.push(Action::DefendPenaltyKick),
-                    _ => actions.push(Action::DefendKickOff),
-                },
-            },
-            Role::StrikerSupporter => actions.push(Action::SupportStriker),
+            None => false,
         };
+        self.update_action_lists(&context.parameters.action_lists);
+        let action_lists = &self.action_lists;
+        let role_actions = action_lists
+            .role(world_state.robot.role)
+            .iter()
+            .find(|situational_actions| {
+                situation_applies(situational_actions.situation, world_state)
+            })
+            .map(|situational_actions| situational_actions.actions.as_slice())
+            .unwrap_or_default();
+        let actions: Vec<_> = action_lists
+            .common
+            .iter()
+            .filter(|action| is_initial_lookaround_active || **action != Action::LookAround)
+            .chain(role_actions)
+            .copied()
+            .collect();
 
         let walk_path_planner = WalkPathPlanner::new(
             context.field_dimensions,
@@ -209,133 +185,137 @@ impl Behavior {
             .dribble_path_obstacles
             .fill_if_subscribed(|| dribble_path_obstacles.clone().unwrap_or_default());
 
-        let (action, motion_command) = actions
-            .iter()
-            .find_map(|action| {
-                let motion_command = match action {
-                    Action::Unstiff => unstiff::execute(world_state),
-                    Action::SitDown => sit_down::execute(world_state),
-                    Action::Penalize => penalize::execute(world_state),
-                    Action::Initial => initial::execute(world_state),
-                    Action::FallSafely => {
-                        fall_safely::execute(world_state, *context.has_ground_contact)
-                    }
-                    Action::StandUp => stand_up::execute(world_state),
-                    Action::LookAround => look_around::execute(world_state),
-                    Action::InterceptBall => intercept_ball::execute(
-                        world_state,
-                        *context.intercept_ball_parameters,
-                        *context.maximum_step_size,
-                    ),
-                    Action::Calibrate => calibrate::execute(world_state),
-                    Action::DefendGoal => defend.goal(&mut context.path_obstacles),
-                    Action::DefendKickOff => defend.kick_off(&mut context.path_obstacles),
-                    Action::DefendLeft => defend.left(&mut context.path_obstacles),
-                    Action::DefendRight => defend.right(&mut context.path_obstacles),
-                    Action::DefendPenaltyKick => defend.penalty_kick(&mut context.path_obstacles),
-                    Action::Stand => stand::execute(world_state, context.field_dimensions),
-                    Action::Dribble => dribble::execute(
-                        world_state,
-                        &walk_path_planner,
-                        context.in_walk_kicks,
-                        &context.parameters.dribbling,
-                        dribble_path.clone(),
-                    ),
-                    Action::Jump => jump::execute(world_state),
-                    Action::PrepareJump => prepare_jump::execute(world_state),
-                    Action::Search => search::execute(
-                        world_state,
-                        &walk_path_planner,
-                        &walk_and_stand,
-                        context.field_dimensions,
-                        &context.parameters.search,
-                        &mut context.path_obstacles,
-                    ),
-                    Action::SearchForLostBall => lost_ball::execute(
-                        world_state,
-                        self.absolute_last_known_ball_position,
-                        &walk_path_planner,
-                        context.lost_ball_parameters,
-                        &mut context.path_obstacles,
-                    ),
-                    Action::SupportLeft => support::execute(
-                        world_state,
-                        context.field_dimensions,
-                        Some(Side::Left),
-                        context
-                            .parameters
-                            .role_positions
-                            .left_midfielder_distance_to_ball,
-                        context
-                            .parameters
-                            .role_positions
-                            .left_midfielder_maximum_x_in_ready_and_when_ball_is_not_free,
-                        context.parameters.role_positions.left_midfielder_minimum_x,
-                        &walk_and_stand,
-                        &look_action,
-                        &mut context.path_obstacles,
-                    ),
-                    Action::SupportRight => support::execute(
-                        world_state,
-                        context.field_dimensions,
-                        Some(Side::Right),
-                        context
-                            .parameters
-                            .role_positions
-                            .right_midfielder_distance_to_ball,
-                        context
-                            .parameters
-                            .role_positions
-                            .right_midfielder_maximum_x_in_ready_and_when_ball_is_not_free,
-                        context.parameters.role_positions.right_midfielder_minimum_x,
-                        &walk_and_stand,
-                        &look_action,
-                        &mut context.path_obstacles,
-                    ),
-                    Action::SupportStriker => support::execute(
-                        world_state,
-                        context.field_dimensions,
-                        None,
-                        context
-                            .parameters
-                            .role_positions
-                            .striker_supporter_distance_to_ball,
-                        context
-                            .parameters
-                            .role_positions
-                            .striker_supporter_maximum_x_in_ready_and_when_ball_is_not_free,
-                        context
-                            .parameters
-                            .role_positions
-                            .striker_supporter_minimum_x,
-                        &walk_and_stand,
-                        &look_action,
-                        &mut context.path_obstacles,
-                    ),
-                    Action::WalkToKickOff => walk_to_kick_off::execute(
-                        world_state,
-                        &walk_and_stand,
-                        &look_action,
-                        &mut context.path_obstacles,
-                        *context.striker_set_position,
-                    ),
-                    Action::WalkToPenaltyKick => walk_to_penalty_kick::execute(
-                        world_state,
-                        &walk_and_stand,
-                        &look_action,
-                        &mut context.path_obstacles,
-                        context.field_dimensions,
-                    ),
-                }?;
-                Some((action, motion_command))
-            })
-            .unwrap_or_else(|| {
-                panic!(
-                    "there has to be at least one action available, world_state: {world_state:#?}",
-                )
+        let (action, motion_command, decision_trace) =
+            select_action(&actions, |action| match action {
+                Action::Unstiff => unstiff::execute(world_state),
+                Action::SitDown => sit_down::execute(world_state),
+                Action::Penalize => penalize::execute(world_state),
+                Action::Initial => initial::execute(world_state),
+                Action::FallSafely => {
+                    fall_safely::execute(world_state, *context.has_ground_contact)
+                }
+                Action::StandUp => stand_up::execute(world_state),
+                Action::LookAround => look_around::execute(world_state),
+                Action::InterceptBall => intercept_ball::execute(
+                    world_state,
+                    *context.intercept_ball_parameters,
+                    *context.maximum_step_size,
+                ),
+                Action::Calibrate => calibrate::execute(world_state),
+                Action::Catch => catch::execute(
+                    world_state,
+                    context.field_dimensions,
+                    &context.parameters.catch,
+                ),
+                Action::DefendGoal => defend.goal(&mut context.path_obstacles),
+                Action::DefendKickOff => defend.kick_off(&mut context.path_obstacles),
+                Action::DefendLeft => defend.left(&mut context.path_obstacles),
+                Action::DefendRight => defend.right(&mut context.path_obstacles),
+                Action::DefendPenaltyKick => defend.penalty_kick(&mut context.path_obstacles),
+                Action::Stand => stand::execute(world_state, context.field_dimensions),
+                Action::Dribble => dribble::execute(
+                    world_state,
+                    &walk_path_planner,
+                    context.in_walk_kicks,
+                    context.standing_kicks,
+                    &context.parameters.dribbling,
+                    dribble_path.clone(),
+                ),
+                Action::Jump => jump::execute(world_state),
+                Action::PrepareJump => prepare_jump::execute(world_state),
+                Action::Search => search::execute(
+                    world_state,
+                    &walk_path_planner,
+                    &walk_and_stand,
+                    context.field_dimensions,
+                    &context.parameters.search,
+                    &mut context.path_obstacles,
+                ),
+                Action::SearchForLostBall => lost_ball::execute(
+                    world_state,
+                    self.absolute_last_known_ball_position,
+                    &walk_path_planner,
+                    context.lost_ball_parameters,
+                    &mut context.path_obstacles,
+                ),
+                Action::SupportLeft => support::execute(
+                    world_state,
+                    context.field_dimensions,
+                    Some(Side::Left),
+                    context
+                        .parameters
+                        .role_positions
+                        .left_midfielder_distance_to_ball,
+                    context
+                        .parameters
+                        .role_positions
+                        .left_midfielder_maximum_x_in_ready_and_when_ball_is_not_free,
+                    context.parameters.role_positions.left_midfielder_minimum_x,
+                    &walk_and_stand,
+                    &look_action,
+                    &mut context.path_obstacles,
+                ),
+                Action::SupportRight => support::execute(
+                    world_state,
+                    context.field_dimensions,
+                    Some(Side::Right),
+                    context
+                        .parameters
+                        .role_positions
+                        .right_midfielder_distance_to_ball,
+                    context
+                        .parameters
+                        .role_positions
+                        .right_midfielder_maximum_x_in_ready_and_when_ball_is_not_free,
+                    context.parameters.role_positions.right_midfielder_minimum_x,
+                    &walk_and_stand,
+                    &look_action,
+                    &mut context.path_obstacles,
+                ),
+                Action::SupportStriker => support::execute(
+                    world_state,
+                    context.field_dimensions,
+                    None,
+                    context
+                        .parameters
+                        .role_positions
+                        .striker_supporter_distance_to_ball,
+                    context
+                        .parameters
+                        .role_positions
+                        .striker_supporter_maximum_x_in_ready_and_when_ball_is_not_free,
+                    context
+                        .parameters
+                        .role_positions
+                        .striker_supporter_minimum_x,
+                    &walk_and_stand,
+                    &look_action,
+                    &mut context.path_obstacles,
+                ),
+                Action::WalkToKickOff => walk_to_kick_off::execute(
+                    world_state,
+                    &walk_and_stand,
+                    &look_action,
+                    &mut context.path_obstacles,
+                    *context.striker_set_position,
+                ),
+                Action::WalkToPenaltyKick => walk_to_penalty_kick::execute(
+                    world_state,
+                    &walk_and_stand,
+                    &look_action,
+                    &mut context.path_obstacles,
+                    context.field_dimensions,
+                ),
             });
-        context.active_action.fill_if_subscribed(|| *action);
+        context.active_action.fill_if_subscribed(|| action);
+        context.decision_trace.fill_if_subscribed(|| decision_trace);
 
+        *context.walk_target = match (&motion_command, world_state.robot.robot_to_field) {
+            (MotionCommand::Walk { path, .. }, Some(robot_to_field)) => {
+                path.last().map(|segment| robot_to_field * segment.end())
+            }
+            _ => None,
+        };
         self.last_motion_command = motion_command.clone();
 
         if matches!(action, Action::Dribble) {
@@ -349,4 +329,445 @@ impl Behavior {
             dribble_path: dribble_path.into(),
         })
     }
+
+    /// Adopts action lists changed at runtime if they are valid, invalid lists are reported once
+    /// and the previous lists stay in use
+    fn update_action_lists(&mut self, action_lists: &ActionListsParameters) {
+        if *action_lists == self.action_lists
+            || self.rejected_action_lists.as_ref() == Some(action_lists)
+        {
+            return;
+        }
+        match validate_action_lists(action_lists) {
+            Ok(()) => {
+                self.action_lists = action_lists.clone();
+                self.rejected_action_lists = None;
+            }
+            Err(error) => {
+                warn!("keeping previous action lists, changed lists are invalid: {error}");
+                self.rejected_action_lists = Some(action_lists.clone());
+            }
+        }
+    }
+}
+
+/// Executes the actions in priority order until one of them produces a motion command
+fn select_action(
+    actions: &[Action],
+    mut execute: impl FnMut(Action) -> Result<MotionCommand, Rejection>,
+) -> (Action, MotionCommand, DecisionTrace) {
+    let mut decision_trace = DecisionTrace::new();
+    let (action, motion_command) = actions
+        .iter()
+        .find_map(|&action| match execute(action) {
+            Ok(motion_command) => {
+                decision_trace.push(ActionDecision {
+                    action,
+                    outcome: ActionOutcome::Selected,
+                });
+                Some((action, motion_command))
+            }
+            Err(rejection) => {
+                decision_trace.push(ActionDecision {
+                    action,
+                    outcome: ActionOutcome::Rejected { rejection },
+                });
+                None
+            }
+        })
+        // every action rejected, the decision trace shows why
+        .unwrap_or((
+            Action::Stand,
+            MotionCommand::Stand {
+                head: HeadMotion::Center,
+                is_energy_saving: false,
+            },
+        ));
+    (action, motion_command, decision_trace)
+}
+
+fn situation_applies(situation: GameSituation, world_state: &WorldState) -> bool {
+    let game_controller_state = world_state.game_controller_state.as_ref();
+    let is_penalty_kick = matches!(
+        game_controller_state,
+        Some(GameControllerState {
+            sub_state: Some(SubState::PenaltyKick),
+            ..
+        })
+    );
+    let is_own_ready = matches!(
+        world_state.filtered_game_state,
+        Some(FilteredGameState::Ready {
+            kicking_team: Team::Hulks,
+        })
+    );
+    match situation {
+        GameSituation::Always => true,
+        GameSituation::BallIsFree => matches!(
+            world_state.filtered_game_state,
+            None | Some(FilteredGameState::Playing { ball_is_free: true })
+        ),
+        GameSituation::OwnKickOff => is_own_ready && !is_penalty_kick,
+        GameSituation::OwnPenaltyKick => is_own_ready && is_penalty_kick,
+        GameSituation::OpponentPenaltyKick => matches!(
+            game_controller_state,
+            Some(GameControllerState {
+                game_state: GameState::Ready,
+                sub_state: Some(SubState::PenaltyKick),
+                kicking_team: Team::Opponent,
+                ..
+            })
+        ),
+        GameSituation::PenaltyShootout => matches!(
+            game_controller_state,
+            Some(GameControllerState {
+                game_phase: GamePhase::PenaltyShootout { .. },
+                ..
+            })
+        ),
+    }
+}
+
+fn validate_action_lists(action_lists: &ActionListsParameters) -> Result<()> {
+    if let Some(action) = first_duplicate(&action_lists.common) {
+        bail!("action {action:?} appears twice in common actions");
+    }
+    for role in all::<Role>() {
+        let situational_actions = action_lists.role(role);
+        for SituationalActions { situation, actions } in situational_actions {
+            if let Some(action) = first_duplicate(actions) {
+                bail!("action {action:?} appears twice in actions of {role:?} in {situation:?}");
+            }
+        }
+        if !matches!(
+            situational_actions.last(),
+            Some(SituationalActions {
+                situation: GameSituation::Always,
+                ..
+            })
+        ) {
+            bail!(
+                "actions of {role:?} have to end with situation {:?} to cover every situation",
+                GameSituation::Always
+            );
+        }
+    }
+    Ok(())
+}
+
+fn first_duplicate(actions: &[Action]) -> Option<Action> {
+    actions
+        .iter()
+        .enumerate()
+        .find(|(index, action)| actions[..*index].contains(action))
+        .map(|(_index, action)| *action)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use serde_json::Value;
+    use spl_network_messages::TeamColor;
+    use types::game_controller_state::TeamColors;
+
+    use super::*;
+
+    const DEFAULT_PARAMETERS_PATH: &str = "../../etc/parameters/default.json";
+
+    /// Action lists shipped in the default parameters
+    fn default_action_lists() -> ActionListsParameters {
+        let default_parameters: Value =
+            serde_json::from_reader(File::open(DEFAULT_PARAMETERS_PATH).unwrap()).unwrap();
+        serde_json::from_value(default_parameters["behavior"]["action_lists"].clone()).unwrap()
+    }
+
+    fn always(actions: Vec<Action>) -> Vec<SituationalActions> {
+        vec![SituationalActions {
+            situation: GameSituation::Always,
+            actions,
+        }]
+    }
+
+    fn valid_action_lists() -> ActionListsParameters {
+        ActionListsParameters {
+            common: vec![Action::Unstiff, Action::Penalize],
+            defender_left: always(vec![Action::DefendLeft]),
+            defender_right: always(vec![Action::DefendRight]),
+            keeper: always(vec![Action::DefendGoal]),
+            loser: always(vec![Action::SearchForLostBall]),
+            midfielder_left: always(vec![Action::SupportLeft]),
+            midfielder_right: always(vec![Action::SupportRight]),
+            replacement_keeper: always(vec![Action::DefendGoal]),
+            searcher: always(vec![Action::Search]),
+            striker: always(vec![Action::Dribble]),
+            striker_supporter: always(vec![Action::SupportStriker]),
+        }
+    }
+
+    fn game_controller_state(
+        game_state: GameState,
+        game_phase: GamePhase,
+        kicking_team: Team,
+        sub_state: Option<SubState>,
+    ) -> GameControllerState {
+        let team_colors = TeamColors {
+            field_player: TeamColor::Black,
+            goal_keeper: TeamColor::Gray,
+        };
+        GameControllerState {
+            game_state,
+            game_phase,
+            kicking_team,
+            last_game_state_change: SystemTime::UNIX_EPOCH,
+            penalties: Default::default(),
+            remaining_amount_of_messages: 0,
+            sub_state,
+            hulks_team_is_home_after_coin_toss: true,
+            hulks_team_colors: team_colors,
+            opponent_team_colors: team_colors,
+        }
+    }
+
+    #[test]
+    fn default_action_lists_are_valid() {
+        validate_action_lists(&default_action_lists()).unwrap();
+    }
+
+    #[test]
+    fn keepers_try_to_catch_before_intercepting() {
+        let action_lists = default_action_lists();
+        assert!(!action_lists.common.contains(&Action::InterceptBall));
+        for role in [Role::Keeper, Role::ReplacementKeeper] {
+            let actions = &action_lists.role(role).last().unwrap().actions;
+            let position = |action: Action| actions.iter().position(|&other| other == action);
+
+            assert!(position(Action::Catch).unwrap() < position(Action::InterceptBall).unwrap());
+        }
+    }
+
+    #[test]
+    fn duplicate_common_action_is_rejected() {
+        let mut action_lists = valid_action_lists();
+        action_lists.common.push(Action::Unstiff);
+
+        assert!(validate_action_lists(&action_lists).is_err());
+    }
+
+    #[test]
+    fn duplicate_role_action_is_rejected() {
+        let mut action_lists = valid_action_lists();
+        action_lists.striker = always(vec![Action::Dribble, Action::Search, Action::Dribble]);
+
+        assert!(validate_action_lists(&action_lists).is_err());
+    }
+
+    #[test]
+    fn same_action_in_different_situations_is_accepted() {
+        let mut action_lists = valid_action_lists();
+        action_lists.striker = vec![
+            SituationalActions {
+                situation: GameSituation::OwnKickOff,
+                actions: vec![Action::WalkToKickOff, Action::Dribble],
+            },
+            SituationalActions {
+                situation: GameSituation::Always,
+                actions: vec![Action::Dribble],
+            },
+        ];
+
+        validate_action_lists(&action_lists).unwrap();
+    }
+
+    #[test]
+    fn actions_without_trailing_always_are_rejected() {
+        let mut action_lists = valid_action_lists();
+        action_lists.keeper = vec![
+            SituationalActions {
+                situation: GameSituation::Always,
+                actions: vec![Action::DefendGoal],
+            },
+            SituationalActions {
+                situation: GameSituation::PenaltyShootout,
+                actions: vec![Action::PrepareJump],
+            },
+        ];
+        assert!(validate_action_lists(&action_lists).is_err());
+
+        action_lists.keeper = Vec::new();
+        assert!(validate_action_lists(&action_lists).is_err());
+    }
+
+    #[test]
+    fn always_and_ball_is_free_apply_without_game_controller() {
+        let world_state = WorldState::default();
+
+        assert!(situation_applies(GameSituation::Always, &world_state));
+        assert!(situation_applies(GameSituation::BallIsFree, &world_state));
+        assert!(!situation_applies(GameSituation::OwnKickOff, &world_state));
+        assert!(!situation_applies(
+            GameSituation::OpponentPenaltyKick,
+            &world_state
+        ));
+        assert!(!situation_applies(
+            GameSituation::PenaltyShootout,
+            &world_state
+        ));
+    }
+
+    #[test]
+    fn ball_is_free_follows_filtered_game_state() {
+        let mut world_state = WorldState {
+            filtered_game_state: Some(FilteredGameState::Playing {
+                ball_is_free: false,
+            }),
+            ..Default::default()
+        };
+        assert!(!situation_applies(GameSituation::BallIsFree, &world_state));
+
+        world_state.filtered_game_state = Some(FilteredGameState::Playing { ball_is_free: true });
+        assert!(situation_applies(GameSituation::BallIsFree, &world_state));
+    }
+
+    #[test]
+    fn own_ready_distinguishes_kick_off_and_penalty_kick() {
+        let mut world_state = WorldState {
+            filtered_game_state: Some(FilteredGameState::Ready {
+                kicking_team: Team::Hulks,
+            }),
+            game_controller_state: Some(game_controller_state(
+                GameState::Ready,
+                GamePhase::Normal,
+                Team::Hulks,
+                None,
+            )),
+            ..Default::default()
+        };
+        assert!(situation_applies(GameSituation::OwnKickOff, &world_state));
+        assert!(!situation_applies(
+            GameSituation::OwnPenaltyKick,
+            &world_state
+        ));
+
+        world_state.game_controller_state = Some(game_controller_state(
+            GameState::Ready,
+            GamePhase::Normal,
+            Team::Hulks,
+            Some(SubState::PenaltyKick),
+        ));
+        assert!(!situation_applies(GameSituation::OwnKickOff, &world_state));
+        assert!(situation_applies(
+            GameSituation::OwnPenaltyKick,
+            &world_state
+        ));
+
+        world_state.filtered_game_state = Some(FilteredGameState::Ready {
+            kicking_team: Team::Opponent,
+        });
+        assert!(!situation_applies(
+            GameSituation::OwnPenaltyKick,
+            &world_state
+        ));
+    }
+
+    #[test]
+    fn opponent_penalty_kick_and_penalty_shootout_follow_game_controller() {
+        let mut world_state = WorldState {
+            game_controller_state: Some(game_controller_state(
+                GameState::Ready,
+                GamePhase::Normal,
+                Team::Opponent,
+                Some(SubState::PenaltyKick),
+            )),
+            ..Default::default()
+        };
+        assert!(situation_applies(
+            GameSituation::OpponentPenaltyKick,
+            &world_state
+        ));
+        assert!(!situation_applies(
+            GameSituation::PenaltyShootout,
+            &world_state
+        ));
+
+        world_state.game_controller_state = Some(game_controller_state(
+            GameState::Set,
+            GamePhase::PenaltyShootout {
+                kicking_team: Team::Hulks,
+            },
+            Team::Hulks,
+            None,
+        ));
+        assert!(!situation_applies(
+            GameSituation::OpponentPenaltyKick,
+            &world_state
+        ));
+        assert!(situation_applies(
+            GameSituation::PenaltyShootout,
+            &world_state
+        ));
+    }
+
+    #[test]
+    fn first_accepted_action_is_selected() {
+        let (action, motion_command, decision_trace) = select_action(
+            &[Action::Unstiff, Action::SitDown, Action::Jump],
+            |action| match action {
+                Action::Unstiff => Err(Rejection::NotFallen),
+                _ => Ok(MotionCommand::Unstiff),
+            },
+        );
+
+        assert_eq!(action, Action::SitDown);
+        assert!(matches!(motion_command, MotionCommand::Unstiff));
+        assert_eq!(
+            decision_trace,
+            vec![
+                ActionDecision {
+                    action: Action::Unstiff,
+                    outcome: ActionOutcome::Rejected {
+                        rejection: Rejection::NotFallen,
+                    },
+                },
+                ActionDecision {
+                    action: Action::SitDown,
+                    outcome: ActionOutcome::Selected,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn stand_is_selected_if_every_action_is_rejected() {
+        let (action, motion_command, decision_trace) =
+            select_action(&[Action::Dribble, Action::Search], |_action| {
+                Err(Rejection::NoBall)
+            });
+
+        assert_eq!(action, Action::Stand);
+        assert!(matches!(
+            motion_command,
+            MotionCommand::Stand {
+                head: HeadMotion::Center,
+                is_energy_saving: false,
+            }
+        ));
+        assert_eq!(
+            decision_trace,
+            vec![
+                ActionDecision {
+                    action: Action::Dribble,
+                    outcome: ActionOutcome::Rejected {
+                        rejection: Rejection::NoBall,
+                    },
+                },
+                ActionDecision {
+                    action: Action::Search,
+                    outcome: ActionOutcome::Rejected {
+                        rejection: Rejection::NoBall,
+                    },
+                },
+            ]
+        );
+    }
 }
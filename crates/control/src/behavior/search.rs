@@ -1,6 +1,7 @@
 use framework::AdditionalOutput;
 use nalgebra::{point, Isometry2, Point2, UnitComplex};
 use types::{
+    decision_trace::Rejection,
     field_dimensions::FieldDimensions,
     motion_command::{HeadMotion, MotionCommand, OrientationMode},
     parameters::SearchParameters,
@@ -58,8 +59,11 @@ pub fn execute(
     field_dimensions: &FieldDimensions,
     parameters: &SearchParameters,
     path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
-) -> Option<MotionCommand> {
-    let robot_to_field = world_state.robot.robot_to_field?;
+) -> Result<MotionCommand, Rejection> {
+    let robot_to_field = world_state
+        .robot
+        .robot_to_field
+        .ok_or(Rejection::NoLocalization)?;
     let search_role = assign_search_role(world_state);
     let search_position = search_role
         .map(|role| role.to_position(robot_to_field, field_dimensions))
@@ -85,7 +89,7 @@ pub fn execute(
         } else {
             OrientationMode::AlignWithPath
         };
-        Some(walk_path_planner.walk_with_obstacle_avoiding_arms(head, orientation_mode, path))
+        Ok(walk_path_planner.walk_with_obstacle_avoiding_arms(head, orientation_mode, path))
     }
 }
 
@@ -1,14 +1,15 @@
 use types::{
+    decision_trace::Rejection,
     motion_command::{HeadMotion, MotionCommand},
     primary_state::PrimaryState,
     world_state::WorldState,
 };
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+pub fn execute(world_state: &WorldState) -> Result<MotionCommand, Rejection> {
     match world_state.robot.primary_state {
-        PrimaryState::Finished => Some(MotionCommand::SitDown {
+        PrimaryState::Finished => Ok(MotionCommand::SitDown {
             head: HeadMotion::Unstiff,
         }),
-        _ => None,
+        primary_state => Err(Rejection::UnsuitablePrimaryState { primary_state }),
     }
 }
@@ -1,6 +1,7 @@
 use nalgebra::{point, Point2};
 use spl_network_messages::{GamePhase, SubState, Team};
 use types::{
+    decision_trace::Rejection,
     field_dimensions::FieldDimensions,
     game_controller_state::GameControllerState,
     motion_command::{HeadMotion, MotionCommand},
@@ -12,14 +13,17 @@ use types::{
 pub fn execute(
     world_state: &WorldState,
     field_dimensions: &FieldDimensions,
-) -> Option<MotionCommand> {
+) -> Result<MotionCommand, Rejection> {
     match world_state.robot.primary_state {
-        PrimaryState::Initial => Some(MotionCommand::Stand {
+        PrimaryState::Initial => Ok(MotionCommand::Stand {
             head: HeadMotion::ZeroAngles,
             is_energy_saving: true,
         }),
         PrimaryState::Set => {
-            let robot_to_field = world_state.robot.robot_to_field?;
+            let robot_to_field = world_state
+                .robot
+                .robot_to_field
+                .ok_or(Rejection::NoLocalization)?;
             let fallback_target = match world_state.game_controller_state {
                 Some(GameControllerState {
                     sub_state: Some(SubState::PenaltyKick),
@@ -41,7 +45,7 @@ pub fn execute(
                 .ball
                 .map(|state| state.ball_in_ground)
                 .unwrap_or(fallback_target);
-            Some(MotionCommand::Stand {
+            Ok(MotionCommand::Stand {
                 head: HeadMotion::LookAt {
                     target,
                     camera: None,
@@ -62,13 +66,13 @@ pub fn execute(
                     }),
                     Role::Striker,
                     None,
-                ) => Some(MotionCommand::Stand {
+                ) => Ok(MotionCommand::Stand {
                     head: HeadMotion::Center,
                     is_energy_saving: true,
                 }),
-                _ => None,
+                _ => Err(Rejection::NotSearchingBallInPenaltyShootout),
             }
         }
-        _ => None,
+        primary_state => Err(Rejection::UnsuitablePrimaryState { primary_state }),
     }
 }
@@ -1,8 +1,11 @@
-use types::{fall_state::FallState, motion_command::MotionCommand, world_state::WorldState};
+use types::{
+    decision_trace::Rejection, fall_state::FallState, motion_command::MotionCommand,
+    world_state::WorldState,
+};
 
-pub fn execute(world_state: &WorldState) -> Option<MotionCommand> {
+pub fn execute(world_state: &WorldState) -> Result<MotionCommand, Rejection> {
     match world_state.robot.fall_state {
-        FallState::Fallen { facing } => Some(MotionCommand::StandUp { facing }),
-        _ => None,
+        FallState::Fallen { facing } => Ok(MotionCommand::StandUp { facing }),
+        _ => Err(Rejection::NotFallen),
     }
 }
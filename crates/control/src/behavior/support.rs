@@ -2,8 +2,9 @@ use std::f32::consts::FRAC_PI_4;
 
 use framework::AdditionalOutput;
 use geometry::look_at::LookAt;
-use nalgebra::{point, Isometry2, UnitComplex, Vector2};
+use nalgebra::{distance, point, Isometry2, Point2, UnitComplex, Vector2};
 use types::{
+    decision_trace::Rejection,
     field_dimensions::FieldDimensions,
     filtered_game_state::FilteredGameState,
     motion_command::MotionCommand,
@@ -25,7 +26,7 @@ pub fn execute(
     walk_and_stand: &WalkAndStand,
     look_action: &LookAction,
     path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
-) -> Option<MotionCommand> {
+) -> Result<MotionCommand, Rejection> {
     let pose = support_pose(
         world_state,
         field_dimensions,
@@ -44,18 +45,30 @@ fn support_pose(
     distance_to_ball: f32,
     maximum_x_in_ready_and_when_ball_is_not_free: f32,
     minimum_x: f32,
-) -> Option<Isometry2<f32>> {
-    let robot_to_field = world_state.robot.robot_to_field?;
+) -> Result<Isometry2<f32>, Rejection> {
+    let robot_to_field = world_state
+        .robot
+        .robot_to_field
+        .ok_or(Rejection::NoLocalization)?;
     let ball = world_state
         .rule_ball
         .or(world_state.ball)
         .unwrap_or_else(|| BallState::new_at_center(robot_to_field));
-    let side = field_side.unwrap_or_else(|| ball.field_side.opposite());
-    let offset_vector = UnitComplex::new(match side {
-        Side::Left => -FRAC_PI_4,
-        Side::Right => FRAC_PI_4,
-    }) * -(Vector2::x() * distance_to_ball);
-    let supporting_position = ball.ball_in_field + offset_vector;
+    let side = field_side.unwrap_or_else(|| {
+        let preferred_side = ball.field_side.opposite();
+        let clearance = |side| {
+            distance_to_closest_walk_target(
+                world_state,
+                ball.ball_in_field + supporting_offset(side, distance_to_ball),
+            )
+        };
+        if clearance(preferred_side.opposite()) > clearance(preferred_side) {
+            preferred_side.opposite()
+        } else {
+            preferred_side
+        }
+    });
+    let supporting_position = ball.ball_in_field + supporting_offset(side, distance_to_ball);
     let clamped_x = match world_state.filtered_game_state {
         Some(FilteredGameState::Ready { .. })
         | Some(FilteredGameState::Playing {
@@ -76,5 +89,95 @@ fn support_pose(
         clamped_position.coords,
         clamped_position.look_at(&ball.ball_in_field).angle(),
     );
-    Some(robot_to_field.inverse() * support_pose)
+    Ok(robot_to_field.inverse() * support_pose)
+}
+
+fn supporting_offset(side: Side, distance_to_ball: f32) -> Vector2<f32> {
+    UnitComplex::new(match side {
+        Side::Left => -FRAC_PI_4,
+        Side::Right => FRAC_PI_4,
+    }) * -(Vector2::x() * distance_to_ball)
+}
+
+/// Distance from `position` to the closest announced walk target of a teammate
+fn distance_to_closest_walk_target(world_state: &WorldState, position: Point2<f32>) -> f32 {
+    world_state
+        .teammates
+        .iter()
+        .filter_map(|teammate| teammate.walk_target)
+        .map(|walk_target| distance(&walk_target, &position))
+        .fold(f32::INFINITY, f32::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use nalgebra::vector;
+    use spl_network_messages::{PlayerNumber, Role};
+    use types::world_state::{RobotState, TeammateState};
+
+    use super::*;
+
+    fn field_dimensions() -> FieldDimensions {
+        FieldDimensions {
+            length: 9.0,
+            width: 6.0,
+            ..Default::default()
+        }
+    }
+
+    /// Ball on the left half of the field, so the supporter prefers the right side
+    fn world_state(teammate_walk_targets: &[Point2<f32>]) -> WorldState {
+        let robot_to_field = Isometry2::identity();
+        WorldState {
+            ball: Some(BallState {
+                ball_in_ground: point![1.0, 1.0],
+                ball_in_field: point![1.0, 1.0],
+                ball_in_ground_velocity: Vector2::zeros(),
+                last_seen_ball: UNIX_EPOCH,
+                penalty_shot_direction: None,
+                field_side: Side::Left,
+            }),
+            robot: RobotState {
+                robot_to_field: Some(robot_to_field),
+                ..Default::default()
+            },
+            teammates: teammate_walk_targets
+                .iter()
+                .map(|walk_target| TeammateState {
+                    player_number: PlayerNumber::Three,
+                    role: Role::MidfielderRight,
+                    robot_to_field,
+                    walk_target: Some(*walk_target),
+                    fallen: false,
+                    time_to_reach_kick_position: Some(Duration::from_secs(10)),
+                    last_update: UNIX_EPOCH,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn supporting_side(world_state: &WorldState) -> Side {
+        let pose = support_pose(world_state, &field_dimensions(), None, 1.0, 0.0, -4.5).unwrap();
+        if pose.translation.y < 1.0 {
+            Side::Right
+        } else {
+            Side::Left
+        }
+    }
+
+    #[test]
+    fn supporter_prefers_side_opposite_of_ball() {
+        assert_eq!(supporting_side(&world_state(&[])), Side::Right);
+    }
+
+    #[test]
+    fn supporter_avoids_side_a_teammate_is_walking_to() {
+        let walk_target =
+            point![1.0, 1.0] + supporting_offset(Side::Right, 1.0) + vector![0.1, 0.0];
+
+        assert_eq!(supporting_side(&world_state(&[walk_target])), Side::Left);
+    }
 }
@@ -1,6 +1,9 @@
 use framework::AdditionalOutput;
 use nalgebra::{Translation2, Vector2};
-use types::{motion_command::MotionCommand, path_obstacles::PathObstacle, world_state::WorldState};
+use types::{
+    decision_trace::Rejection, motion_command::MotionCommand, path_obstacles::PathObstacle,
+    world_state::WorldState,
+};
 
 use super::{head::LookAction, walk_to_pose::WalkAndStand};
 
@@ -10,8 +13,11 @@ pub fn execute(
     look_action: &LookAction,
     path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
     striker_set_position: Vector2<f32>,
-) -> Option<MotionCommand> {
-    let robot_to_field = world_state.robot.robot_to_field?;
+) -> Result<MotionCommand, Rejection> {
+    let robot_to_field = world_state
+        .robot
+        .robot_to_field
+        .ok_or(Rejection::NoLocalization)?;
     walk_and_stand.execute(
         robot_to_field.inverse() * Translation2::from(striker_set_position),
         look_action.execute(),
@@ -1,8 +1,8 @@
 use framework::AdditionalOutput;
 use nalgebra::Isometry2;
 use types::{
-    field_dimensions::FieldDimensions, motion_command::MotionCommand, path_obstacles::PathObstacle,
-    world_state::WorldState,
+    decision_trace::Rejection, field_dimensions::FieldDimensions, motion_command::MotionCommand,
+    path_obstacles::PathObstacle, world_state::WorldState,
 };
 
 use super::{head::LookAction, walk_to_pose::WalkAndStand};
@@ -13,8 +13,11 @@ pub fn execute(
     look_action: &LookAction,
     path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
     field_dimensions: &FieldDimensions,
-) -> Option<MotionCommand> {
-    let robot_to_field = world_state.robot.robot_to_field?;
+) -> Result<MotionCommand, Rejection> {
+    let robot_to_field = world_state
+        .robot
+        .robot_to_field
+        .ok_or(Rejection::NoLocalization)?;
     let kick_off_pose = Isometry2::translation(
         field_dimensions.length / 2.0
             - field_dimensions.penalty_marker_distance
@@ -2,6 +2,7 @@ use filtering::hysteresis::less_than_with_hysteresis;
 use framework::AdditionalOutput;
 use nalgebra::{point, Isometry2, Point2, UnitComplex};
 use types::{
+    decision_trace::Rejection,
     field_dimensions::FieldDimensions,
     motion_command::ArmMotion,
     motion_command::MotionCommand,
@@ -156,8 +157,12 @@ impl<'cycle> WalkAndStand<'cycle> {
         target_pose: Isometry2<f32>,
         head: HeadMotion,
         path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
-    ) -> Option<MotionCommand> {
-        let robot_to_field = self.world_state.robot.robot_to_field?;
+    ) -> Result<MotionCommand, Rejection> {
+        let robot_to_field = self
+            .world_state
+            .robot
+            .robot_to_field
+            .ok_or(Rejection::NoLocalization)?;
         let distance_to_walk = target_pose.translation.vector.norm();
         let angle_to_walk = target_pose.rotation.angle();
         let was_standing_last_cycle =
@@ -180,7 +185,7 @@ impl<'cycle> WalkAndStand<'cycle> {
         );
 
         if is_reached {
-            Some(MotionCommand::Stand {
+            Ok(MotionCommand::Stand {
                 head,
                 is_energy_saving: true,
             })
@@ -194,7 +199,7 @@ impl<'cycle> WalkAndStand<'cycle> {
                 &self.world_state.rule_obstacles,
                 path_obstacles_output,
             );
-            Some(self.walk_path_planner.walk_with_obstacle_avoiding_arms(
+            Ok(self.walk_path_planner.walk_with_obstacle_avoiding_arms(
                 head,
                 orientation_mode,
                 path,
@@ -0,0 +1,372 @@
+use std::{
+    f32::consts::PI,
+    mem::take,
+    time::{Duration, SystemTime},
+};
+
+use calibration::{
+    corrections::Corrections, lines::Lines, measurement::Measurement, solve, Solution,
+};
+use color_eyre::Result;
+use context_attribute::context;
+use framework::{AdditionalOutput, MainOutput, PerceptionInput};
+use nalgebra::{vector, Rotation3, Vector2, Vector3};
+use projection::Projection;
+use serde::{Deserialize, Serialize};
+use types::{
+    camera_calibration::{CalibrationMeasurement, CameraCalibrationResult},
+    camera_position::CameraPosition,
+    cycle_time::CycleTime,
+    field_dimensions::FieldDimensions,
+    joints::head::HeadJoints,
+    line::{Line, Line2},
+    parameters::{CameraCalibrationParameters, CameraMatrixParameters},
+    primary_state::PrimaryState,
+};
+
+use crate::camera_matrix_calculator::rotation_from_degrees;
+
+/// Drives the head through the calibration head positions while in `PrimaryState::Calibration`,
+/// collects line measurements of both cameras and solves for the extrinsic corrections.
+///
+/// The robot is expected to stand in front of a goal box facing the goal, such that the goal line,
+/// the goal box line and a goal box side line are visible.
+#[derive(Deserialize, Serialize)]
+pub struct CameraCalibration {
+    head_position_index: usize,
+    head_position_since: Option<SystemTime>,
+    #[serde(skip)]
+    measurements: Vec<Measurement>,
+    result: Option<CameraCalibrationResult>,
+}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    calibration_result:
+        AdditionalOutput<Option<CameraCalibrationResult>, "camera_calibration.result">,
+    number_of_measurements: AdditionalOutput<usize, "camera_calibration.number_of_measurements">,
+
+    correction_in_camera_top: CyclerState<Rotation3<f32>, "correction_in_camera_top">,
+    correction_in_camera_bottom: CyclerState<Rotation3<f32>, "correction_in_camera_bottom">,
+
+    cycle_time: Input<CycleTime, "cycle_time">,
+    primary_state: Input<PrimaryState, "primary_state">,
+
+    bottom_camera_matrix_parameters:
+        Parameter<CameraMatrixParameters, "camera_matrix_parameters.vision_bottom">,
+    correction_in_robot_rotations:
+        Parameter<Vector3<f32>, "camera_matrix_parameters.correction_in_robot">,
+    field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
+    parameters: Parameter<CameraCalibrationParameters, "camera_calibration">,
+    top_camera_matrix_parameters:
+        Parameter<CameraMatrixParameters, "camera_matrix_parameters.vision_top">,
+
+    measurements_bottom:
+        PerceptionInput<Option<CalibrationMeasurement>, "VisionBottom", "calibration_measurement?">,
+    measurements_top:
+        PerceptionInput<Option<CalibrationMeasurement>, "VisionTop", "calibration_measurement?">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub calibration_head_joints: MainOutput<HeadJoints<f32>>,
+}
+
+impl CameraCalibration {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {
+            head_position_index: 0,
+            head_position_since: None,
+            measurements: Vec::new(),
+            result: None,
+        })
+    }
+
+    pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
+        let now = context.cycle_time.start_time;
+        match (*context.primary_state, self.head_position_since) {
+            (PrimaryState::Calibration, None) => {
+                self.head_position_index = 0;
+                self.head_position_since = Some(now);
+                self.measurements.clear();
+                self.result = None;
+            }
+            (PrimaryState::Calibration, Some(since)) if self.result.is_none() => {
+                self.capture(&context, since, now);
+            }
+            (PrimaryState::Calibration, Some(_)) => {}
+            (_, _) => self.head_position_since = None,
+        }
+
+        context
+            .calibration_result
+            .fill_if_subscribed(|| self.result.clone());
+        context
+            .number_of_measurements
+            .fill_if_subscribed(|| self.measurements.len());
+
+        let calibration_head_joints = context
+            .parameters
+            .head_positions
+            .get(self.head_position_index)
+            .copied()
+            .unwrap_or_default();
+        Ok(MainOutputs {
+            calibration_head_joints: calibration_head_joints.into(),
+        })
+    }
+
+    fn capture(&mut self, context: &CycleContext, since: SystemTime, now: SystemTime) {
+        let parameters = context.parameters;
+        let capture_start = since + parameters.settle_duration;
+        for (position, measurements) in [
+            (CameraPosition::Top, &context.measurements_top),
+            (CameraPosition::Bottom, &context.measurements_bottom),
+        ] {
+            let new_measurements = measurements
+                .persistent
+                .iter()
+                .filter(|(detection_time, _)| **detection_time >= capture_start)
+                .flat_map(|(_, measurements)| measurements.iter().flatten())
+                .filter_map(|measurement| {
+                    Some(Measurement {
+                        position,
+                        matrix: measurement.camera_matrix.clone(),
+                        lines: identify_calibration_lines(
+                            measurement,
+                            parameters.maximum_line_angle_deviation,
+                        )?,
+                    })
+                });
+            self.measurements.extend(new_measurements);
+        }
+
+        let time_at_head_position = now.duration_since(since).unwrap_or(Duration::ZERO);
+        if time_at_head_position < parameters.settle_duration + parameters.capture_duration {
+            return;
+        }
+        self.head_position_since = Some(now);
+        self.head_position_index += 1;
+        if self.head_position_index < parameters.head_positions.len() {
+            return;
+        }
+        self.head_position_index = 0;
+        // continue with another pass over the head positions until enough lines were seen
+        if self.measurements.len() < parameters.minimum_number_of_measurements {
+            return;
+        }
+
+        let measurements = subsample(
+            take(&mut self.measurements),
+            parameters.maximum_number_of_measurements,
+        );
+        let number_of_measurements = measurements.len();
+        let solution = solve(
+            Corrections::default(),
+            measurements,
+            context.field_dimensions.clone(),
+        );
+        self.result = Some(calibration_result(
+            solution,
+            number_of_measurements,
+            context,
+        ));
+    }
+}
+
+/// Evenly picks at most `maximum_number_of_measurements` measurements, such that all head
+/// positions stay represented
+fn subsample(
+    measurements: Vec<Measurement>,
+    maximum_number_of_measurements: usize,
+) -> Vec<Measurement> {
+    let maximum_number_of_measurements = maximum_number_of_measurements.max(1);
+    let step = ((measurements.len() + maximum_number_of_measurements - 1)
+        / maximum_number_of_measurements)
+        .max(1);
+    measurements.into_iter().step_by(step).collect()
+}
+
+/// Identifies the goal line, the goal box line and a goal box side line among the lines of one image
+fn identify_calibration_lines(
+    measurement: &CalibrationMeasurement,
+    maximum_angle_deviation: f32,
+) -> Option<Lines> {
+    let lines: Vec<(Line2, Line2)> = measurement
+        .lines_in_image
+        .iter()
+        .filter_map(|&line_in_image| {
+            let line_in_ground = Line(
+                measurement
+                    .camera_matrix
+                    .pixel_to_ground(line_in_image.0)
+                    .ok()?,
+                measurement
+                    .camera_matrix
+                    .pixel_to_ground(line_in_image.1)
+                    .ok()?,
+            );
+            Some((line_in_image, line_in_ground))
+        })
+        .collect();
+    let is_aligned_with = |line: &Line2, direction: Vector2<f32>| {
+        let angle = (line.1 - line.0).angle(&direction);
+        angle.min(PI - angle) < maximum_angle_deviation
+    };
+
+    let mut lines_across: Vec<_> = lines
+        .iter()
+        .filter(|(_, line_in_ground)| {
+            is_aligned_with(line_in_ground, Vector2::y()) && line_in_ground.center().x > 0.0
+        })
+        .collect();
+    lines_across.sort_by(|(_, left), (_, right)| left.center().x.total_cmp(&right.center().x));
+    let [(goal_box_line, goal_box_line_in_ground), (border_line, border_line_in_ground), ..] =
+        lines_across.as_slice()
+    else {
+        return None;
+    };
+    let between_goal_box_line_and_border_line =
+        goal_box_line_in_ground.center().x..border_line_in_ground.center().x;
+    let (connecting_line, _) = lines
+        .iter()
+        .filter(|(_, line_in_ground)| {
+            is_aligned_with(line_in_ground, Vector2::x())
+                && between_goal_box_line_and_border_line.contains(&line_in_ground.center().x)
+        })
+        .max_by(|(_, left), (_, right)| left.length().total_cmp(&right.length()))?;
+
+    Some(Lines {
+        border_line: *border_line,
+        goal_box_line: *goal_box_line,
+        connecting_line: *connecting_line,
+    })
+}
+
+fn calibration_result(
+    solution: Solution,
+    number_of_measurements: usize,
+    context: &CycleContext,
+) -> CameraCalibrationResult {
+    let Corrections {
+        correction_in_robot,
+        correction_in_camera_top,
+        correction_in_camera_bottom,
+    } = solution.corrections;
+    CameraCalibrationResult {
+        number_of_measurements,
+        termination: format!("{:?}", solution.report.termination),
+        number_of_evaluations: solution.report.number_of_evaluations,
+        objective_function: solution.report.objective_function,
+        residuals: solution
+            .residuals
+            .map(|residuals| residuals.iter().copied().collect())
+            .unwrap_or_default(),
+        correction_in_robot: degrees_from_rotation(
+            rotation_from_degrees(*context.correction_in_robot_rotations) * correction_in_robot,
+        ),
+        extrinsic_rotations_top: corrected_extrinsic_rotations(
+            context.top_camera_matrix_parameters.extrinsic_rotations,
+            *context.correction_in_camera_top,
+            correction_in_camera_top,
+        ),
+        extrinsic_rotations_bottom: corrected_extrinsic_rotations(
+            context.bottom_camera_matrix_parameters.extrinsic_rotations,
+            *context.correction_in_camera_bottom,
+            correction_in_camera_bottom,
+        ),
+    }
+}
+
+/// Folds a correction on top of the currently applied camera correction into the extrinsic rotations
+fn corrected_extrinsic_rotations(
+    extrinsic_rotations: Vector3<f32>,
+    current_correction: Rotation3<f32>,
+    correction: Rotation3<f32>,
+) -> Vector3<f32> {
+    degrees_from_rotation(
+        rotation_from_degrees(extrinsic_rotations)
+            * current_correction.inverse()
+            * correction.inverse()
+            * current_correction,
+    )
+}
+
+fn degrees_from_rotation(rotation: Rotation3<f32>) -> Vector3<f32> {
+    let (roll, pitch, yaw) = rotation.euler_angles();
+    vector![roll, pitch, yaw].map(|a: f32| a.to_degrees())
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use nalgebra::{point, Isometry3, Translation3, UnitQuaternion};
+    use types::camera_matrix::CameraMatrix;
+
+    use super::*;
+
+    #[test]
+    fn extrinsic_rotations_absorb_camera_correction() {
+        let extrinsic_rotations = vector![1.0, -2.0, 0.5];
+        let current_correction = Rotation3::from_euler_angles(0.01, 0.0, -0.02);
+        let correction = Rotation3::from_euler_angles(-0.03, 0.02, 0.01);
+
+        let corrected =
+            corrected_extrinsic_rotations(extrinsic_rotations, current_correction, correction);
+
+        // camera_to_head is corrected by the inverse of the correction, see `CameraMatrix::to_corrected`
+        let expected = rotation_from_degrees(extrinsic_rotations)
+            * current_correction.inverse()
+            * correction.inverse();
+        assert_relative_eq!(
+            rotation_from_degrees(corrected) * current_correction.inverse(),
+            expected,
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn identifies_lines_in_front_of_goal_box() {
+        // camera 0.5 m above the ground looking down along the x axis of the robot
+        let camera_to_robot = Isometry3::from_parts(
+            Translation3::new(0.0, 0.0, 0.5),
+            UnitQuaternion::from_euler_angles(0.0, 0.6, 0.0),
+        );
+        let camera_matrix = CameraMatrix::from_normalized_focal_and_center(
+            vector![0.95, 1.27],
+            point![0.5, 0.5],
+            vector![640.0, 480.0],
+            camera_to_robot,
+            Isometry3::identity(),
+            Isometry3::identity(),
+        );
+        let to_image = |line: Line2| {
+            Line(
+                camera_matrix.ground_to_pixel(line.0).unwrap(),
+                camera_matrix.ground_to_pixel(line.1).unwrap(),
+            )
+        };
+        let goal_box_line = to_image(Line(point![1.0, -0.4], point![1.0, 0.4]));
+        let border_line = to_image(Line(point![1.6, -0.6], point![1.6, 0.6]));
+        let connecting_line = to_image(Line(point![1.05, 0.3], point![1.55, 0.3]));
+        let measurement = CalibrationMeasurement {
+            camera_matrix,
+            lines_in_image: vec![connecting_line, border_line, goal_box_line],
+        };
+
+        let lines = identify_calibration_lines(&measurement, 0.35).unwrap();
+
+        for (identified, expected) in [
+            (lines.goal_box_line, goal_box_line),
+            (lines.border_line, border_line),
+            (lines.connecting_line, connecting_line),
+        ] {
+            assert_eq!(identified.0, expected.0);
+            assert_eq!(identified.1, expected.1);
+        }
+    }
+}
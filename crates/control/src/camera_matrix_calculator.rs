@@ -29,6 +29,8 @@ pub struct CycleContext {
 
     bottom_camera_matrix_parameters:
         Parameter<CameraMatrixParameters, "camera_matrix_parameters.vision_bottom">,
+    correction_in_robot_rotations:
+        Parameter<Vector3<f32>, "camera_matrix_parameters.correction_in_robot">,
     field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
     top_camera_matrix_parameters:
         Parameter<CameraMatrixParameters, "camera_matrix_parameters.vision_top">,
@@ -77,6 +79,8 @@ impl CameraMatrixCalculator {
             *context.robot_to_ground,
         );
 
+        let correction_in_robot = *context.correction_in_robot
+            * rotation_from_degrees(*context.correction_in_robot_rotations);
         let field_dimensions = context.field_dimensions;
         context
             .projected_field_lines
@@ -88,14 +92,10 @@ impl CameraMatrixCalculator {
             });
         Ok(MainOutputs {
             camera_matrices: Some(CameraMatrices {
-                top: top_camera_matrix.to_corrected(
-                    *context.correction_in_robot,
-                    *context.correction_in_camera_top,
-                ),
-                bottom: bottom_camera_matrix.to_corrected(
-                    *context.correction_in_robot,
-                    *context.correction_in_camera_bottom,
-                ),
+                top: top_camera_matrix
+                    .to_corrected(correction_in_robot, *context.correction_in_camera_top),
+                bottom: bottom_camera_matrix
+                    .to_corrected(correction_in_robot, *context.correction_in_camera_bottom),
             })
             .into(),
         })
@@ -125,6 +125,12 @@ pub fn camera_to_head(
         * extrinsic_rotation
 }
 
+/// Rotation from roll, pitch and yaw angles in degrees as used in the camera matrix parameters
+pub fn rotation_from_degrees(angles: Vector3<f32>) -> Rotation3<f32> {
+    let angles = angles.map(|a: f32| a.to_radians());
+    Rotation3::from_euler_angles(angles.x, angles.y, angles.z)
+}
+
 fn project_penalty_area_on_images(
     field_dimensions: &FieldDimensions,
     camera_matrix: &CameraMatrix,
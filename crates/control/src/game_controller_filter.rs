@@ -20,7 +20,7 @@ pub struct CreationContext {}
 #[context]
 pub struct CycleContext {
     cycle_time: Input<CycleTime, "cycle_time">,
-    network_message: PerceptionInput<IncomingMessage, "SplNetwork", "message">,
+    network_message: PerceptionInput<Option<IncomingMessage>, "SplNetwork", "message?">,
 }
 
 #[context]
@@ -43,9 +43,10 @@ impl GameControllerFilter {
             .persistent
             .values()
             .flatten()
+            .flatten()
             .filter_map(|message| match message {
                 IncomingMessage::GameController(message) => Some(message),
-                IncomingMessage::Spl(_) => None,
+                IncomingMessage::Spl(_) | IncomingMessage::IncompatibleSpl(_) => None,
             })
         {
             let game_state_changed = match &self.game_controller_state {
@@ -69,6 +70,8 @@ impl GameControllerFilter {
                 sub_state: game_controller_state_message.sub_state,
                 hulks_team_is_home_after_coin_toss: game_controller_state_message
                     .hulks_team_is_home_after_coin_toss,
+                hulks_team_colors: (&game_controller_state_message.hulks_team).into(),
+                opponent_team_colors: (&game_controller_state_message.opponent_team).into(),
             });
         }
         Ok(MainOutputs {
@@ -0,0 +1,338 @@
+use std::f32::consts::{PI, TAU};
+
+use nalgebra::{distance, vector, Isometry2};
+use ordered_float::NotNan;
+use rand::Rng;
+use types::{
+    field_dimensions::FieldDimensions, field_marks::FieldMark, line::Line2,
+    parameters::GlobalRelocalizationParameters,
+};
+
+/// Lines without any close field mark are weighted as if they were this many standard deviations
+/// away, such that single false positive lines do not rule out the correct pose
+const OUTLIER_ERROR_IN_STANDARD_DEVIATIONS: f32 = 3.0;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Particle {
+    pub robot_to_field: Isometry2<f32>,
+    pub weight: f32,
+}
+
+/// Searches the whole field for poses at which `lines_in_robot` match the `field_marks`.
+///
+/// Particles are initialized on a grid covering the field, weighted by the likelihood of the
+/// measured lines and iteratively resampled with perturbation. Returns the best particles which are
+/// at least `minimum_hypothesis_distance` apart, best first.
+pub fn relocalize(
+    lines_in_robot: &[Line2],
+    field_marks: &[FieldMark],
+    field_dimensions: &FieldDimensions,
+    line_length_acceptance_factor: f32,
+    parameters: &GlobalRelocalizationParameters,
+    random_number_generator: &mut impl Rng,
+) -> Vec<Particle> {
+    if lines_in_robot.is_empty() {
+        return vec![];
+    }
+    let weigh = |particles: &mut [Particle]| {
+        weigh_particles(
+            particles,
+            lines_in_robot,
+            field_marks,
+            line_length_acceptance_factor,
+            parameters.measurement_noise,
+        )
+    };
+
+    let mut particles = generate_grid_particles(field_dimensions, parameters);
+    weigh(&mut particles);
+    for _ in 0..parameters.number_of_iterations {
+        particles = resample(
+            &particles,
+            parameters.number_of_particles,
+            parameters,
+            random_number_generator,
+        );
+        weigh(&mut particles);
+    }
+
+    select_distinct_particles(
+        particles,
+        parameters.number_of_hypotheses,
+        parameters.minimum_hypothesis_distance,
+    )
+}
+
+fn generate_grid_particles(
+    field_dimensions: &FieldDimensions,
+    parameters: &GlobalRelocalizationParameters,
+) -> Vec<Particle> {
+    let steps_x = (field_dimensions.length / parameters.grid_resolution).floor() as usize;
+    let steps_y = (field_dimensions.width / parameters.grid_resolution).floor() as usize;
+    let number_of_orientations = parameters.number_of_orientations.max(1);
+    (0..=steps_x)
+        .flat_map(|step_x| (0..=steps_y).map(move |step_y| (step_x, step_y)))
+        .flat_map(|(step_x, step_y)| {
+            let translation = vector![
+                -field_dimensions.length / 2.0 + step_x as f32 * parameters.grid_resolution,
+                -field_dimensions.width / 2.0 + step_y as f32 * parameters.grid_resolution
+            ];
+            (0..number_of_orientations).map(move |orientation| Particle {
+                robot_to_field: Isometry2::new(
+                    translation,
+                    orientation as f32 * TAU / number_of_orientations as f32 - PI,
+                ),
+                weight: 0.0,
+            })
+        })
+        .collect()
+}
+
+/// Sets the weights of all particles to their normalized measurement likelihood
+fn weigh_particles(
+    particles: &mut [Particle],
+    lines_in_robot: &[Line2],
+    field_marks: &[FieldMark],
+    line_length_acceptance_factor: f32,
+    measurement_noise: f32,
+) {
+    let log_likelihoods: Vec<_> = particles
+        .iter()
+        .map(|particle| {
+            log_likelihood(
+                particle.robot_to_field,
+                lines_in_robot,
+                field_marks,
+                line_length_acceptance_factor,
+                measurement_noise,
+            )
+        })
+        .collect();
+    let maximum_log_likelihood = log_likelihoods
+        .iter()
+        .copied()
+        .fold(f32::NEG_INFINITY, f32::max);
+    for (particle, log_likelihood) in particles.iter_mut().zip(&log_likelihoods) {
+        particle.weight = (log_likelihood - maximum_log_likelihood).exp();
+    }
+    let weight_sum: f32 = particles.iter().map(|particle| particle.weight).sum();
+    for particle in particles.iter_mut() {
+        particle.weight /= weight_sum;
+    }
+}
+
+fn log_likelihood(
+    robot_to_field: Isometry2<f32>,
+    lines_in_robot: &[Line2],
+    field_marks: &[FieldMark],
+    line_length_acceptance_factor: f32,
+    measurement_noise: f32,
+) -> f32 {
+    lines_in_robot
+        .iter()
+        .map(|&line_in_robot| {
+            let line_in_field = robot_to_field * line_in_robot;
+            let error = field_marks
+                .iter()
+                .filter_map(|field_mark| {
+                    matching_error(line_in_field, field_mark, line_length_acceptance_factor)
+                })
+                .fold(
+                    OUTLIER_ERROR_IN_STANDARD_DEVIATIONS * measurement_noise,
+                    f32::min,
+                );
+            -0.5 * (error / measurement_noise).powi(2)
+        })
+        .sum()
+}
+
+/// Mean distance between the corresponding points of the measured line and the field mark, if
+/// the measured line is not too long for the field mark
+fn matching_error(
+    measured_line_in_field: Line2,
+    field_mark: &FieldMark,
+    line_length_acceptance_factor: f32,
+) -> Option<f32> {
+    let field_mark_length = match field_mark {
+        FieldMark::Line { line, direction: _ } => line.length(),
+        FieldMark::Circle { center: _, radius } => *radius, // approximation
+    };
+    if measured_line_in_field.length() > field_mark_length * line_length_acceptance_factor {
+        return None;
+    }
+    let (correspondence_0, correspondence_1) = field_mark
+        .to_correspondence_points(measured_line_in_field)
+        .correspondence_points;
+    Some(
+        (distance(&correspondence_0.measured, &correspondence_0.reference)
+            + distance(&correspondence_1.measured, &correspondence_1.reference))
+            / 2.0,
+    )
+}
+
+/// Systematic resampling, each drawn particle is perturbed uniformly within the resampling noise
+fn resample(
+    particles: &[Particle],
+    number_of_particles: usize,
+    parameters: &GlobalRelocalizationParameters,
+    random_number_generator: &mut impl Rng,
+) -> Vec<Particle> {
+    let step = 1.0 / number_of_particles as f32;
+    let mut threshold = random_number_generator.gen_range(0.0..step);
+    let mut cumulative_weight = 0.0;
+    let mut particle_iterator = particles.iter().peekable();
+    let mut resampled_particles = Vec::with_capacity(number_of_particles);
+    while resampled_particles.len() < number_of_particles {
+        let Some(particle) = particle_iterator.peek() else {
+            break;
+        };
+        if cumulative_weight + particle.weight < threshold {
+            cumulative_weight += particle.weight;
+            particle_iterator.next();
+            continue;
+        }
+        let noise = parameters.resampling_noise;
+        let translation = particle.robot_to_field.translation.vector
+            + vector![
+                random_number_generator.gen_range(-1.0..=1.0) * noise.x,
+                random_number_generator.gen_range(-1.0..=1.0) * noise.y
+            ];
+        let orientation = particle.robot_to_field.rotation.angle()
+            + random_number_generator.gen_range(-1.0..=1.0) * noise.z;
+        resampled_particles.push(Particle {
+            robot_to_field: Isometry2::new(translation, orientation),
+            weight: step,
+        });
+        threshold += step;
+    }
+    resampled_particles
+}
+
+fn select_distinct_particles(
+    mut particles: Vec<Particle>,
+    number_of_particles: usize,
+    minimum_distance: f32,
+) -> Vec<Particle> {
+    particles.sort_by_key(|particle| NotNan::new(-particle.weight).unwrap());
+    let mut selected_particles: Vec<Particle> = Vec::with_capacity(number_of_particles);
+    for particle in particles {
+        if selected_particles.len() >= number_of_particles {
+            break;
+        }
+        let is_distinct = selected_particles.iter().all(|selected_particle| {
+            (selected_particle.robot_to_field.translation.vector
+                - particle.robot_to_field.translation.vector)
+                .norm()
+                >= minimum_distance
+        });
+        if is_distinct {
+            selected_particles.push(particle);
+        }
+    }
+    selected_particles
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{point, Vector3};
+    use rand::{rngs::StdRng, SeedableRng};
+    use types::{field_marks::field_marks_from_field_dimensions, line::Line};
+
+    use crate::localization::goal_support_structure_line_marks_from_field_dimensions;
+
+    use super::*;
+
+    fn field_dimensions() -> FieldDimensions {
+        FieldDimensions {
+            ball_radius: 0.05,
+            length: 9.0,
+            width: 6.0,
+            line_width: 0.05,
+            penalty_marker_size: 0.1,
+            goal_box_area_length: 0.6,
+            goal_box_area_width: 2.2,
+            penalty_area_length: 1.65,
+            penalty_area_width: 4.0,
+            penalty_marker_distance: 1.3,
+            center_circle_diameter: 1.5,
+            border_strip_width: 0.7,
+            goal_inner_width: 1.5,
+            goal_post_diameter: 0.1,
+            goal_depth: 0.5,
+        }
+    }
+
+    fn parameters() -> GlobalRelocalizationParameters {
+        GlobalRelocalizationParameters {
+            enable: true,
+            match_quality_low_pass_factor: 0.05,
+            trigger_match_quality: 0.2,
+            minimum_number_of_lines: 2,
+            grid_resolution: 0.5,
+            number_of_orientations: 12,
+            number_of_particles: 500,
+            number_of_iterations: 10,
+            measurement_noise: 0.2,
+            resampling_noise: Vector3::new(0.2, 0.2, 0.1),
+            number_of_hypotheses: 4,
+            minimum_hypothesis_distance: 0.5,
+            seed: 42,
+        }
+    }
+
+    #[test]
+    fn finds_pose_in_front_of_penalty_area() {
+        let field_dimensions = field_dimensions();
+        let field_marks: Vec<_> = field_marks_from_field_dimensions(&field_dimensions)
+            .into_iter()
+            .chain(goal_support_structure_line_marks_from_field_dimensions(
+                &field_dimensions,
+            ))
+            .collect();
+        let robot_to_field = Isometry2::new(vector![1.5, 0.5], 0.3);
+        let lines_in_field = [
+            Line(point![2.85, -1.0], point![2.85, 1.5]),
+            Line(point![2.85, 2.0], point![4.0, 2.0]),
+            Line(point![3.9, -0.8], point![3.9, 0.8]),
+        ];
+        let lines_in_robot: Vec<_> = lines_in_field
+            .iter()
+            .map(|&line| robot_to_field.inverse() * line)
+            .collect();
+
+        let particles = relocalize(
+            &lines_in_robot,
+            &field_marks,
+            &field_dimensions,
+            1.5,
+            &parameters(),
+            &mut StdRng::seed_from_u64(42),
+        );
+
+        // the field is point symmetric, the mirrored pose explains the lines equally well
+        let mirrored_robot_to_field = Isometry2::rotation(PI) * robot_to_field;
+        assert!(particles.iter().any(|particle| {
+            [robot_to_field, mirrored_robot_to_field]
+                .iter()
+                .any(|expected| {
+                    let difference = expected.inverse() * particle.robot_to_field;
+                    difference.translation.vector.norm() < 0.3
+                        && difference.rotation.angle().abs() < 0.15
+                })
+        }));
+    }
+
+    #[test]
+    fn no_lines_yield_no_particles() {
+        let field_dimensions = field_dimensions();
+        let particles = relocalize(
+            &[],
+            &field_marks_from_field_dimensions(&field_dimensions),
+            &field_dimensions,
+            1.5,
+            &parameters(),
+            &mut StdRng::seed_from_u64(42),
+        );
+        assert!(particles.is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+/// Solves the assignment problem for the square matrix `costs[row][column]` with the Hungarian
+/// method in O(n³). Returns the assigned column of each row such that the sum of costs is minimal.
+///
+/// All costs have to be finite.
+pub fn minimum_cost_assignment(costs: &[Vec<f32>]) -> Vec<usize> {
+    let size = costs.len();
+    debug_assert!(costs
+        .iter()
+        .all(|row| row.len() == size && row.iter().all(|cost| cost.is_finite())));
+
+    // Index 0 is a virtual row/column, all others are shifted by one
+    let mut row_potentials = vec![0.0; size + 1];
+    let mut column_potentials = vec![0.0; size + 1];
+    let mut row_of_column = vec![0; size + 1];
+    let mut previous_column = vec![0; size + 1];
+
+    for row in 1..=size {
+        row_of_column[0] = row;
+        let mut column = 0;
+        let mut minimum_slacks = vec![f32::INFINITY; size + 1];
+        let mut visited = vec![false; size + 1];
+        loop {
+            visited[column] = true;
+            let current_row = row_of_column[column];
+            let mut delta = f32::INFINITY;
+            let mut next_column = 0;
+            for candidate in 1..=size {
+                if visited[candidate] {
+                    continue;
+                }
+                let slack = costs[current_row - 1][candidate - 1]
+                    - row_potentials[current_row]
+                    - column_potentials[candidate];
+                if slack < minimum_slacks[candidate] {
+                    minimum_slacks[candidate] = slack;
+                    previous_column[candidate] = column;
+                }
+                if minimum_slacks[candidate] < delta {
+                    delta = minimum_slacks[candidate];
+                    next_column = candidate;
+                }
+            }
+            for candidate in 0..=size {
+                if visited[candidate] {
+                    row_potentials[row_of_column[candidate]] += delta;
+                    column_potentials[candidate] -= delta;
+                } else {
+                    minimum_slacks[candidate] -= delta;
+                }
+            }
+            column = next_column;
+            if row_of_column[column] == 0 {
+                break;
+            }
+        }
+        // Flip the augmenting path
+        while column != 0 {
+            let previous = previous_column[column];
+            row_of_column[column] = row_of_column[previous];
+            column = previous;
+        }
+    }
+
+    let mut column_of_row = vec![0; size];
+    for column in 1..=size {
+        column_of_row[row_of_column[column] - 1] = column - 1;
+    }
+    column_of_row
+}
+
+#[cfg(test)]
+mod test {
+    use itertools::Itertools;
+
+    use super::*;
+
+    fn total_cost(costs: &[Vec<f32>], assignment: &[usize]) -> f32 {
+        assignment
+            .iter()
+            .enumerate()
+            .map(|(row, &column)| costs[row][column])
+            .sum()
+    }
+
+    #[test]
+    fn empty_matrix_has_empty_assignment() {
+        assert!(minimum_cost_assignment(&[]).is_empty());
+    }
+
+    #[test]
+    fn greedy_choice_is_not_optimal() {
+        let costs = vec![
+            vec![1.0, 2.0, 9.0],
+            vec![1.5, 8.0, 9.0],
+            vec![4.0, 3.0, 1.0],
+        ];
+        assert_eq!(minimum_cost_assignment(&costs), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn assignment_matches_brute_force() {
+        let costs: Vec<Vec<f32>> = (0..6)
+            .map(|row| {
+                (0..6)
+                    .map(|column| ((row * 7 + column * 13) % 11) as f32 - 0.5 * column as f32)
+                    .collect()
+            })
+            .collect();
+        let assignment = minimum_cost_assignment(&costs);
+
+        assert!(assignment.iter().all_unique());
+        let minimum_cost = (0..6)
+            .permutations(6)
+            .map(|permutation| total_cost(&costs, &permutation))
+            .fold(f32::INFINITY, f32::min);
+        assert!((total_cost(&costs, &assignment) - minimum_cost).abs() < 1e-4);
+    }
+}
@@ -6,17 +6,21 @@ use framework::{AdditionalOutput, MainOutput};
 use geometry::{
     circle::Circle, line_segment::LineSegment, look_at::LookAt, two_line_segments::TwoLineSegments,
 };
-use itertools::iproduct;
+use itertools::{chain, iproduct};
 use nalgebra::{distance, point, vector, Isometry2, Point2, UnitComplex, Vector2};
 use ordered_float::NotNan;
 use serde::{Deserialize, Serialize};
+use spl_network_messages::Team;
 use types::{
     field_dimensions::FieldDimensions,
-    kick_decision::KickDecision,
+    kick_decision::{KickDecision, KickType},
     kick_target::KickTarget,
-    motion_command::KickVariant,
+    motion_command::{KickVariant, StandingKickVariant},
     obstacles::Obstacle,
-    parameters::{FindKickTargetsParameters, InWalkKickInfoParameters, InWalkKicksParameters},
+    parameters::{
+        FindKickTargetsParameters, InWalkKicksParameters, KickInfoParameters, PassingParameters,
+        StandingKicksParameters,
+    },
     support_foot::Side,
     world_state::BallState,
 };
@@ -36,6 +40,7 @@ pub struct CycleContext {
     field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
 
     in_walk_kicks: Parameter<InWalkKicksParameters, "in_walk_kicks">,
+    standing_kicks: Parameter<StandingKicksParameters, "standing_kicks">,
     angle_distance_weight: Parameter<f32, "kick_selector.angle_distance_weight">,
     max_kick_around_obstacle_angle: Parameter<f32, "kick_selector.max_kick_around_obstacle_angle">,
     kick_pose_obstacle_radius: Parameter<f32, "kick_selector.kick_pose_obstacle_radius">,
@@ -44,6 +49,7 @@ pub struct CycleContext {
     closer_threshold: Parameter<f32, "kick_selector.closer_threshold">,
     find_kick_targets: Parameter<FindKickTargetsParameters, "kick_selector.find_kick_targets">,
     goal_accuracy_margin: Parameter<f32, "kick_selector.goal_accuracy_margin">,
+    passing: Parameter<PassingParameters, "kick_selector.passing">,
 
     default_kick_strength: Parameter<f32, "kick_selector.default_kick_strength">,
     corner_kick_strength: Parameter<f32, "kick_selector.corner_kick_strength">,
@@ -77,6 +83,13 @@ impl KickSelector {
         if context.in_walk_kicks.side.enabled {
             kick_variants.push(KickVariant::Side)
         }
+        let mut standing_kick_variants = Vec::new();
+        if context.standing_kicks.forward.enabled {
+            standing_kick_variants.push(StandingKickVariant::Forward)
+        }
+        if context.standing_kicks.outer.enabled {
+            standing_kick_variants.push(StandingKickVariant::Outer)
+        }
 
         let obstacle_circles = generate_obstacle_circles(
             context.obstacles,
@@ -107,25 +120,63 @@ impl KickSelector {
             *context.corner_kick_strength,
         );
 
+        let pass_targets = if context.passing.enable {
+            collect_pass_targets(
+                *context.robot_to_field,
+                context.field_dimensions,
+                context.obstacles,
+                ball_position,
+                *context.ball_radius_for_kick_target_selection,
+                context.passing,
+            )
+        } else {
+            Vec::new()
+        };
+
         context
             .kick_targets
-            .fill_if_subscribed(|| kick_targets.clone());
-
-        let mut kick_decisions: Vec<_> = iproduct!(sides, kick_variants)
-            .filter_map(|(side, kick_variant)| {
-                kick_decisions_from_targets(
-                    &kick_targets,
-                    context.in_walk_kicks,
-                    kick_variant,
-                    side,
-                    ball_position,
-                    *context.default_kick_strength,
-                )
-            })
-            .flatten()
-            .collect();
+            .fill_if_subscribed(|| chain!(&kick_targets, &pass_targets).copied().collect());
 
-        kick_decisions.sort_by(|left, right| {
+        let decisions_for_targets = |targets: &[KickTarget]| -> Vec<KickDecision> {
+            let (long_kick_targets, short_kick_targets) = partition_long_kick_targets(
+                targets,
+                ball_position,
+                !standing_kick_variants.is_empty(),
+                context.standing_kicks.minimum_target_distance,
+            );
+            let in_walk_kicks = kick_variants
+                .iter()
+                .map(|&variant| KickType::InWalk { variant })
+                .map(|kick| (kick, &short_kick_targets));
+            let standing_kicks = standing_kick_variants
+                .iter()
+                .map(|&variant| KickType::Standing { variant })
+                .map(|kick| (kick, &long_kick_targets));
+            iproduct!(sides, in_walk_kicks.chain(standing_kicks))
+                .filter_map(|(side, (kick, targets))| {
+                    kick_decisions_from_targets(
+                        targets,
+                        kick.info(context.in_walk_kicks, context.standing_kicks),
+                        kick,
+                        side,
+                        ball_position,
+                        *context.default_kick_strength,
+                    )
+                })
+                .flatten()
+                .collect()
+        };
+        let mut kick_decisions: Vec<_> = chain!(
+            decisions_for_targets(&kick_targets)
+                .into_iter()
+                .map(|decision| (decision, 0.0)),
+            decisions_for_targets(&pass_targets)
+                .into_iter()
+                .map(|decision| (decision, context.passing.preference)),
+        )
+        .collect();
+
+        kick_decisions.sort_by(|(left, left_preference), (right, right_preference)| {
             let left_in_obstacle = is_inside_any_obstacle(
                 left.kick_pose,
                 context.obstacles,
@@ -137,9 +188,11 @@ impl KickSelector {
                 *context.kick_pose_obstacle_radius,
             );
             let distance_to_left =
-                distance_to_kick_pose(left.kick_pose, *context.angle_distance_weight);
+                distance_to_kick_pose(left.kick_pose, *context.angle_distance_weight)
+                    - left_preference;
             let distance_to_right =
-                distance_to_kick_pose(right.kick_pose, *context.angle_distance_weight);
+                distance_to_kick_pose(right.kick_pose, *context.angle_distance_weight)
+                    - right_preference;
             match (left_in_obstacle, right_in_obstacle) {
                 (true, false) => Ordering::Less,
                 (false, true) => Ordering::Greater,
@@ -147,6 +200,11 @@ impl KickSelector {
             }
         });
 
+        let kick_decisions: Vec<_> = kick_decisions
+            .into_iter()
+            .map(|(decision, _)| decision)
+            .collect();
+
         Ok(MainOutputs {
             kick_decisions: Some(kick_decisions).into(),
             instant_kick_decisions: Some(instant_kick_decisions).into(),
@@ -154,6 +212,20 @@ impl KickSelector {
     }
 }
 
+/// Splits targets into long shots and other targets, long shots are played with standing kicks if
+/// any is enabled, all others in walk
+fn partition_long_kick_targets(
+    targets: &[KickTarget],
+    ball_position: Point2<f32>,
+    is_any_standing_kick_enabled: bool,
+    minimum_target_distance: f32,
+) -> (Vec<KickTarget>, Vec<KickTarget>) {
+    targets.iter().copied().partition(|target| {
+        is_any_standing_kick_enabled
+            && distance(&ball_position, &target.position) >= minimum_target_distance
+    })
+}
+
 fn generate_obstacle_circles(
     obstacles: &[Obstacle],
     ball_radius_for_kick_target_selection: f32,
@@ -235,7 +307,7 @@ fn generate_decisions_for_instant_kicks(
                     .mutate_if_subscribed(|targets| targets.as_mut().unwrap().push(target));
                 let kick_pose = compute_kick_pose(ball_position, target, kick_info, kicking_side);
                 Some(KickDecision {
-                    variant,
+                    kick: KickType::InWalk { variant },
                     kicking_side,
                     kick_pose,
                     strength: default_kick_strength,
@@ -342,6 +414,48 @@ fn collect_kick_targets(
         .collect()
 }
 
+/// Targets at the positions of teammates further towards the opponent goal, whose lane from the
+/// ball is not blocked by any other obstacle
+fn collect_pass_targets(
+    robot_to_field: Isometry2<f32>,
+    field_dimensions: &FieldDimensions,
+    obstacles: &[Obstacle],
+    ball_position: Point2<f32>,
+    ball_radius_for_kick_target_selection: f32,
+    parameters: &PassingParameters,
+) -> Vec<KickTarget> {
+    let ball_in_field = robot_to_field * ball_position;
+    obstacles
+        .iter()
+        .enumerate()
+        .filter(|(_, obstacle)| obstacle.team == Team::Hulks)
+        .filter(|(_, teammate)| {
+            let teammate_in_field = robot_to_field * teammate.position;
+            parameters
+                .distance
+                .contains(&distance(&ball_position, &teammate.position))
+                && teammate_in_field.x - ball_in_field.x >= parameters.minimum_forward_progress
+                && field_dimensions.is_inside_field(teammate_in_field)
+        })
+        .filter(|(receiver_index, teammate)| {
+            let ball_to_teammate = LineSegment(ball_position, teammate.position);
+            obstacles
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| index != receiver_index)
+                .all(|(_, obstacle)| {
+                    let circle = Circle {
+                        center: obstacle.position,
+                        radius: obstacle.radius_at_foot_height
+                            + ball_radius_for_kick_target_selection,
+                    };
+                    !circle.intersects_line_segment(&ball_to_teammate)
+                })
+        })
+        .map(|(_, teammate)| KickTarget::new_with_strength(teammate.position, parameters.strength))
+        .collect()
+}
+
 fn generate_corner_kick_targets(
     parameters: &FindKickTargetsParameters,
     field_dimensions: &FieldDimensions,
@@ -379,8 +493,8 @@ fn generate_goal_line_kick_targets(
 
 fn kick_decisions_from_targets(
     targets_to_kick_to: &[KickTarget],
-    in_walk_kicks: &InWalkKicksParameters,
-    variant: KickVariant,
+    kick_info: &KickInfoParameters,
+    kick: KickType,
     kicking_side: Side,
     ball_position: Point2<f32>,
     default_strength: f32,
@@ -389,10 +503,9 @@ fn kick_decisions_from_targets(
         targets_to_kick_to
             .iter()
             .map(|&KickTarget { position, strength }| {
-                let kick_info = &in_walk_kicks[variant];
                 let kick_pose = compute_kick_pose(ball_position, position, kick_info, kicking_side);
                 KickDecision {
-                    variant,
+                    kick,
                     kicking_side,
                     kick_pose,
                     strength: strength.unwrap_or(default_strength),
@@ -428,7 +541,7 @@ fn mirror_kick_offset(kick_offset: Vector2<f32>) -> Vector2<f32> {
 fn compute_kick_pose(
     ball_position: Point2<f32>,
     target_to_kick_to: Point2<f32>,
-    kick_info: &InWalkKickInfoParameters,
+    kick_info: &KickInfoParameters,
     side: Side,
 ) -> Isometry2<f32> {
     let kick_rotation = ball_position.look_at(&target_to_kick_to);
@@ -462,3 +575,59 @@ fn is_ball_in_opponents_corners(
         distance(&global_ball, &right_opponent_corner) < parameters.distance_from_corner;
     ball_near_left_opponent_corner || ball_near_right_opponent_corner
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(targets: &[KickTarget]) -> Vec<Point2<f32>> {
+        targets.iter().map(|target| target.position).collect()
+    }
+
+    #[test]
+    fn distant_targets_are_long_shots() {
+        let targets = [
+            KickTarget::new(point![1.0, 0.0]),
+            KickTarget::new(point![4.0, 3.0]),
+            KickTarget::new(point![3.0, 4.0]),
+            KickTarget::new(point![6.0, 0.0]),
+        ];
+
+        let (long_kick_targets, short_kick_targets) =
+            partition_long_kick_targets(&targets, point![1.0, 0.0], true, 5.0);
+
+        assert_eq!(positions(&long_kick_targets), [point![6.0, 0.0]]);
+        assert_eq!(
+            positions(&short_kick_targets),
+            [point![1.0, 0.0], point![4.0, 3.0], point![3.0, 4.0]]
+        );
+    }
+
+    #[test]
+    fn target_at_minimum_distance_is_long_shot() {
+        let targets = [KickTarget::new(point![3.0, 4.0])];
+
+        let (long_kick_targets, short_kick_targets) =
+            partition_long_kick_targets(&targets, Point2::origin(), true, 5.0);
+
+        assert_eq!(positions(&long_kick_targets), [point![3.0, 4.0]]);
+        assert!(short_kick_targets.is_empty());
+    }
+
+    #[test]
+    fn every_target_is_played_in_walk_without_standing_kicks() {
+        let targets = [
+            KickTarget::new(point![1.0, 0.0]),
+            KickTarget::new(point![9.0, 0.0]),
+        ];
+
+        let (long_kick_targets, short_kick_targets) =
+            partition_long_kick_targets(&targets, Point2::origin(), false, 5.0);
+
+        assert!(long_kick_targets.is_empty());
+        assert_eq!(
+            positions(&short_kick_targets),
+            [point![1.0, 0.0], point![9.0, 0.0]]
+        );
+    }
+}
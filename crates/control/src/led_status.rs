@@ -37,7 +37,7 @@ pub struct CycleContext {
 
     balls_bottom: PerceptionInput<Option<Vec<Ball>>, "VisionBottom", "balls?">,
     balls_top: PerceptionInput<Option<Vec<Ball>>, "VisionTop", "balls?">,
-    network_message: PerceptionInput<IncomingMessage, "SplNetwork", "message">,
+    network_message: PerceptionInput<Option<IncomingMessage>, "SplNetwork", "message?">,
     sensor_data: Input<SensorData, "sensor_data">,
 }
 
@@ -175,7 +175,7 @@ impl LedStatus {
             .find_map(|(timestamp, messages)| {
                 messages
                     .iter()
-                    .any(|message| matches!(message, IncomingMessage::GameController(_)))
+                    .any(|message| matches!(message, Some(IncomingMessage::GameController(_))))
                     .then_some(timestamp)
             })
         {
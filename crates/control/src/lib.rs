@@ -4,6 +4,7 @@ pub mod ball_filter;
 pub mod ball_state_composer;
 pub mod behavior;
 pub mod button_filter;
+pub mod camera_calibration;
 pub mod camera_matrix_calculator;
 pub mod center_of_mass_provider;
 pub mod dribble_path_planner;
@@ -11,8 +12,10 @@ pub mod fake_data;
 pub mod fall_state_estimation;
 pub mod game_controller_filter;
 pub mod game_state_filter;
+pub mod global_localization;
 pub mod ground_contact_detector;
 pub mod ground_provider;
+pub mod hungarian;
 pub mod kick_selector;
 pub mod kinematics_provider;
 pub mod led_status;
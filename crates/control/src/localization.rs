@@ -1,53 +1,78 @@
 use std::{
     f32::consts::{FRAC_PI_2, PI},
     mem::take,
+    time::SystemTime,
 };
 
 use approx::assert_relative_eq;
 use color_eyre::{eyre::WrapErr, Result};
 use context_attribute::context;
 use filtering::pose_filter::PoseFilter;
-use framework::{AdditionalOutput, HistoricInput, MainOutput, PerceptionInput};
+use framework::{
+    deserialize_not_implemented, AdditionalOutput, HistoricInput, MainOutput, PerceptionInput,
+};
 use nalgebra::{
     distance, matrix, point, vector, Isometry2, Matrix, Matrix2, Matrix3, Point2, Rotation2,
     Translation2, Vector2, Vector3,
 };
 use ordered_float::NotNan;
+use rand::{rngs::StdRng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use spl_network_messages::{GamePhase, Penalty, PlayerNumber, Team};
 use types::{
     field_dimensions::FieldDimensions,
-    field_marks::{field_marks_from_field_dimensions, CorrespondencePoints, Direction, FieldMark},
+    field_marks::{
+        field_marks_from_field_dimensions, point_landmarks_from_field_dimensions,
+        CorrespondencePoints, Direction, FieldMark, PointLandmark, PointLandmarkKind,
+    },
     game_controller_state::GameControllerState,
+    goal_post::GoalPost,
     initial_pose::InitialPose,
     line::{Line, Line2},
     line_data::LineData,
+    line_intersections::LineIntersections,
     localization::{ScoredPose, Update},
     multivariate_normal_distribution::MultivariateNormalDistribution,
+    parameters::GlobalRelocalizationParameters,
+    penalty_marks::PenaltyMarks,
     players::Players,
     primary_state::PrimaryState,
     support_foot::Side,
 };
 
+use crate::global_localization::relocalize;
+
+/// Intersections are computed from the same lines that are fused as line measurements, their noise
+/// is inflated by this factor to not count that evidence twice
+const INTERSECTION_NOISE_INFLATION_WITH_LINE_MEASUREMENTS: f32 = 4.0;
+
 #[derive(Deserialize, Serialize)]
 pub struct Localization {
     field_marks: Vec<FieldMark>,
+    point_landmarks: Vec<PointLandmark>,
     last_primary_state: PrimaryState,
     hypotheses: Vec<ScoredPose>,
     hypotheses_when_entered_playing: Vec<ScoredPose>,
     is_penalized_with_motion_in_set: bool,
     was_picked_up_while_penalized_with_motion_in_set: bool,
+    /// Low-pass filtered fraction of measured lines well matched by the best hypothesis
+    match_quality: f32,
+    #[serde(skip, default = "deserialize_not_implemented")]
+    random_number_generator: StdRng,
 }
 
 #[context]
 pub struct CreationContext {
     field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
+    global_relocalization:
+        Parameter<GlobalRelocalizationParameters, "localization.global_relocalization">,
 }
 
 #[context]
 pub struct CycleContext {
     correspondence_lines: AdditionalOutput<Vec<Line2>, "localization.correspondence_lines">,
     fit_errors: AdditionalOutput<Vec<Vec<Vec<Vec<f32>>>>, "localization.fit_errors">,
+    match_quality: AdditionalOutput<f32, "localization.match_quality">,
     measured_lines_in_field: AdditionalOutput<Vec<Line2>, "localization.measured_lines_in_field">,
     pose_hypotheses: AdditionalOutput<Vec<ScoredPose>, "localization.pose_hypotheses">,
     updates: AdditionalOutput<Vec<Vec<Update>>, "localization.updates">,
@@ -61,6 +86,11 @@ pub struct CycleContext {
 
     circle_measurement_noise: Parameter<Vector2<f32>, "localization.circle_measurement_noise">,
     field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
+    global_relocalization:
+        Parameter<GlobalRelocalizationParameters, "localization.global_relocalization">,
+    goal_post_association_distance: Parameter<f32, "localization.goal_post_association_distance">,
+    goal_post_measurement_noise:
+        Parameter<Vector2<f32>, "localization.goal_post_measurement_noise">,
     good_matching_threshold: Parameter<f32, "localization.good_matching_threshold">,
     gradient_convergence_threshold: Parameter<f32, "localization.gradient_convergence_threshold">,
     gradient_descent_step_size: Parameter<f32, "localization.gradient_descent_step_size">,
@@ -72,6 +102,10 @@ pub struct CycleContext {
         Parameter<Matrix3<f32>, "localization.initial_hypothesis_covariance">,
     initial_hypothesis_score: Parameter<f32, "localization.initial_hypothesis_score">,
     initial_poses: Parameter<Players<InitialPose>, "localization.initial_poses">,
+    intersection_association_distance:
+        Parameter<f32, "localization.intersection_association_distance">,
+    intersection_measurement_noise:
+        Parameter<Vector2<f32>, "localization.intersection_measurement_noise">,
     line_length_acceptance_factor: Parameter<f32, "localization.line_length_acceptance_factor">,
     line_measurement_noise: Parameter<Vector2<f32>, "localization.line_measurement_noise">,
     maximum_amount_of_gradient_descent_iterations:
@@ -80,16 +114,31 @@ pub struct CycleContext {
         Parameter<usize, "localization.maximum_amount_of_outer_iterations">,
     minimum_fit_error: Parameter<f32, "localization.minimum_fit_error">,
     odometry_noise: Parameter<Vector3<f32>, "localization.odometry_noise">,
+    penalty_mark_association_distance:
+        Parameter<f32, "localization.penalty_mark_association_distance">,
+    penalty_mark_measurement_noise:
+        Parameter<Vector2<f32>, "localization.penalty_mark_measurement_noise">,
     player_number: Parameter<PlayerNumber, "player_number">,
     score_per_good_match: Parameter<f32, "localization.score_per_good_match">,
+    use_goal_post_measurements: Parameter<bool, "localization.use_goal_post_measurements">,
+    use_intersection_measurements: Parameter<bool, "localization.use_intersection_measurements">,
     use_line_measurements: Parameter<bool, "localization.use_line_measurements">,
+    use_penalty_mark_measurements: Parameter<bool, "localization.use_penalty_mark_measurements">,
     injected_robot_to_field_of_home_after_coin_toss_before_second_half: Parameter<
         Option<Isometry2<f32>>,
         "injected_robot_to_field_of_home_after_coin_toss_before_second_half?",
     >,
 
+    goal_posts_bottom: PerceptionInput<Option<Vec<GoalPost>>, "VisionBottom", "goal_posts?">,
+    goal_posts_top: PerceptionInput<Option<Vec<GoalPost>>, "VisionTop", "goal_posts?">,
     line_data_bottom: PerceptionInput<Option<LineData>, "VisionBottom", "line_data?">,
     line_data_top: PerceptionInput<Option<LineData>, "VisionTop", "line_data?">,
+    line_intersections_bottom:
+        PerceptionInput<Option<LineIntersections>, "VisionBottom", "line_intersections?">,
+    line_intersections_top:
+        PerceptionInput<Option<LineIntersections>, "VisionTop", "line_intersections?">,
+    penalty_marks_bottom: PerceptionInput<Option<PenaltyMarks>, "VisionBottom", "penalty_marks?">,
+    penalty_marks_top: PerceptionInput<Option<PenaltyMarks>, "VisionTop", "penalty_marks?">,
 
     robot_to_field: CyclerState<Isometry2<f32>, "robot_to_field">,
 }
@@ -111,11 +160,14 @@ impl Localization {
                     context.field_dimensions,
                 ))
                 .collect(),
+            point_landmarks: point_landmarks_from_field_dimensions(context.field_dimensions),
             last_primary_state: PrimaryState::Unstiff,
             hypotheses: vec![],
             hypotheses_when_entered_playing: vec![],
             is_penalized_with_motion_in_set: false,
             was_picked_up_while_penalized_with_motion_in_set: false,
+            match_quality: 1.0,
+            random_number_generator: StdRng::seed_from_u64(context.global_relocalization.seed),
         })
     }
 
@@ -240,6 +292,9 @@ impl Localization {
 
     fn update_state(&mut self, context: &mut CycleContext) -> Result<()> {
         let mut fit_errors_per_measurement = vec![];
+        let mut number_of_lines_per_hypothesis = vec![0; self.hypotheses.len()];
+        let mut number_of_good_matches_per_hypothesis = vec![0; self.hypotheses.len()];
+        let mut latest_lines_in_robot = vec![];
 
         context.measured_lines_in_field.fill_if_subscribed(Vec::new);
         context.correspondence_lines.fill_if_subscribed(Vec::new);
@@ -261,7 +316,17 @@ impl Localization {
             let current_odometry_to_last_odometry = context
                 .current_odometry_to_last_odometry
                 .get(line_data_top_timestamp);
-
+            let current_lines_in_robot: Vec<_> = line_data_top
+                .iter()
+                .chain(line_data_bottom.iter())
+                .filter_map(|data| data.as_ref())
+                .flat_map(|line_data| line_data.lines_in_robot.iter().copied())
+                .collect();
+            if !current_lines_in_robot.is_empty() {
+                latest_lines_in_robot = current_lines_in_robot;
+            }
+            let current_point_measurements =
+                collect_point_measurements(context, line_data_top_timestamp);
             let mut fit_errors_per_hypothesis = vec![];
             for (hypothesis_index, scored_state) in self.hypotheses.iter_mut().enumerate() {
                 if let Some(current_odometry_to_last_odometry) = current_odometry_to_last_odometry {
@@ -273,6 +338,32 @@ impl Localization {
                     .wrap_err("failed to predict pose filter")?;
                     scored_state.score *= *context.hypothesis_prediction_score_reduction_factor;
                 }
+                for measurement in &current_point_measurements {
+                    let robot_to_field = scored_state.state.as_isometry();
+                    let Some((reference, association_distance)) = associate_point_landmark(
+                        &self.point_landmarks,
+                        measurement.kind,
+                        robot_to_field * measurement.position_in_robot,
+                        measurement.association_distance,
+                    ) else {
+                        continue;
+                    };
+                    scored_state
+                        .state
+                        .update_with_2d_translation(
+                            measurement.position_in_robot.coords,
+                            measurement.noise,
+                            |state| {
+                                let robot_to_field =
+                                    Isometry2::new(vector![state.x, state.y], state.z);
+                                (robot_to_field.inverse() * reference).coords
+                            },
+                        )
+                        .context("Failed to update pose filter")?;
+                    if association_distance < *context.good_matching_threshold {
+                        scored_state.score += *context.score_per_good_match;
+                    }
+                }
                 if *context.use_line_measurements {
                     let robot_to_field = scored_state.state.as_isometry();
                     let current_measured_lines_in_field: Vec<_> = line_data_top
@@ -339,6 +430,8 @@ impl Localization {
                     if context.fit_errors.is_subscribed() {
                         fit_errors_per_hypothesis.push(fit_errors);
                     }
+                    number_of_lines_per_hypothesis[hypothesis_index] +=
+                        current_measured_lines_in_field.len();
                     let clamped_fit_error = fit_error.max(*context.minimum_fit_error);
                     let number_of_measurements_weight =
                         1.0 / field_mark_correspondences.len() as f32;
@@ -431,6 +524,7 @@ impl Localization {
                             < *context.good_matching_threshold
                         {
                             scored_state.score += *context.score_per_good_match;
+                            number_of_good_matches_per_hypothesis[hypothesis_index] += 1;
                         }
                     }
                 }
@@ -442,6 +536,54 @@ impl Localization {
             }
         }
 
+        self.update_match_quality(
+            &number_of_lines_per_hypothesis,
+            &number_of_good_matches_per_hypothesis,
+            context.global_relocalization,
+        );
+        let relocalization = context.global_relocalization;
+        if relocalization.enable
+            && self.match_quality < relocalization.trigger_match_quality
+            && latest_lines_in_robot.len() >= relocalization.minimum_number_of_lines
+        {
+            let particles = relocalize(
+                &latest_lines_in_robot,
+                &self.field_marks,
+                context.field_dimensions,
+                *context.line_length_acceptance_factor,
+                relocalization,
+                &mut self.random_number_generator,
+            );
+            if let Some(best_particle) = particles.first() {
+                // the field is point symmetric, hence relocalized hypotheses are added alongside the
+                // existing ones instead of replacing them, subsequent matches decide between them
+                let best_weight = best_particle.weight;
+                let best_score = self
+                    .get_best_hypothesis()
+                    .map_or(*context.initial_hypothesis_score, |hypothesis| {
+                        hypothesis.score
+                    });
+                let existing_hypotheses = take(&mut self.hypotheses);
+                // the best hypothesis is the last one with maximal score, existing hypotheses come
+                // last such that the previous estimate wins ties
+                self.hypotheses = particles
+                    .iter()
+                    .map(|particle| {
+                        ScoredPose::from_isometry(
+                            particle.robot_to_field,
+                            *context.initial_hypothesis_covariance,
+                            best_score * particle.weight / best_weight,
+                        )
+                    })
+                    .chain(existing_hypotheses)
+                    .collect();
+            }
+            self.match_quality = 1.0;
+        }
+        context
+            .match_quality
+            .fill_if_subscribed(|| self.match_quality);
+
         let best_hypothesis = self
             .get_best_hypothesis()
             .expect("Expected at least one hypothesis");
@@ -475,6 +617,10 @@ impl Localization {
             .map(|game_controller_state| game_controller_state.game_phase);
 
         self.reset_state(primary_state, game_phase, &context, &penalty);
+        if primary_state != self.last_primary_state {
+            // hypotheses may have been re-seeded, past matches do not tell anything about them
+            self.match_quality = 1.0;
+        }
         self.last_primary_state = primary_state;
 
         if self.is_penalized_with_motion_in_set && !context.has_ground_contact {
@@ -514,6 +660,30 @@ impl Localization {
         })
     }
 
+    fn update_match_quality(
+        &mut self,
+        number_of_lines_per_hypothesis: &[usize],
+        number_of_good_matches_per_hypothesis: &[usize],
+        parameters: &GlobalRelocalizationParameters,
+    ) {
+        let Some((best_hypothesis_index, _)) = self
+            .hypotheses
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, scored_filter)| NotNan::new(scored_filter.score).unwrap())
+        else {
+            return;
+        };
+        let number_of_lines = number_of_lines_per_hypothesis[best_hypothesis_index];
+        if number_of_lines == 0 {
+            return;
+        }
+        let good_match_ratio = number_of_good_matches_per_hypothesis[best_hypothesis_index] as f32
+            / number_of_lines as f32;
+        self.match_quality = (1.0 - parameters.match_quality_low_pass_factor) * self.match_quality
+            + parameters.match_quality_low_pass_factor * good_match_ratio;
+    }
+
     fn get_best_hypothesis(&self) -> Option<&ScoredPose> {
         self.hypotheses
             .iter()
@@ -521,6 +691,123 @@ impl Localization {
     }
 }
 
+/// Measurement of a `PointLandmark` in robot coordinates
+struct PointMeasurement {
+    position_in_robot: Point2<f32>,
+    kind: PointLandmarkKind,
+    noise: Matrix2<f32>,
+    association_distance: f32,
+}
+
+fn collect_point_measurements(
+    context: &CycleContext,
+    timestamp: &SystemTime,
+) -> Vec<PointMeasurement> {
+    let mut measurements = Vec::new();
+    // The four goal posts are point symmetric and measurements are associated with the closest
+    // post, so they refine a hypothesis but cannot tell it apart from its mirrored counterpart
+    if *context.use_goal_post_measurements {
+        let noise = Matrix2::from_diagonal(context.goal_post_measurement_noise);
+        measurements.extend(
+            persistent_data_at(
+                [&context.goal_posts_top, &context.goal_posts_bottom],
+                timestamp,
+            )
+            .into_iter()
+            .flatten()
+            .map(|goal_post| PointMeasurement {
+                position_in_robot: goal_post.position,
+                kind: PointLandmarkKind::GoalPost,
+                noise: goal_post.covariance + noise,
+                association_distance: *context.goal_post_association_distance,
+            }),
+        );
+    }
+    if *context.use_intersection_measurements {
+        let noise = intersection_noise(
+            *context.intersection_measurement_noise,
+            *context.use_line_measurements,
+        );
+        measurements.extend(
+            persistent_data_at(
+                [
+                    &context.line_intersections_top,
+                    &context.line_intersections_bottom,
+                ],
+                timestamp,
+            )
+            .into_iter()
+            .flat_map(|line_intersections| &line_intersections.intersections_in_robot)
+            .map(|intersection| PointMeasurement {
+                position_in_robot: intersection.position,
+                kind: PointLandmarkKind::Intersection(intersection.kind),
+                noise,
+                association_distance: *context.intersection_association_distance,
+            }),
+        );
+    }
+    if *context.use_penalty_mark_measurements {
+        measurements.extend(
+            persistent_data_at(
+                [&context.penalty_marks_top, &context.penalty_marks_bottom],
+                timestamp,
+            )
+            .into_iter()
+            .flat_map(|penalty_marks| &penalty_marks.positions_in_robot)
+            .map(|position| PointMeasurement {
+                position_in_robot: *position,
+                kind: PointLandmarkKind::PenaltyMark,
+                noise: Matrix2::from_diagonal(context.penalty_mark_measurement_noise),
+                association_distance: *context.penalty_mark_association_distance,
+            }),
+        );
+    }
+    measurements
+}
+
+fn intersection_noise(noise: Vector2<f32>, use_line_measurements: bool) -> Matrix2<f32> {
+    let inflation = if use_line_measurements {
+        INTERSECTION_NOISE_INFLATION_WITH_LINE_MEASUREMENTS
+    } else {
+        1.0
+    };
+    Matrix2::from_diagonal(&(noise * inflation))
+}
+
+fn persistent_data_at<'data, T>(
+    inputs: [&PerceptionInput<Vec<Option<&'data T>>>; 2],
+    timestamp: &SystemTime,
+) -> Vec<&'data T> {
+    inputs
+        .into_iter()
+        .filter_map(|input| input.persistent.get(timestamp))
+        .flatten()
+        .flatten()
+        .copied()
+        .collect()
+}
+
+/// Closest reference landmark of the same kind as the measured one together with their distance,
+/// if it is within the association distance
+fn associate_point_landmark(
+    reference_landmarks: &[PointLandmark],
+    kind: PointLandmarkKind,
+    measured_position_in_field: Point2<f32>,
+    maximum_association_distance: f32,
+) -> Option<(Point2<f32>, f32)> {
+    reference_landmarks
+        .iter()
+        .filter(|reference| reference.kind == kind)
+        .map(|reference| {
+            (
+                reference.position,
+                distance(&reference.position, &measured_position_in_field),
+            )
+        })
+        .filter(|(_, distance)| *distance < maximum_association_distance)
+        .min_by(|(_, left), (_, right)| left.total_cmp(right))
+}
+
 pub fn goal_support_structure_line_marks_from_field_dimensions(
     field_dimensions: &FieldDimensions,
 ) -> Vec<FieldMark> {
@@ -988,6 +1275,7 @@ mod tests {
     use std::f32::consts::FRAC_PI_4;
 
     use nalgebra::point;
+    use types::line_intersections::IntersectionKind;
 
     use super::*;
 
@@ -1668,4 +1956,55 @@ mod tests {
         let update = get_2d_translation_measurement(robot_to_field, field_mark_correspondence);
         assert_relative_eq!(update, vector![0.0, -2.0], epsilon = 0.0001);
     }
+
+    #[test]
+    fn measured_point_is_associated_with_closest_reference_of_same_kind() {
+        let reference_landmarks = [
+            PointLandmark {
+                position: point![4.5, 0.8],
+                kind: PointLandmarkKind::GoalPost,
+            },
+            PointLandmark {
+                position: point![4.5, -0.8],
+                kind: PointLandmarkKind::GoalPost,
+            },
+            PointLandmark {
+                position: point![4.5, -1.1],
+                kind: PointLandmarkKind::Intersection(IntersectionKind::T),
+            },
+        ];
+
+        let association = associate_point_landmark(
+            &reference_landmarks,
+            PointLandmarkKind::GoalPost,
+            point![4.4, -1.0],
+            0.5,
+        );
+        assert_eq!(
+            association.map(|(reference, _)| reference),
+            Some(point![4.5, -0.8])
+        );
+
+        let association = associate_point_landmark(
+            &reference_landmarks,
+            PointLandmarkKind::Intersection(IntersectionKind::L),
+            point![4.4, -1.0],
+            0.5,
+        );
+        assert!(association.is_none());
+    }
+
+    #[test]
+    fn intersection_noise_is_inflated_when_lines_are_fused_as_well() {
+        let noise = vector![0.5, 0.25];
+
+        assert_relative_eq!(
+            intersection_noise(noise, false),
+            Matrix2::new(0.5, 0.0, 0.0, 0.25)
+        );
+        assert_relative_eq!(
+            intersection_noise(noise, true),
+            Matrix2::new(2.0, 0.0, 0.0, 1.0)
+        );
+    }
 }
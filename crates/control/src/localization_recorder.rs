@@ -12,7 +12,9 @@ use framework::{HistoricInput, PerceptionInput};
 use nalgebra::Isometry2;
 use serde::{Deserialize, Serialize};
 use types::{
-    game_controller_state::GameControllerState, line_data::LineData, primary_state::PrimaryState,
+    game_controller_state::GameControllerState, goal_post::GoalPost, line_data::LineData,
+    line_intersections::LineIntersections, penalty_marks::PenaltyMarks,
+    primary_state::PrimaryState,
 };
 
 #[derive(Deserialize, Serialize)]
@@ -40,8 +42,16 @@ pub struct CycleContext {
     primary_state: Input<PrimaryState, "primary_state">,
     robot_to_field: Input<Option<Isometry2<f32>>, "robot_to_field?">,
 
+    goal_posts_bottom: PerceptionInput<Option<Vec<GoalPost>>, "VisionBottom", "goal_posts?">,
+    goal_posts_top: PerceptionInput<Option<Vec<GoalPost>>, "VisionTop", "goal_posts?">,
     line_data_bottom: PerceptionInput<Option<LineData>, "VisionBottom", "line_data?">,
     line_data_top: PerceptionInput<Option<LineData>, "VisionTop", "line_data?">,
+    line_intersections_bottom:
+        PerceptionInput<Option<LineIntersections>, "VisionBottom", "line_intersections?">,
+    line_intersections_top:
+        PerceptionInput<Option<LineIntersections>, "VisionTop", "line_intersections?">,
+    penalty_marks_bottom: PerceptionInput<Option<PenaltyMarks>, "VisionBottom", "penalty_marks?">,
+    penalty_marks_top: PerceptionInput<Option<PenaltyMarks>, "VisionTop", "penalty_marks?">,
 }
 
 #[context]
@@ -109,6 +119,30 @@ impl LocalizationRecorder {
             has_ground_contact: *context.has_ground_contact,
             primary_state: *context.primary_state,
             robot_to_field: context.robot_to_field.cloned(),
+            goal_posts_bottom_persistent: context
+                .goal_posts_bottom
+                .persistent
+                .iter()
+                .map(|(key, value)| (*key, value.iter().map(|value| value.cloned()).collect()))
+                .collect(),
+            goal_posts_bottom_temporary: context
+                .goal_posts_bottom
+                .temporary
+                .iter()
+                .map(|(key, value)| (*key, value.iter().map(|value| value.cloned()).collect()))
+                .collect(),
+            goal_posts_top_persistent: context
+                .goal_posts_top
+                .persistent
+                .iter()
+                .map(|(key, value)| (*key, value.iter().map(|value| value.cloned()).collect()))
+                .collect(),
+            goal_posts_top_temporary: context
+                .goal_posts_top
+                .temporary
+                .iter()
+                .map(|(key, value)| (*key, value.iter().map(|value| value.cloned()).collect()))
+                .collect(),
             line_data_bottom_persistent: context
                 .line_data_bottom
                 .persistent
@@ -133,6 +167,54 @@ impl LocalizationRecorder {
                 .iter()
                 .map(|(key, value)| (*key, value.iter().map(|value| value.cloned()).collect()))
                 .collect(),
+            line_intersections_bottom_persistent: context
+                .line_intersections_bottom
+                .persistent
+                .iter()
+                .map(|(key, value)| (*key, value.iter().map(|value| value.cloned()).collect()))
+                .collect(),
+            line_intersections_bottom_temporary: context
+                .line_intersections_bottom
+                .temporary
+                .iter()
+                .map(|(key, value)| (*key, value.iter().map(|value| value.cloned()).collect()))
+                .collect(),
+            line_intersections_top_persistent: context
+                .line_intersections_top
+                .persistent
+                .iter()
+                .map(|(key, value)| (*key, value.iter().map(|value| value.cloned()).collect()))
+                .collect(),
+            line_intersections_top_temporary: context
+                .line_intersections_top
+                .temporary
+                .iter()
+                .map(|(key, value)| (*key, value.iter().map(|value| value.cloned()).collect()))
+                .collect(),
+            penalty_marks_bottom_persistent: context
+                .penalty_marks_bottom
+                .persistent
+                .iter()
+                .map(|(key, value)| (*key, value.iter().map(|value| value.cloned()).collect()))
+                .collect(),
+            penalty_marks_bottom_temporary: context
+                .penalty_marks_bottom
+                .temporary
+                .iter()
+                .map(|(key, value)| (*key, value.iter().map(|value| value.cloned()).collect()))
+                .collect(),
+            penalty_marks_top_persistent: context
+                .penalty_marks_top
+                .persistent
+                .iter()
+                .map(|(key, value)| (*key, value.iter().map(|value| value.cloned()).collect()))
+                .collect(),
+            penalty_marks_top_temporary: context
+                .penalty_marks_top
+                .temporary
+                .iter()
+                .map(|(key, value)| (*key, value.iter().map(|value| value.cloned()).collect()))
+                .collect(),
         };
         let buffer =
             serialize(&recorded_context).wrap_err("failed to serialize recorded context")?;
@@ -154,8 +236,20 @@ pub struct RecordedCycleContext {
     pub primary_state: PrimaryState,
     pub robot_to_field: Option<Isometry2<f32>>,
 
+    pub goal_posts_bottom_persistent: BTreeMap<SystemTime, Vec<Option<Vec<GoalPost>>>>,
+    pub goal_posts_bottom_temporary: BTreeMap<SystemTime, Vec<Option<Vec<GoalPost>>>>,
+    pub goal_posts_top_persistent: BTreeMap<SystemTime, Vec<Option<Vec<GoalPost>>>>,
+    pub goal_posts_top_temporary: BTreeMap<SystemTime, Vec<Option<Vec<GoalPost>>>>,
     pub line_data_bottom_persistent: BTreeMap<SystemTime, Vec<Option<LineData>>>,
     pub line_data_bottom_temporary: BTreeMap<SystemTime, Vec<Option<LineData>>>,
     pub line_data_top_persistent: BTreeMap<SystemTime, Vec<Option<LineData>>>,
     pub line_data_top_temporary: BTreeMap<SystemTime, Vec<Option<LineData>>>,
+    pub line_intersections_bottom_persistent: BTreeMap<SystemTime, Vec<Option<LineIntersections>>>,
+    pub line_intersections_bottom_temporary: BTreeMap<SystemTime, Vec<Option<LineIntersections>>>,
+    pub line_intersections_top_persistent: BTreeMap<SystemTime, Vec<Option<LineIntersections>>>,
+    pub line_intersections_top_temporary: BTreeMap<SystemTime, Vec<Option<LineIntersections>>>,
+    pub penalty_marks_bottom_persistent: BTreeMap<SystemTime, Vec<Option<PenaltyMarks>>>,
+    pub penalty_marks_bottom_temporary: BTreeMap<SystemTime, Vec<Option<PenaltyMarks>>>,
+    pub penalty_marks_top_persistent: BTreeMap<SystemTime, Vec<Option<PenaltyMarks>>>,
+    pub penalty_marks_top_temporary: BTreeMap<SystemTime, Vec<Option<PenaltyMarks>>>,
 }
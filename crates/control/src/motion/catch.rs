@@ -0,0 +1,83 @@
+use color_eyre::Result;
+use context_attribute::context;
+use framework::MainOutput;
+use hardware::PathsInterface;
+use motionfile::{MotionFile, MotionInterpolator};
+use serde::{Deserialize, Serialize};
+use types::{
+    condition_input::ConditionInput,
+    cycle_time::CycleTime,
+    joints::Joints,
+    motion_selection::{MotionSafeExits, MotionSelection, MotionType},
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct Catch {
+    squat: MotionInterpolator<Joints<f32>>,
+    stationary_left: MotionInterpolator<Joints<f32>>,
+    stationary_right: MotionInterpolator<Joints<f32>>,
+}
+
+#[context]
+pub struct CreationContext {
+    hardware_interface: HardwareInterface,
+}
+
+#[context]
+pub struct CycleContext {
+    motion_safe_exits: CyclerState<MotionSafeExits, "motion_safe_exits">,
+
+    condition_input: Input<ConditionInput, "condition_input">,
+    cycle_time: Input<CycleTime, "cycle_time">,
+    motion_selection: Input<MotionSelection, "motion_selection">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub squat_catch_positions: MainOutput<Joints<f32>>,
+    pub stationary_catch_left_positions: MainOutput<Joints<f32>>,
+    pub stationary_catch_right_positions: MainOutput<Joints<f32>>,
+}
+
+impl Catch {
+    pub fn new(context: CreationContext<impl PathsInterface>) -> Result<Self> {
+        let paths = context.hardware_interface.get_paths();
+        Ok(Self {
+            squat: MotionFile::from_motion2_path(paths.motions.join("squatCatchFront.motion2"))?
+                .try_into()?,
+            stationary_left: MotionFile::from_motion2_path(
+                paths.motions.join("stationaryCatchLeft.motion2"),
+            )?
+            .try_into()?,
+            stationary_right: MotionFile::from_motion2_path(
+                paths.motions.join("stationaryCatchRight.motion2"),
+            )?
+            .try_into()?,
+        })
+    }
+
+    pub fn cycle(&mut self, context: CycleContext) -> Result<MainOutputs> {
+        for (motion_type, interpolator) in [
+            (MotionType::SquatCatch, &mut self.squat),
+            (MotionType::StationaryCatchLeft, &mut self.stationary_left),
+            (MotionType::StationaryCatchRight, &mut self.stationary_right),
+        ] {
+            if context.motion_selection.current_motion == motion_type {
+                interpolator.advance_by(
+                    context.cycle_time.last_cycle_duration,
+                    context.condition_input,
+                );
+            } else {
+                interpolator.reset();
+            }
+            context.motion_safe_exits[motion_type] = interpolator.is_finished();
+        }
+
+        Ok(MainOutputs {
+            squat_catch_positions: self.squat.value().into(),
+            stationary_catch_left_positions: self.stationary_left.value().into(),
+            stationary_catch_right_positions: self.stationary_right.value().into(),
+        })
+    }
+}
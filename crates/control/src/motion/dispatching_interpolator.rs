@@ -29,11 +29,15 @@ pub struct CycleContext {
     energy_saving_stand: Input<BodyMotorCommands<f32>, "energy_saving_stand_command">,
     jump_left_joints_command: Input<MotorCommands<f32>, "jump_left_joints_command">,
     jump_right_joints_command: Input<MotorCommands<f32>, "jump_right_joints_command">,
+    kick_positions: Input<Joints<f32>, "kick_positions">,
     motion_selection: Input<MotionSelection, "motion_selection">,
     cycle_time: Input<CycleTime, "cycle_time">,
     sit_down_joints_command: Input<MotorCommands<f32>, "sit_down_joints_command">,
+    squat_catch_positions: Input<Joints<f32>, "squat_catch_positions">,
     stand_up_back_positions: Input<Joints<f32>, "stand_up_back_positions">,
     stand_up_front_positions: Input<Joints<f32>, "stand_up_front_positions">,
+    stationary_catch_left_positions: Input<Joints<f32>, "stationary_catch_left_positions">,
+    stationary_catch_right_positions: Input<Joints<f32>, "stationary_catch_right_positions">,
     walk_joints_command: Input<BodyMotorCommands<f32>, "walk_joints_command">,
 
     initial_pose: Parameter<Joints<f32>, "initial_pose">,
@@ -88,14 +92,18 @@ impl DispatchingInterpolator {
                 MotionType::Initial => *context.initial_pose,
                 MotionType::JumpLeft => context.jump_left_joints_command.positions,
                 MotionType::JumpRight => context.jump_right_joints_command.positions,
+                MotionType::Kick => *context.kick_positions,
                 MotionType::Penalized => *context.penalized_pose,
                 MotionType::SitDown => context.sit_down_joints_command.positions,
+                MotionType::SquatCatch => *context.squat_catch_positions,
                 MotionType::Stand => Joints::from_head_and_body(
                     HeadJoints::fill(0.0),
                     context.walk_joints_command.positions,
                 ),
                 MotionType::StandUpBack => *context.stand_up_back_positions,
                 MotionType::StandUpFront => *context.stand_up_front_positions,
+                MotionType::StationaryCatchLeft => *context.stationary_catch_left_positions,
+                MotionType::StationaryCatchRight => *context.stationary_catch_right_positions,
                 MotionType::Unstiff => panic!("Dispatching Unstiff doesn't make sense"),
                 MotionType::Walk => Joints::from_head_and_body(
                     HeadJoints::fill(0.0),
@@ -28,6 +28,7 @@ pub struct CycleContext {
     outer_maximum_pitch: Parameter<f32, "head_motion.outer_maximum_pitch">,
     outer_yaw: Parameter<f32, "head_motion.outer_yaw">,
 
+    calibration_head_joints: Input<HeadJoints<f32>, "calibration_head_joints">,
     look_around: Input<HeadJoints<f32>, "look_around">,
     look_at: Input<HeadJoints<f32>, "look_at">,
     motion_command: Input<MotionCommand, "motion_command">,
@@ -117,6 +118,10 @@ impl HeadMotion {
                 positions: *context.look_at,
                 stiffnesses,
             },
+            Some(HeadMotionCommand::Calibrate) => HeadMotorCommands {
+                positions: *context.calibration_head_joints,
+                stiffnesses,
+            },
             Some(HeadMotionCommand::Unstiff) => HeadMotorCommands {
                 positions: context.sensor_data.positions.head,
                 stiffnesses: HeadJoints::fill(0.0),
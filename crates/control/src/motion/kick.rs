@@ -0,0 +1,96 @@
+use color_eyre::Result;
+use context_attribute::context;
+use framework::MainOutput;
+use hardware::PathsInterface;
+use motionfile::{MotionFile, MotionInterpolator};
+use serde::{Deserialize, Serialize};
+use types::{
+    condition_input::ConditionInput,
+    cycle_time::CycleTime,
+    joints::Joints,
+    motion_command::{MotionCommand, StandingKickVariant},
+    motion_selection::{MotionSafeExits, MotionSelection, MotionType},
+    support_foot::Side,
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct Kick {
+    forward_left: MotionInterpolator<Joints<f32>>,
+    forward_right: MotionInterpolator<Joints<f32>>,
+    outer_left: MotionInterpolator<Joints<f32>>,
+    outer_right: MotionInterpolator<Joints<f32>>,
+    active_kick: (StandingKickVariant, Side),
+}
+
+#[context]
+pub struct CreationContext {
+    hardware_interface: HardwareInterface,
+}
+
+#[context]
+pub struct CycleContext {
+    motion_safe_exits: CyclerState<MotionSafeExits, "motion_safe_exits">,
+
+    condition_input: Input<ConditionInput, "condition_input">,
+    cycle_time: Input<CycleTime, "cycle_time">,
+    motion_command: Input<MotionCommand, "motion_command">,
+    motion_selection: Input<MotionSelection, "motion_selection">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub kick_positions: MainOutput<Joints<f32>>,
+}
+
+impl Kick {
+    pub fn new(context: CreationContext<impl PathsInterface>) -> Result<Self> {
+        let paths = context.hardware_interface.get_paths();
+        Ok(Self {
+            forward_left: MotionFile::from_motion2_path(paths.motions.join("kick_L.motion2"))?
+                .try_into()?,
+            forward_right: MotionFile::from_motion2_path(paths.motions.join("kick_R.motion2"))?
+                .try_into()?,
+            outer_left: MotionFile::from_motion2_path(paths.motions.join("kick_L_outer.motion2"))?
+                .try_into()?,
+            outer_right: MotionFile::from_motion2_path(paths.motions.join("kick_R_outer.motion2"))?
+                .try_into()?,
+            active_kick: (StandingKickVariant::Forward, Side::Left),
+        })
+    }
+
+    pub fn cycle(&mut self, context: CycleContext) -> Result<MainOutputs> {
+        let is_kicking = context.motion_selection.current_motion == MotionType::Kick;
+        // the kick is only switched while not kicking to not jump between keyframes mid-motion
+        if !is_kicking {
+            if let MotionCommand::Kick { variant, side } = context.motion_command {
+                self.active_kick = (*variant, *side);
+            }
+        }
+
+        let interpolator = self.active_interpolator();
+        if is_kicking {
+            interpolator.advance_by(
+                context.cycle_time.last_cycle_duration,
+                context.condition_input,
+            );
+        } else {
+            interpolator.reset();
+        }
+
+        context.motion_safe_exits[MotionType::Kick] = interpolator.is_finished();
+
+        Ok(MainOutputs {
+            kick_positions: interpolator.value().into(),
+        })
+    }
+
+    fn active_interpolator(&mut self) -> &mut MotionInterpolator<Joints<f32>> {
+        match self.active_kick {
+            (StandingKickVariant::Forward, Side::Left) => &mut self.forward_left,
+            (StandingKickVariant::Forward, Side::Right) => &mut self.forward_right,
+            (StandingKickVariant::Outer, Side::Left) => &mut self.outer_left,
+            (StandingKickVariant::Outer, Side::Right) => &mut self.outer_right,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod arms_up_squat;
+pub mod catch;
 pub mod command_sender;
 pub mod condition_input_provider;
 pub mod dispatching_interpolator;
@@ -7,6 +8,7 @@ pub mod fall_protector;
 pub mod head_motion;
 pub mod jump_left;
 pub mod jump_right;
+pub mod kick;
 pub mod look_around;
 pub mod look_at;
 pub mod motion_selector;
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use types::{
     motion_command::{Facing, JumpDirection, MotionCommand},
     motion_selection::{MotionSafeExits, MotionSelection, MotionType},
+    support_foot::Side,
 };
 
 #[derive(Deserialize, Serialize)]
@@ -86,6 +87,7 @@ fn motion_type_from_command(
         },
         MotionCommand::Penalized => MotionType::Penalized,
         MotionCommand::SitDown { .. } => MotionType::SitDown,
+        MotionCommand::SquatCatch => MotionType::SquatCatch,
         MotionCommand::Stand {
             is_energy_saving, ..
         } => {
@@ -99,9 +101,14 @@ fn motion_type_from_command(
             Facing::Down => MotionType::StandUpFront,
             Facing::Up => MotionType::StandUpBack,
         },
+        MotionCommand::StationaryCatch { side } => match side {
+            Side::Left => MotionType::StationaryCatchLeft,
+            Side::Right => MotionType::StationaryCatchRight,
+        },
         MotionCommand::Unstiff => MotionType::Unstiff,
         MotionCommand::Walk { .. } => MotionType::Walk,
         MotionCommand::InWalkKick { .. } => MotionType::Walk,
+        MotionCommand::Kick { .. } => MotionType::Kick,
     }
 }
 
@@ -119,7 +126,26 @@ fn transition_motion(
         (MotionType::StandUpBack, _, MotionType::FallProtection, _) => MotionType::StandUpBack,
         (MotionType::StandUpFront, true, MotionType::StandUpFront, _) => MotionType::Dispatching,
         (MotionType::StandUpBack, true, MotionType::StandUpBack, _) => MotionType::Dispatching,
+        (MotionType::Kick, true, MotionType::Kick, _) => MotionType::Dispatching,
         (_, _, MotionType::FallProtection, _) => MotionType::FallProtection,
+        // the ball would be in the goal before dispatching finishes, so catches start directly
+        // from standing, a walking robot first finishes its step until walking is safe to exit
+        (
+            MotionType::EnergySavingStand | MotionType::Stand,
+            _,
+            MotionType::SquatCatch
+            | MotionType::StationaryCatchLeft
+            | MotionType::StationaryCatchRight,
+            true,
+        )
+        | (
+            MotionType::Walk,
+            true,
+            MotionType::SquatCatch
+            | MotionType::StationaryCatchLeft
+            | MotionType::StationaryCatchRight,
+            true,
+        ) => to,
         (MotionType::Dispatching, true, _, _) => to,
         (MotionType::Stand, _, MotionType::Walk, _) => MotionType::Walk,
         (MotionType::Walk, _, MotionType::Stand, _) => MotionType::Stand,
@@ -127,3 +153,68 @@ fn transition_motion(
         _ => from,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kick_is_dispatched_into_from_stand_and_walk() {
+        assert_eq!(
+            transition_motion(MotionType::Stand, MotionType::Kick, true, true),
+            MotionType::Dispatching
+        );
+        assert_eq!(
+            transition_motion(MotionType::Walk, MotionType::Kick, true, true),
+            MotionType::Dispatching
+        );
+        assert_eq!(
+            transition_motion(MotionType::Dispatching, MotionType::Kick, true, true),
+            MotionType::Kick
+        );
+    }
+
+    #[test]
+    fn walk_finishes_its_step_before_kicking() {
+        assert_eq!(
+            transition_motion(MotionType::Walk, MotionType::Kick, false, true),
+            MotionType::Walk
+        );
+        assert_eq!(
+            transition_motion(MotionType::Dispatching, MotionType::Kick, false, true),
+            MotionType::Dispatching
+        );
+    }
+
+    #[test]
+    fn kick_is_not_interrupted_before_safe_to_exit() {
+        for requested_motion in [MotionType::Kick, MotionType::Stand, MotionType::Walk] {
+            assert_eq!(
+                transition_motion(MotionType::Kick, requested_motion, false, true),
+                MotionType::Kick
+            );
+        }
+    }
+
+    #[test]
+    fn finished_kick_is_dispatched_out_of() {
+        for requested_motion in [MotionType::Kick, MotionType::Stand, MotionType::Walk] {
+            assert_eq!(
+                transition_motion(MotionType::Kick, requested_motion, true, true),
+                MotionType::Dispatching
+            );
+        }
+    }
+
+    #[test]
+    fn falling_interrupts_kick() {
+        assert_eq!(
+            transition_motion(MotionType::Kick, MotionType::FallProtection, false, true),
+            MotionType::FallProtection
+        );
+        assert_eq!(
+            transition_motion(MotionType::Kick, MotionType::Unstiff, false, false),
+            MotionType::Unstiff
+        );
+    }
+}
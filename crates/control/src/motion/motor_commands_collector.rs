@@ -24,11 +24,15 @@ pub struct CycleContext {
     head_joints_command: Input<HeadMotorCommands<f32>, "head_joints_command">,
     jump_left_joints_command: Input<MotorCommands<f32>, "jump_left_joints_command">,
     jump_right_joints_command: Input<MotorCommands<f32>, "jump_right_joints_command">,
+    kick_positions: Input<Joints<f32>, "kick_positions">,
     motion_selection: Input<MotionSelection, "motion_selection">,
     sensor_data: Input<SensorData, "sensor_data">,
     sit_down_joints_command: Input<MotorCommands<f32>, "sit_down_joints_command">,
+    squat_catch_positions: Input<Joints<f32>, "squat_catch_positions">,
     stand_up_back_positions: Input<Joints<f32>, "stand_up_back_positions">,
     stand_up_front_positions: Input<Joints<f32>, "stand_up_front_positions">,
+    stationary_catch_left_positions: Input<Joints<f32>, "stationary_catch_left_positions">,
+    stationary_catch_right_positions: Input<Joints<f32>, "stationary_catch_right_positions">,
     walk_joints_command: Input<BodyMotorCommands<f32>, "walk_joints_command">,
 
     joint_calibration_offsets: Parameter<Joints<f32>, "joint_calibration_offsets">,
@@ -59,6 +63,7 @@ impl MotorCommandCollector {
         let arms_up_squat = context.arms_up_squat_joints_command;
         let jump_left = context.jump_left_joints_command;
         let jump_right = context.jump_right_joints_command;
+        let kick_positions = context.kick_positions;
         let sit_down = context.sit_down_joints_command;
         let stand_up_back_positions = context.stand_up_back_positions;
         let stand_up_front_positions = context.stand_up_front_positions;
@@ -74,14 +79,22 @@ impl MotorCommandCollector {
             MotionType::Initial => (*context.initial_pose, Joints::fill(0.8)),
             MotionType::JumpLeft => (jump_left.positions, jump_left.stiffnesses),
             MotionType::JumpRight => (jump_right.positions, jump_right.stiffnesses),
+            MotionType::Kick => (*kick_positions, Joints::fill(1.0)),
             MotionType::Penalized => (*context.penalized_pose, Joints::fill(0.8)),
             MotionType::SitDown => (sit_down.positions, sit_down.stiffnesses),
+            MotionType::SquatCatch => (*context.squat_catch_positions, Joints::fill(1.0)),
             MotionType::Stand => (
                 Joints::from_head_and_body(head_joints_command.positions, walk.positions),
                 Joints::from_head_and_body(head_joints_command.stiffnesses, walk.stiffnesses),
             ),
             MotionType::StandUpBack => (*stand_up_back_positions, Joints::fill(1.0)),
             MotionType::StandUpFront => (*stand_up_front_positions, Joints::fill(1.0)),
+            MotionType::StationaryCatchLeft => {
+                (*context.stationary_catch_left_positions, Joints::fill(1.0))
+            }
+            MotionType::StationaryCatchRight => {
+                (*context.stationary_catch_right_positions, Joints::fill(1.0))
+            }
             MotionType::Unstiff => (current_positions, Joints::fill(0.0)),
             MotionType::Walk => (
                 Joints::from_head_and_body(head_joints_command.positions, walk.positions),
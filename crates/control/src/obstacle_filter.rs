@@ -7,6 +7,7 @@ use framework::{AdditionalOutput, HistoricInput, MainOutput, PerceptionInput};
 use itertools::{chain, iproduct};
 use nalgebra::{distance, point, Isometry2, Matrix2, Point2};
 use serde::{Deserialize, Serialize};
+use spl_network_messages::Team;
 use types::{
     cycle_time::CycleTime,
     detected_feet::DetectedFeet,
@@ -102,6 +103,7 @@ impl ObstacleFilter {
                 self.update_hypotheses_with_measurement(
                     *network_robot_obstacle,
                     ObstacleKind::Robot,
+                    Team::Hulks,
                     *detection_time,
                     context
                         .obstacle_filter_parameters
@@ -127,6 +129,7 @@ impl ObstacleFilter {
                     self.update_hypotheses_with_measurement(
                         *position,
                         ObstacleKind::Robot,
+                        Team::Uncertain,
                         *detection_time,
                         context
                             .obstacle_filter_parameters
@@ -142,15 +145,16 @@ impl ObstacleFilter {
                 .obstacle_filter_parameters
                 .use_robot_detection_measurements
             {
-                let measured_positions_in_control_cycle = robots_top
+                let measured_robots_in_control_cycle = robots_top
                     .iter()
                     .chain(robots_bottom.iter())
                     .flat_map(|obstacles| obstacles.on_ground.iter());
 
-                for position in measured_positions_in_control_cycle {
+                for robot in measured_robots_in_control_cycle {
                     self.update_hypotheses_with_measurement(
-                        *position,
+                        robot.position,
                         ObstacleKind::Robot,
+                        robot.team,
                         *detection_time,
                         context
                             .obstacle_filter_parameters
@@ -176,6 +180,7 @@ impl ObstacleFilter {
                     self.update_hypotheses_with_measurement(
                         sonar_obstacle.position_in_robot,
                         ObstacleKind::Unknown,
+                        Team::Uncertain,
                         *detection_time,
                         context
                             .obstacle_filter_parameters
@@ -231,6 +236,14 @@ impl ObstacleFilter {
                     kind: hypothesis.obstacle_kind,
                     radius_at_hip_height,
                     radius_at_foot_height,
+                    team: hypothesis.team(
+                        context
+                            .obstacle_filter_parameters
+                            .minimum_team_measurement_count,
+                        context
+                            .obstacle_filter_parameters
+                            .minimum_team_measurement_fraction,
+                    ),
                 }
             })
             .collect::<Vec<_>>();
@@ -273,6 +286,7 @@ impl ObstacleFilter {
         &mut self,
         detected_position: Point2<f32>,
         detected_obstacle_kind: ObstacleKind,
+        detected_team: Team,
         detection_time: SystemTime,
         matching_distance: f32,
         measurement_noise: Matrix2<f32>,
@@ -288,6 +302,7 @@ impl ObstacleFilter {
             self.spawn_hypothesis(
                 detected_position,
                 detected_obstacle_kind,
+                detected_team,
                 detection_time,
                 measurement_noise,
             );
@@ -305,6 +320,7 @@ impl ObstacleFilter {
                 _ => panic!("Unexpected obstacle kind"),
             };
             hypothesis.measurement_count += 1;
+            hypothesis.count_team_measurement(detected_team);
             hypothesis.last_update = detection_time;
         });
     }
@@ -313,11 +329,12 @@ impl ObstacleFilter {
         &mut self,
         detected_position: Point2<f32>,
         obstacle_kind: ObstacleKind,
+        team: Team,
         detection_time: SystemTime,
         initial_covariance: Matrix2<f32>,
     ) {
         let initial_state = detected_position.coords;
-        let new_hypothesis = Hypothesis {
+        let mut new_hypothesis = Hypothesis {
             state: MultivariateNormalDistribution {
                 mean: initial_state,
                 covariance: initial_covariance,
@@ -325,7 +342,10 @@ impl ObstacleFilter {
             obstacle_kind,
             measurement_count: 1,
             last_update: detection_time,
+            hulks_measurement_count: 0,
+            opponent_measurement_count: 0,
         };
+        new_hypothesis.count_team_measurement(team);
         self.hypotheses.push(new_hypothesis);
     }
 
@@ -361,6 +381,10 @@ impl ObstacleFilter {
                         ObstacleKind::Unknown => hypothesis.obstacle_kind,
                         _ => panic!("Unexpected obstacle kind"),
                     };
+                    existing_hypothesis.hulks_measurement_count +=
+                        hypothesis.hulks_measurement_count;
+                    existing_hypothesis.opponent_measurement_count +=
+                        hypothesis.opponent_measurement_count;
                 }
                 None => deduplicated_hypotheses.push(hypothesis),
             }
@@ -2,12 +2,16 @@ use color_eyre::{eyre::WrapErr, Result};
 use context_attribute::context;
 use framework::{MainOutput, PerceptionInput};
 use hardware::NetworkInterface;
-use nalgebra::{Isometry2, Point2, Vector2};
+use nalgebra::{distance, Isometry2, Matrix2, Point2};
 use serde::{Deserialize, Serialize};
 use spl_network_messages::{
-    GameControllerReturnMessage, GamePhase, HulkMessage, Penalty, PlayerNumber, Team,
+    GameControllerReturnMessage, GamePhase, HulkMessage, NetworkBallPosition, Penalty,
+    PlayerNumber, Team,
+};
+use std::{
+    iter::{once, repeat},
+    time::{Duration, SystemTime},
 };
-use std::time::{Duration, SystemTime};
 use types::{
     ball_position::BallPosition,
     cycle_time::CycleTime,
@@ -16,13 +20,14 @@ use types::{
     game_controller_state::GameControllerState,
     initial_pose::InitialPose,
     messages::{IncomingMessage, OutgoingMessage},
-    parameters::SplNetworkParameters,
+    parameters::{RoleAssignmentMode, SplNetworkParameters, UtilityRoleAssignmentParameters},
     players::Players,
     primary_state::PrimaryState,
     roles::Role,
+    world_state::TeammateState,
 };
 
-use crate::localization::generate_initial_pose;
+use crate::{hungarian::minimum_cost_assignment, localization::generate_initial_pose};
 
 #[derive(Deserialize, Serialize)]
 pub struct RoleAssignment {
@@ -33,6 +38,8 @@ pub struct RoleAssignment {
     role_initialized: bool,
     team_ball: Option<BallPosition>,
     last_time_keeper_penalized: Option<SystemTime>,
+    teammates: Players<Option<TeammateState>>,
+    last_utility_assignment: Players<Option<Role>>,
 }
 
 #[context]
@@ -46,13 +53,16 @@ pub struct CycleContext {
     primary_state: Input<PrimaryState, "primary_state">,
     robot_to_field: Input<Option<Isometry2<f32>>, "robot_to_field?">,
     cycle_time: Input<CycleTime, "cycle_time">,
-    network_message: PerceptionInput<IncomingMessage, "SplNetwork", "message">,
+    network_message: PerceptionInput<Option<IncomingMessage>, "SplNetwork", "message?">,
     time_to_reach_kick_position: CyclerState<Duration, "time_to_reach_kick_position">,
+    walk_target: CyclerState<Option<Point2<f32>>, "walk_target">,
 
     field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
     forced_role: Parameter<Option<Role>, "role_assignment.forced_role?">,
     keeper_replacementkeeper_switch_time:
         Parameter<Duration, "role_assignment.keeper_replacementkeeper_switch_time">,
+    mode: Parameter<RoleAssignmentMode, "role_assignment.mode">,
+    utility: Parameter<UtilityRoleAssignmentParameters, "role_assignment.utility">,
     initial_poses: Parameter<Players<InitialPose>, "localization.initial_poses">,
     optional_roles: Parameter<Vec<Role>, "behavior.optional_roles">,
     player_number: Parameter<PlayerNumber, "player_number">,
@@ -67,6 +77,7 @@ pub struct MainOutputs {
     pub team_ball: MainOutput<Option<BallPosition>>,
     pub network_robot_obstacles: MainOutput<Vec<Point2<f32>>>,
     pub role: MainOutput<Role>,
+    pub teammates: MainOutput<Vec<TeammateState>>,
 }
 
 impl RoleAssignment {
@@ -79,6 +90,8 @@ impl RoleAssignment {
             role_initialized: false,
             team_ball: None,
             last_time_keeper_penalized: None,
+            teammates: Default::default(),
+            last_utility_assignment: Default::default(),
         })
     }
 
@@ -173,7 +186,7 @@ impl RoleAssignment {
                         player_number: *context.player_number,
                         fallen: matches!(context.fall_state, FallState::Fallen { .. }),
                         robot_to_field,
-                        ball_position: seen_ball_to_network_ball_position(
+                        ball_position: seen_ball_to_game_controller_ball_position(
                             context.ball_position,
                             cycle_start_time,
                         ),
@@ -184,91 +197,179 @@ impl RoleAssignment {
 
         let mut team_ball = self.team_ball;
 
-        if spl_striker_message_timeout {
-            match role {
-                Role::Keeper => {
-                    team_ball = None;
-                }
-                Role::ReplacementKeeper => {
-                    team_ball = None;
-                }
-                Role::Striker => {
-                    send_spl_striker_message = true;
-                    team_ball = None;
-                    role = Role::Loser;
-                }
-                _ => {
-                    send_spl_striker_message = false;
-                    team_ball = None;
-                    role = Role::Searcher
-                }
-            }
-        }
-
-        let mut network_robot_obstacles = vec![];
-        let mut spl_messages = context
+        let spl_messages: Vec<_> = context
             .network_message
             .persistent
             .values()
             .flatten()
+            .flatten()
             .filter_map(|message| match message {
-                IncomingMessage::GameController(_) => None,
+                IncomingMessage::GameController(_) | IncomingMessage::IncompatibleSpl(_) => None,
                 IncomingMessage::Spl(message) => Some(message),
             })
-            .peekable();
-        if spl_messages.peek().is_none() {
-            (role, send_spl_striker_message, team_ball) = process_role_state_machine(
-                role,
-                robot_to_field,
-                context.ball_position,
-                primary_state,
-                None,
-                Some(*context.time_to_reach_kick_position),
-                send_spl_striker_message,
-                team_ball,
-                cycle_start_time,
-                context.game_controller_state,
-                *context.player_number,
-                context.spl_network.striker_trusts_team_ball,
-                context.optional_roles,
-            );
-        } else {
-            for spl_message in spl_messages {
-                self.last_received_spl_striker_message = Some(cycle_start_time);
-                let sender_position =
-                    (robot_to_field.inverse() * spl_message.robot_to_field) * Point2::origin();
-                if spl_message.player_number != *context.player_number {
-                    network_robot_obstacles.push(sender_position);
+            .collect();
+        let network_robot_obstacles: Vec<_> = spl_messages
+            .iter()
+            .filter(|spl_message| spl_message.player_number != *context.player_number)
+            .map(|spl_message| {
+                (robot_to_field.inverse() * spl_message.robot_to_field) * Point2::origin()
+            })
+            .collect();
+
+        for spl_message in &spl_messages {
+            if spl_message.player_number != *context.player_number {
+                self.teammates[spl_message.player_number] = Some(TeammateState {
+                    player_number: spl_message.player_number,
+                    role: spl_message.role,
+                    robot_to_field: spl_message.robot_to_field,
+                    walk_target: spl_message.walk_target,
+                    fallen: spl_message.fallen,
+                    time_to_reach_kick_position: spl_message.time_to_reach_kick_position,
+                    last_update: cycle_start_time,
+                });
+            }
+        }
+        self.teammates[*context.player_number] = Some(TeammateState {
+            player_number: *context.player_number,
+            role: self.role,
+            robot_to_field,
+            walk_target: *context.walk_target,
+            fallen: matches!(context.fall_state, FallState::Fallen { .. }),
+            time_to_reach_kick_position: Some(*context.time_to_reach_kick_position),
+            last_update: cycle_start_time,
+        });
+
+        match context.mode {
+            RoleAssignmentMode::StateMachine => {
+                if spl_striker_message_timeout {
+                    match role {
+                        Role::Keeper => {
+                            team_ball = None;
+                        }
+                        Role::ReplacementKeeper => {
+                            team_ball = None;
+                        }
+                        Role::Striker => {
+                            send_spl_striker_message = true;
+                            team_ball = None;
+                            role = Role::Loser;
+                        }
+                        _ => {
+                            send_spl_striker_message = false;
+                            team_ball = None;
+                            role = Role::Searcher
+                        }
+                    }
                 }
-                (role, send_spl_striker_message, team_ball) = process_role_state_machine(
-                    role,
-                    robot_to_field,
+
+                if spl_messages.is_empty() {
+                    (role, send_spl_striker_message, team_ball) = process_role_state_machine(
+                        role,
+                        robot_to_field,
+                        context.ball_position,
+                        primary_state,
+                        None,
+                        Some(*context.time_to_reach_kick_position),
+                        send_spl_striker_message,
+                        team_ball,
+                        cycle_start_time,
+                        context.game_controller_state,
+                        *context.player_number,
+                        context.spl_network.striker_trusts_team_ball,
+                        context.optional_roles,
+                    );
+                } else {
+                    for spl_message in spl_messages {
+                        self.last_received_spl_striker_message = Some(cycle_start_time);
+                        (role, send_spl_striker_message, team_ball) = process_role_state_machine(
+                            role,
+                            robot_to_field,
+                            context.ball_position,
+                            primary_state,
+                            Some(spl_message),
+                            Some(*context.time_to_reach_kick_position),
+                            send_spl_striker_message,
+                            team_ball,
+                            cycle_start_time,
+                            context.game_controller_state,
+                            *context.player_number,
+                            context.spl_network.striker_trusts_team_ball,
+                            context.optional_roles,
+                        );
+                    }
+                }
+
+                if let Some(last_time_keeper_penalized) = self.last_time_keeper_penalized {
+                    let deny_replacement_keeper_switch = cycle_start_time
+                        .duration_since(last_time_keeper_penalized)
+                        .expect("Keeper was penalized in the Future")
+                        < *context.keeper_replacementkeeper_switch_time;
+                    if self.role == Role::ReplacementKeeper
+                        && !send_spl_striker_message
+                        && deny_replacement_keeper_switch
+                    {
+                        role = Role::ReplacementKeeper;
+                    }
+                }
+            }
+            RoleAssignmentMode::Utility => {
+                let own_ball = team_ball_from_seen_ball(
                     context.ball_position,
-                    primary_state,
-                    Some(spl_message),
-                    Some(*context.time_to_reach_kick_position),
-                    send_spl_striker_message,
-                    team_ball,
+                    robot_to_field,
                     cycle_start_time,
-                    context.game_controller_state,
-                    *context.player_number,
-                    context.spl_network.striker_trusts_team_ball,
-                    context.optional_roles,
                 );
+                let received_balls = spl_messages
+                    .iter()
+                    .filter(|spl_message| spl_message.player_number != *context.player_number)
+                    .filter_map(|spl_message| {
+                        team_ball_from_spl_message(cycle_start_time, spl_message)
+                    });
+                team_ball = own_ball
+                    .into_iter()
+                    .chain(received_balls)
+                    .chain(team_ball)
+                    .filter(|ball| {
+                        cycle_start_time
+                            .duration_since(ball.last_seen)
+                            .unwrap_or_default()
+                            < context.spl_network.striker_trusts_team_ball
+                    })
+                    .max_by_key(|ball| ball.last_seen);
+
+                send_spl_striker_message = match self.last_transmitted_spl_striker_message {
+                    Some(last_transmitted_spl_striker_message) => {
+                        cycle_start_time.duration_since(last_transmitted_spl_striker_message)?
+                            > context.utility.message_interval
+                    }
+                    None => true,
+                };
+
+                if let Some(penalty_shootout_role) =
+                    penalty_shootout_role(context.game_controller_state)
+                {
+                    role = penalty_shootout_role;
+                } else if primary_state == PrimaryState::Playing {
+                    let assignment = assign_roles_by_utility(
+                        &self.teammates,
+                        context
+                            .game_controller_state
+                            .map(|game_controller_state| &game_controller_state.penalties),
+                        &self.last_utility_assignment,
+                        team_ball.as_ref(),
+                        context.optional_roles,
+                        cycle_start_time,
+                        context.utility,
+                    );
+                    role = assignment[*context.player_number].unwrap_or(role);
+                    self.last_utility_assignment = assignment;
+                }
             }
         }
 
-        if let Some(last_time_keeper_penalized) = self.last_time_keeper_penalized {
-            let deny_replacement_keeper_switch = cycle_start_time
-                .duration_since(last_time_keeper_penalized)
-                .expect("Keeper was penalized in the Future")
-                < *context.keeper_replacementkeeper_switch_time;
-            if self.role == Role::ReplacementKeeper
-                && !send_spl_striker_message
-                && deny_replacement_keeper_switch
-            {
-                role = Role::ReplacementKeeper;
-            }
+        if let Some(forced_role) = context.forced_role {
+            self.role = *forced_role;
+        } else {
+            self.role = role;
         }
 
         if send_spl_striker_message
@@ -297,7 +398,9 @@ impl RoleAssignment {
                         .write_to_network(OutgoingMessage::Spl(HulkMessage {
                             player_number: *context.player_number,
                             fallen: matches!(context.fall_state, FallState::Fallen { .. }),
+                            role: self.role,
                             robot_to_field,
+                            walk_target: *context.walk_target,
                             ball_position,
                             time_to_reach_kick_position: Some(*context.time_to_reach_kick_position),
                         }))?;
@@ -305,11 +408,6 @@ impl RoleAssignment {
             }
         }
 
-        if let Some(forced_role) = context.forced_role {
-            self.role = *forced_role;
-        } else {
-            self.role = role;
-        }
         self.team_ball = team_ball;
 
         if let Some(game_controller_state) = context.game_controller_state {
@@ -322,6 +420,19 @@ impl RoleAssignment {
             role: self.role.into(),
             team_ball: self.team_ball.into(),
             network_robot_obstacles: network_robot_obstacles.into(),
+            teammates: self
+                .teammates
+                .iter()
+                .filter(|(player_number, _)| *player_number != *context.player_number)
+                .filter_map(|(_, teammate)| *teammate)
+                .filter(|teammate| {
+                    cycle_start_time
+                        .duration_since(teammate.last_update)
+                        .unwrap_or_default()
+                        <= context.utility.teammate_timeout
+                })
+                .collect::<Vec<_>>()
+                .into(),
         })
     }
 }
@@ -342,16 +453,8 @@ fn process_role_state_machine(
     striker_trusts_team_ball: Duration,
     optional_roles: &[Role],
 ) -> (Role, bool, Option<BallPosition>) {
-    if let Some(game_controller_state) = game_controller_state {
-        match game_controller_state.game_phase {
-            GamePhase::PenaltyShootout {
-                kicking_team: Team::Hulks,
-            } => return (Role::Striker, false, None),
-            GamePhase::PenaltyShootout {
-                kicking_team: Team::Opponent,
-            } => return (Role::Keeper, false, None),
-            _ => {}
-        }
+    if let Some(role) = penalty_shootout_role(game_controller_state) {
+        return (role, false, None);
     }
 
     if primary_state != PrimaryState::Playing {
@@ -578,6 +681,18 @@ fn process_role_state_machine(
     }
 }
 
+fn penalty_shootout_role(game_controller_state: Option<&GameControllerState>) -> Option<Role> {
+    match game_controller_state?.game_phase {
+        GamePhase::PenaltyShootout {
+            kicking_team: Team::Hulks,
+        } => Some(Role::Striker),
+        GamePhase::PenaltyShootout {
+            kicking_team: Team::Opponent,
+        } => Some(Role::Keeper),
+        _ => None,
+    }
+}
+
 fn decide_if_claiming_striker_or_other_role(
     spl_message: &HulkMessage,
     time_to_reach_kick_position: Option<Duration>,
@@ -609,6 +724,18 @@ fn decide_if_claiming_striker_or_other_role(
 fn seen_ball_to_network_ball_position(
     ball: Option<&BallPosition>,
     cycle_start_time: SystemTime,
+) -> Option<NetworkBallPosition> {
+    ball.map(|ball| NetworkBallPosition {
+        relative_position: ball.position,
+        velocity: ball.velocity,
+        covariance: ball.covariance,
+        age: cycle_start_time.duration_since(ball.last_seen).unwrap(),
+    })
+}
+
+fn seen_ball_to_game_controller_ball_position(
+    ball: Option<&BallPosition>,
+    cycle_start_time: SystemTime,
 ) -> Option<spl_network_messages::BallPosition> {
     ball.map(|ball| spl_network_messages::BallPosition {
         age: cycle_start_time.duration_since(ball.last_seen).unwrap(),
@@ -620,12 +747,14 @@ fn team_ball_to_network_ball_position(
     team_ball: Option<BallPosition>,
     robot_to_field: Isometry2<f32>,
     cycle_start_time: SystemTime,
-) -> Option<spl_network_messages::BallPosition> {
-    team_ball.map(|team_ball| spl_network_messages::BallPosition {
+) -> Option<NetworkBallPosition> {
+    team_ball.map(|team_ball| NetworkBallPosition {
+        relative_position: robot_to_field.inverse() * team_ball.position,
+        velocity: robot_to_field.inverse() * team_ball.velocity,
+        covariance: rotate_covariance(team_ball.covariance, robot_to_field.inverse()),
         age: cycle_start_time
             .duration_since(team_ball.last_seen)
             .unwrap(),
-        relative_position: robot_to_field.inverse() * team_ball.position,
     })
 }
 
@@ -638,7 +767,8 @@ fn team_ball_from_spl_message(
         .as_ref()
         .map(|ball_position| BallPosition {
             position: spl_message.robot_to_field * ball_position.relative_position,
-            velocity: Vector2::zeros(),
+            velocity: spl_message.robot_to_field * ball_position.velocity,
+            covariance: rotate_covariance(ball_position.covariance, spl_message.robot_to_field),
             last_seen: cycle_start_time - ball_position.age,
         })
 }
@@ -650,11 +780,18 @@ fn team_ball_from_seen_ball(
 ) -> Option<BallPosition> {
     ball.as_ref().map(|ball| BallPosition {
         position: (current_pose * ball.position),
-        velocity: Vector2::zeros(),
+        velocity: current_pose * ball.velocity,
+        covariance: rotate_covariance(ball.covariance, current_pose),
         last_seen: cycle_start_time,
     })
 }
 
+/// Expresses a position covariance in the coordinate frame `transform` maps into
+fn rotate_covariance(covariance: Matrix2<f32>, transform: Isometry2<f32>) -> Matrix2<f32> {
+    let rotation = transform.rotation.to_rotation_matrix().into_inner();
+    rotation * covariance * rotation.transpose()
+}
+
 fn generate_role(
     own_player_number: PlayerNumber,
     game_controller_state: Option<&GameControllerState>,
@@ -761,3 +898,107 @@ fn assign_keeper_or_replacement_keeper(
 
     unassigned_robots
 }
+
+/// Assigns the keeper to player one and all other roles to the remaining available teammates
+/// such that the sum of their costs is minimal. Roles are filled in the order striker,
+/// replacement keeper (if player one is unavailable), optional roles and searchers.
+fn assign_roles_by_utility(
+    teammates: &Players<Option<TeammateState>>,
+    penalties: Option<&Players<Option<Penalty>>>,
+    last_assignment: &Players<Option<Role>>,
+    team_ball: Option<&BallPosition>,
+    optional_roles: &[Role],
+    cycle_start_time: SystemTime,
+    parameters: &UtilityRoleAssignmentParameters,
+) -> Players<Option<Role>> {
+    let mut assignment = Players::<Option<Role>>::default();
+    let mut available_players: Vec<_> = teammates
+        .iter()
+        .filter_map(|(player_number, teammate)| Some((player_number, (*teammate)?)))
+        .filter(|(player_number, teammate)| {
+            let is_penalized =
+                penalties.is_some_and(|penalties| penalties[*player_number].is_some());
+            let is_timed_out = cycle_start_time
+                .duration_since(teammate.last_update)
+                .unwrap_or_default()
+                > parameters.teammate_timeout;
+            !is_penalized && !is_timed_out
+        })
+        .collect();
+
+    let keeper_index = available_players
+        .iter()
+        .position(|(player_number, _)| *player_number == PlayerNumber::One);
+    let keeper_role = match keeper_index {
+        Some(index) => {
+            available_players.remove(index);
+            assignment[PlayerNumber::One] = Some(Role::Keeper);
+            None
+        }
+        None => Some(Role::ReplacementKeeper),
+    };
+    let roles: Vec<_> = once(Role::Striker)
+        .chain(keeper_role)
+        .chain(optional_roles.iter().copied())
+        .chain(repeat(Role::Searcher))
+        .take(available_players.len())
+        .collect();
+
+    let costs: Vec<Vec<_>> = available_players
+        .iter()
+        .map(|(player_number, teammate)| {
+            roles
+                .iter()
+                .map(|&role| {
+                    role_cost(
+                        teammate,
+                        role,
+                        last_assignment[*player_number],
+                        team_ball,
+                        parameters,
+                    )
+                })
+                .collect()
+        })
+        .collect();
+    for ((player_number, _), role_index) in available_players
+        .iter()
+        .zip(minimum_cost_assignment(&costs))
+    {
+        assignment[*player_number] = Some(roles[role_index]);
+    }
+    assignment
+}
+
+/// Estimated time in seconds until `teammate` is able to fulfill `role`
+fn role_cost(
+    teammate: &TeammateState,
+    role: Role,
+    last_role: Option<Role>,
+    team_ball: Option<&BallPosition>,
+    parameters: &UtilityRoleAssignmentParameters,
+) -> f32 {
+    let position = Point2::from(teammate.robot_to_field.translation.vector);
+    let time_to_reach = match (role, teammate.time_to_reach_kick_position, team_ball) {
+        (Role::Striker, Some(time_to_reach_kick_position), _) => {
+            time_to_reach_kick_position.as_secs_f32()
+        }
+        (Role::Striker, None, Some(team_ball)) => {
+            distance(&position, &team_ball.position) / parameters.walking_speed
+        }
+        (role, _, _) => {
+            distance(&position, &parameters.anchors.role(role)) / parameters.walking_speed
+        }
+    };
+    let fallen_cost = if teammate.fallen {
+        parameters.fallen_cost
+    } else {
+        0.0
+    };
+    let role_keeping_bonus = if last_role == Some(role) || teammate.role == role {
+        parameters.role_keeping_bonus
+    } else {
+        0.0
+    };
+    time_to_reach.min(parameters.maximum_cost) + fallen_cost - role_keeping_bonus
+}
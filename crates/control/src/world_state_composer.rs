@@ -13,7 +13,7 @@ use types::{
     primary_state::PrimaryState,
     roles::Role,
     rule_obstacles::RuleObstacle,
-    world_state::{BallState, RobotState, WorldState},
+    world_state::{BallState, RobotState, TeammateState, WorldState},
 };
 
 #[derive(Deserialize, Serialize)]
@@ -41,6 +41,7 @@ pub struct CycleContext {
     primary_state: Input<PrimaryState, "primary_state">,
     role: Input<Role, "role">,
     position_of_interest: Input<Point2<f32>, "position_of_interest">,
+    teammates: Input<Vec<TeammateState>, "teammates">,
 }
 
 #[context]
@@ -75,6 +76,7 @@ impl WorldStateComposer {
             kick_decisions: context.kick_decisions.cloned(),
             instant_kick_decisions: context.instant_kick_decisions.cloned(),
             game_controller_state: context.game_controller_state.copied(),
+            teammates: context.teammates.clone(),
         };
 
         Ok(MainOutputs {
@@ -16,6 +16,6 @@ pub use historic_input::HistoricInput;
 pub use main_output::MainOutput;
 pub use multiple_buffer::{multiple_buffer_with_slots, Reader, ReaderGuard, Writer, WriterGuard};
 pub use panic::deserialize_not_implemented;
-pub use parameters::Parameters;
+pub use parameters::{CommunicationAddresses, ConnectionRole, Parameters};
 pub use perception_databases::PerceptionDatabases;
 pub use perception_input::PerceptionInput;
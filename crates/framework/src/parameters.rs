@@ -1,11 +1,58 @@
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::PathBuf,
+};
 
 use serde::Deserialize;
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct Parameters {
-    pub communication_addresses: Option<String>,
+    pub communication_addresses: Option<CommunicationAddresses>,
+    /// File the communication server appends parameter changes and injections to
+    #[serde(default)]
+    pub communication_audit_log: Option<PathBuf>,
     pub cycler_instances_to_be_recorded: HashSet<String>,
     pub hardware_parameters: PathBuf,
     pub parameters_directory: PathBuf,
 }
+
+/// Addresses the communication server listens on, either a single address whose connections are
+/// operators or a map from addresses to the role of connections accepted on them
+///
+/// Clients are not authenticated, the role only depends on the address a client connects to.
+/// Restricting who may reach the operator address is left to network access control, e.g. binding
+/// it to a private interface or firewalling its port.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum CommunicationAddresses {
+    Operator(String),
+    WithRoles(BTreeMap<String, ConnectionRole>),
+}
+
+impl CommunicationAddresses {
+    pub fn listeners(&self) -> Vec<(String, ConnectionRole)> {
+        match self {
+            CommunicationAddresses::Operator(address) => {
+                vec![(address.clone(), ConnectionRole::Operator)]
+            }
+            CommunicationAddresses::WithRoles(addresses) => addresses
+                .iter()
+                .map(|(address, role)| (address.clone(), *role))
+                .collect(),
+        }
+    }
+}
+
+impl From<String> for CommunicationAddresses {
+    fn from(address: String) -> Self {
+        CommunicationAddresses::Operator(address)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub enum ConnectionRole {
+    /// May only read outputs and parameters, e.g. spectators at events
+    Observer,
+    /// May additionally inject outputs, update parameters and write them to disk
+    Operator,
+}
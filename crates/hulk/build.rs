@@ -21,9 +21,12 @@ fn main() -> Result<()> {
                     "vision::feet_detection",
                     "vision::field_border_detection",
                     "vision::field_color_detection",
+                    "vision::goal_post_detection",
                     "vision::image_segmenter",
                     "vision::limb_projector",
                     "vision::line_detection",
+                    "vision::line_intersection_detection",
+                    "vision::penalty_mark_detection",
                     "vision::perspective_grid_candidates_provider",
                     "vision::robot_detection",
                     "vision::segment_filter",
@@ -40,6 +43,7 @@ fn main() -> Result<()> {
                     "control::ball_state_composer",
                     "control::behavior::node",
                     "control::button_filter",
+                    "control::camera_calibration",
                     "control::camera_matrix_calculator",
                     "control::center_of_mass_provider",
                     "control::fall_state_estimation",
@@ -53,6 +57,7 @@ fn main() -> Result<()> {
                     "control::localization",
                     "control::localization_recorder",
                     "control::motion::arms_up_squat",
+                    "control::motion::catch",
                     "control::motion::condition_input_provider",
                     "control::motion::dispatching_interpolator",
                     "control::motion::energy_saving_stand",
@@ -62,6 +67,7 @@ fn main() -> Result<()> {
                     "control::motion::command_sender",
                     "control::motion::jump_left",
                     "control::motion::jump_right",
+                    "control::motion::kick",
                     "control::motion::look_around",
                     "control::motion::look_at",
                     "control::motion::motion_selector",
@@ -0,0 +1,116 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use color_eyre::{eyre::bail, Result};
+use hardware::{
+    ActuatorInterface, CameraInterface, IdInterface, MicrophoneInterface, NetworkInterface,
+    PathsInterface, RecordingInterface, SensorInterface, SpeakerInterface, TimeInterface,
+};
+use parking_lot::Mutex;
+use serde::Deserialize;
+use types::{
+    audio::SpeakerRequest,
+    camera_position::CameraPosition,
+    hardware::{Ids, Paths},
+    joints::Joints,
+    led::Leds,
+    messages::{IncomingMessage, OutgoingMessage},
+    samples::Samples,
+    sensor_data::SensorData,
+    ycbcr422_image::YCbCr422Image,
+};
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Parameters {
+    pub paths: Paths,
+}
+
+pub struct HardwareInterface {
+    ids: Ids,
+    paths: Paths,
+    now: Mutex<SystemTime>,
+}
+
+impl HardwareInterface {
+    pub fn new(ids: Ids, parameters: Parameters) -> Self {
+        Self {
+            ids,
+            paths: parameters.paths,
+            now: Mutex::new(UNIX_EPOCH),
+        }
+    }
+
+    pub fn set_now(&self, now: SystemTime) {
+        *self.now.lock() = now;
+    }
+}
+
+impl ActuatorInterface for HardwareInterface {
+    fn write_to_actuators(
+        &self,
+        _positions: Joints<f32>,
+        _stiffnesses: Joints<f32>,
+        _leds: Leds,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl CameraInterface for HardwareInterface {
+    fn read_from_camera(&self, _camera_position: CameraPosition) -> Result<YCbCr422Image> {
+        bail!("cameras are not available while replaying")
+    }
+}
+
+impl IdInterface for HardwareInterface {
+    fn get_ids(&self) -> Ids {
+        self.ids.clone()
+    }
+}
+
+impl MicrophoneInterface for HardwareInterface {
+    fn read_from_microphones(&self) -> Result<Samples> {
+        bail!("microphones are not available while replaying")
+    }
+}
+
+impl NetworkInterface for HardwareInterface {
+    fn read_from_network(&self) -> Result<IncomingMessage> {
+        bail!("network is not available while replaying")
+    }
+
+    fn write_to_network(&self, _message: OutgoingMessage) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl PathsInterface for HardwareInterface {
+    fn get_paths(&self) -> Paths {
+        self.paths.clone()
+    }
+}
+
+impl RecordingInterface for HardwareInterface {
+    fn should_record(&self) -> bool {
+        false
+    }
+
+    fn set_whether_to_record(&self, _enable: bool) {}
+}
+
+impl SensorInterface for HardwareInterface {
+    fn read_from_sensors(&self) -> Result<SensorData> {
+        bail!("sensors are not available while replaying")
+    }
+}
+
+impl SpeakerInterface for HardwareInterface {
+    fn write_to_speakers(&self, _request: SpeakerRequest) {}
+}
+
+impl TimeInterface for HardwareInterface {
+    fn get_now(&self) -> SystemTime {
+        *self.now.lock()
+    }
+}
+
+impl hulk::HardwareInterface for HardwareInterface {}
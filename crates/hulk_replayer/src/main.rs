@@ -0,0 +1,291 @@
+#![recursion_limit = "256"]
+use std::{
+    fs::File,
+    io::{stdin, stdout, BufRead},
+    ops::Range,
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+
+use clap::Parser;
+use color_eyre::{
+    eyre::{bail, WrapErr},
+    install, Result,
+};
+use framework::{CommunicationAddresses, Parameters as FrameworkParameters};
+use hardware_interface::{HardwareInterface, Parameters as HardwareParameters};
+use hulk::replayer::Replayer;
+use log::{error, info};
+use recording::Recording;
+use serde_json::from_reader;
+use tokio_util::sync::CancellationToken;
+use types::hardware::Ids;
+
+mod hardware_interface;
+mod recording;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct CommandlineArguments {
+    /// Recordings written by the robot, e.g. `logs/Control.1681234567.bincode`
+    #[clap(required = true)]
+    recordings: Vec<PathBuf>,
+    #[clap(long, default_value = "etc/parameters/framework.json")]
+    framework_parameters: PathBuf,
+    /// Body ID of the recorded robot, used to load its parameters
+    #[clap(long, default_value = "replayer")]
+    body_id: String,
+    /// Head ID of the recorded robot, used to load its parameters
+    #[clap(long, default_value = "replayer")]
+    head_id: String,
+}
+
+pub fn setup_logger() -> Result<(), fern::InitError> {
+    fern::Dispatch::new()
+        .format(|out, message, record| {
+            out.finish(format_args!(
+                "{}  {:<18}  {:>5}  {}",
+                chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+                record.target(),
+                record.level(),
+                message
+            ))
+        })
+        .level(log::LevelFilter::Info)
+        .chain(stdout())
+        .apply()?;
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    setup_logger()?;
+    install()?;
+    let arguments = CommandlineArguments::parse();
+
+    let file = File::open(arguments.framework_parameters)
+        .wrap_err("failed to open framework parameters")?;
+    let framework_parameters: FrameworkParameters =
+        from_reader(file).wrap_err("failed to parse framework parameters")?;
+
+    let file = File::open(framework_parameters.hardware_parameters)
+        .wrap_err("failed to open hardware parameters")?;
+    let hardware_parameters: HardwareParameters =
+        from_reader(file).wrap_err("failed to parse hardware parameters")?;
+
+    let ids = Ids {
+        body_id: arguments.body_id,
+        head_id: arguments.head_id,
+    };
+    let hardware_interface = Arc::new(HardwareInterface::new(ids.clone(), hardware_parameters));
+
+    let recording = Recording::open(&arguments.recordings).wrap_err("failed to open recordings")?;
+    if recording.frames().is_empty() {
+        bail!("recordings do not contain any frames");
+    }
+
+    let replayer_arguments = ReplayerArguments {
+        hardware_interface,
+        communication_addresses: framework_parameters.communication_addresses,
+        parameters_directory: framework_parameters.parameters_directory,
+        ids,
+    };
+    let mut player = Player {
+        replayer: Some(replayer_arguments.create()?),
+        replayer_arguments,
+        recording,
+        position: 0,
+    };
+    let result = player.run_interactively();
+
+    if let Some(replayer) = player.replayer.take() {
+        replayer.stop()?;
+    }
+    result
+}
+
+struct ReplayerArguments {
+    hardware_interface: Arc<HardwareInterface>,
+    communication_addresses: Option<CommunicationAddresses>,
+    parameters_directory: PathBuf,
+    ids: Ids,
+}
+
+impl ReplayerArguments {
+    fn create(&self) -> Result<RunningReplayer> {
+        let keep_running = CancellationToken::new();
+        let replayer = Replayer::new(
+            self.hardware_interface.clone(),
+            self.communication_addresses.clone(),
+            self.parameters_directory.clone(),
+            self.ids.body_id.clone(),
+            self.ids.head_id.clone(),
+            keep_running.clone(),
+        )
+        .wrap_err("failed to create replayer")?;
+        Ok(RunningReplayer {
+            replayer,
+            keep_running,
+        })
+    }
+}
+
+struct RunningReplayer {
+    replayer: Replayer<HardwareInterface>,
+    keep_running: CancellationToken,
+}
+
+impl RunningReplayer {
+    fn stop(self) -> Result<()> {
+        self.keep_running.cancel();
+        self.replayer.join()
+    }
+}
+
+struct Player {
+    /// Only `None` while the replayer is re-created
+    replayer: Option<RunningReplayer>,
+    replayer_arguments: ReplayerArguments,
+    recording: Recording,
+    /// Number of frames replayed so far, i.e. the index of the next frame
+    position: usize,
+}
+
+impl Player {
+    fn run_interactively(&mut self) -> Result<()> {
+        info!(
+            "loaded {} frames, commands: [n]ext [count], [p]revious [count], [j]ump <seconds>, [q]uit",
+            self.recording.frames().len()
+        );
+        for line in stdin().lock().lines() {
+            let line = line.wrap_err("failed to read command")?;
+            let mut words = line.split_whitespace();
+            let command = words.next().unwrap_or("n");
+            let argument = words.next();
+            let target = match (command, argument) {
+                ("n", count) => match parse_count(count) {
+                    Some(count) => self.position.saturating_add(count),
+                    None => continue,
+                },
+                ("p", count) => match parse_count(count) {
+                    Some(count) => self.position.saturating_sub(count),
+                    None => continue,
+                },
+                ("j", Some(seconds)) => match seconds.parse() {
+                    Ok(seconds) => self.index_after(Duration::from_secs_f32(seconds)),
+                    Err(_) => {
+                        error!("expected seconds since start of recording, got `{seconds}`");
+                        continue;
+                    }
+                },
+                ("q", None) => break,
+                _ => {
+                    error!("unknown command `{line}`");
+                    continue;
+                }
+            };
+            self.seek(target.min(self.recording.frames().len()))?;
+            self.print_position();
+        }
+        Ok(())
+    }
+
+    /// Replays all frames up to `target`.
+    ///
+    /// Nodes keep state across cycles, hence seeking backwards re-creates the replayer and replays
+    /// from the start of the recording. This reproduces the state of the robot at the target frame
+    /// but takes longer for later frames and disconnects all communication clients.
+    fn seek(&mut self, target: usize) -> Result<()> {
+        let (requires_reset, frames_to_replay) = frames_to_replay(self.position, target);
+        if requires_reset {
+            info!("seeking backwards, replaying from the start of the recording");
+            // the new replayer listens on the same addresses, hence the old one is stopped first
+            if let Some(replayer) = self.replayer.take() {
+                replayer.stop().wrap_err("failed to stop replayer")?;
+            }
+            self.replayer = Some(self.replayer_arguments.create()?);
+            self.position = 0;
+        }
+        let Some(replayer) = self.replayer.as_mut() else {
+            bail!("replayer has not been re-created after seeking backwards");
+        };
+        for index in frames_to_replay {
+            let (frame, data) = self.recording.read_frame(index)?;
+            self.replayer_arguments
+                .hardware_interface
+                .set_now(frame.timestamp);
+            replayer
+                .replayer
+                .replay(&frame.instance, frame.timestamp, &data)
+                .wrap_err_with(|| format!("failed to replay frame {index}"))?;
+            self.position = index + 1;
+        }
+        Ok(())
+    }
+
+    fn index_after(&self, since_start: Duration) -> usize {
+        let frames = self.recording.frames();
+        let timestamp = frames[0].timestamp + since_start;
+        frames.partition_point(|frame| frame.timestamp <= timestamp)
+    }
+
+    fn print_position(&self) {
+        let frames = self.recording.frames();
+        match self.position.checked_sub(1).map(|index| &frames[index]) {
+            Some(frame) => {
+                let since_start = frame
+                    .timestamp
+                    .duration_since(frames[0].timestamp)
+                    .unwrap_or_default();
+                info!(
+                    "frame {}/{}: {} at {:.3}s",
+                    self.position,
+                    frames.len(),
+                    frame.instance,
+                    since_start.as_secs_f32()
+                );
+            }
+            None => info!("frame 0/{}: start of recording", frames.len()),
+        }
+    }
+}
+
+/// Whether the replayer has to be reset and which frames have to be replayed to get from `position`
+/// to `target`
+fn frames_to_replay(position: usize, target: usize) -> (bool, Range<usize>) {
+    if target >= position {
+        (false, position..target)
+    } else {
+        (true, 0..target)
+    }
+}
+
+fn parse_count(count: Option<&str>) -> Option<usize> {
+    match count {
+        Some(count) => match count.parse() {
+            Ok(count) => Some(count),
+            Err(_) => {
+                error!("expected number of frames, got `{count}`");
+                None
+            }
+        },
+        None => Some(1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeking_forwards_continues_from_position() {
+        assert_eq!(frames_to_replay(3, 7), (false, 3..7));
+        assert_eq!(frames_to_replay(3, 3), (false, 3..3));
+    }
+
+    #[test]
+    fn seeking_backwards_replays_from_start() {
+        assert_eq!(frames_to_replay(7, 3), (true, 0..3));
+        assert_eq!(frames_to_replay(7, 0), (true, 0..0));
+    }
+}
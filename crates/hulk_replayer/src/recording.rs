@@ -0,0 +1,204 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufReader, ErrorKind, Read, Seek, SeekFrom},
+    path::Path,
+    time::SystemTime,
+};
+
+use bincode::ErrorKind as BincodeErrorKind;
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use log::warn;
+
+pub struct Frame {
+    pub instance: String,
+    pub timestamp: SystemTime,
+    offset: u64,
+    length: usize,
+}
+
+pub struct Recording {
+    frames: Vec<Frame>,
+    files: HashMap<String, BufReader<File>>,
+}
+
+impl Recording {
+    /// Indexes all frames of the given recording files, file names are expected to be `{instance}.{seconds}.bincode`
+    pub fn open(paths: &[impl AsRef<Path>]) -> Result<Self> {
+        let mut frames = Vec::new();
+        let mut files = HashMap::new();
+        for path in paths {
+            let path = path.as_ref();
+            let instance = path
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .and_then(|file_name| file_name.split('.').next())
+                .ok_or_else(|| eyre!("failed to extract cycler instance from {path:?}"))?
+                .to_string();
+            let file =
+                File::open(path).wrap_err_with(|| format!("failed to open recording {path:?}"))?;
+            let file_length = file
+                .metadata()
+                .wrap_err_with(|| format!("failed to get metadata of recording {path:?}"))?
+                .len();
+            let mut reader = BufReader::new(file);
+            index_frames(&instance, &mut reader, file_length, &mut frames)
+                .wrap_err_with(|| format!("failed to index recording {path:?}"))?;
+            if files.insert(instance.clone(), reader).is_some() {
+                return Err(eyre!(
+                    "more than one recording of cycler instance `{instance}`"
+                ));
+            }
+        }
+        frames.sort_by_key(|frame| frame.timestamp);
+        Ok(Self { frames, files })
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    pub fn read_frame(&mut self, index: usize) -> Result<(&Frame, Vec<u8>)> {
+        let frame = &self.frames[index];
+        let reader = self
+            .files
+            .get_mut(&frame.instance)
+            .expect("every indexed frame belongs to an opened file");
+        reader
+            .seek(SeekFrom::Start(frame.offset))
+            .wrap_err("failed to seek to recording frame")?;
+        let mut data = vec![0; frame.length];
+        reader
+            .read_exact(&mut data)
+            .wrap_err("failed to read recording frame")?;
+        Ok((frame, data))
+    }
+}
+
+fn index_frames(
+    instance: &str,
+    reader: &mut BufReader<File>,
+    file_length: u64,
+    frames: &mut Vec<Frame>,
+) -> Result<()> {
+    loop {
+        let header: (SystemTime, u64) = match bincode::deserialize_from(&mut *reader) {
+            Ok(header) => header,
+            Err(error) => match *error {
+                BincodeErrorKind::Io(error) if error.kind() == ErrorKind::UnexpectedEof => {
+                    return Ok(())
+                }
+                error => return Err(error).wrap_err("failed to read frame header"),
+            },
+        };
+        let (timestamp, length) = header;
+        let offset = reader
+            .stream_position()
+            .wrap_err("failed to get position of recording frame")?;
+        if offset + length > file_length {
+            warn!("ignoring truncated last frame of `{instance}` recording");
+            return Ok(());
+        }
+        reader
+            .seek_relative(length as i64)
+            .wrap_err("failed to skip recording frame")?;
+        frames.push(Frame {
+            instance: instance.to_string(),
+            timestamp,
+            offset,
+            length: length as usize,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use tempfile::{tempdir, TempDir};
+
+    use super::*;
+
+    fn timestamp(milliseconds: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_millis(milliseconds)
+    }
+
+    /// Writes frames the way the recording thread of the framework does
+    fn write_recording(
+        directory: &TempDir,
+        file_name: &str,
+        frames: &[(SystemTime, Vec<u8>)],
+    ) -> PathBuf {
+        let path = directory.path().join(file_name);
+        let mut file = File::create(&path).unwrap();
+        for frame in frames {
+            bincode::serialize_into(&mut file, frame).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn frames_of_all_instances_are_ordered_by_timestamp() {
+        let directory = tempdir().unwrap();
+        let control = write_recording(
+            &directory,
+            "Control.1681234567.bincode",
+            &[(timestamp(0), vec![1, 2]), (timestamp(20), vec![3])],
+        );
+        let vision_top = write_recording(
+            &directory,
+            "VisionTop.1681234567.bincode",
+            &[(timestamp(10), vec![4, 5, 6])],
+        );
+
+        let mut recording = Recording::open(&[control, vision_top]).unwrap();
+
+        let instances: Vec<_> = recording
+            .frames()
+            .iter()
+            .map(|frame| frame.instance.as_str())
+            .collect();
+        assert_eq!(instances, ["Control", "VisionTop", "Control"]);
+        let (frame, data) = recording.read_frame(1).unwrap();
+        assert_eq!(frame.timestamp, timestamp(10));
+        assert_eq!(data, [4, 5, 6]);
+        let (frame, data) = recording.read_frame(2).unwrap();
+        assert_eq!(frame.timestamp, timestamp(20));
+        assert_eq!(data, [3]);
+    }
+
+    #[test]
+    fn truncated_last_frame_is_ignored() {
+        let directory = tempdir().unwrap();
+        let path = write_recording(
+            &directory,
+            "Control.1681234567.bincode",
+            &[(timestamp(0), vec![1, 2]), (timestamp(20), vec![3, 4, 5])],
+        );
+        let length = File::open(&path).unwrap().metadata().unwrap().len();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(length - 1)
+            .unwrap();
+
+        let mut recording = Recording::open(&[path]).unwrap();
+
+        assert_eq!(recording.frames().len(), 1);
+        let (_, data) = recording.read_frame(0).unwrap();
+        assert_eq!(data, [1, 2]);
+    }
+
+    #[test]
+    fn two_recordings_of_one_instance_are_rejected() {
+        let directory = tempdir().unwrap();
+        let first = write_recording(&directory, "Control.1.bincode", &[]);
+        let second = write_recording(&directory, "Control.2.bincode", &[]);
+
+        assert!(Recording::open(&[first, second]).is_err());
+    }
+}
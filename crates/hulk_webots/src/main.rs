@@ -70,6 +70,7 @@ fn main() -> Result<()> {
     run(
         Arc::new(hardware_interface),
         framework_parameters.communication_addresses,
+        framework_parameters.communication_audit_log,
         framework_parameters.parameters_directory,
         ids.body_id,
         ids.head_id,
@@ -1,5 +1,6 @@
 mod condition;
 pub mod fallen_abort_condition;
+pub mod motion2;
 pub mod motion_file;
 pub mod motion_interpolator;
 pub mod spline_interpolator;
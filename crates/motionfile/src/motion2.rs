@@ -0,0 +1,334 @@
+use std::{fs::File, path::Path, time::Duration};
+
+use color_eyre::eyre::{Result, WrapErr};
+use serde::Deserialize;
+use serde_json::from_reader;
+use splines::Interpolation;
+use thiserror::Error;
+use types::joints::{arm::ArmJoints, head::HeadJoints, leg::LegJoints, Joints};
+
+use crate::{KeyFrame, MotionFile, MotionFileFrame};
+
+const NUMBER_OF_JOINTS: usize = 26;
+
+#[derive(Debug, Error)]
+pub enum Motion2Error {
+    #[error("unsupported version {0:?}, expected \"2.0\"")]
+    UnsupportedVersion(String),
+    #[error("joints {0:?} are not a permutation of the {NUMBER_OF_JOINTS} NAO joints")]
+    InvalidJoints(Vec<usize>),
+    #[error("position {index} has {actual} parameters, expected {NUMBER_OF_JOINTS}")]
+    WrongNumberOfParameters { index: usize, actual: usize },
+    #[error("position {index} contains non-finite parameters")]
+    NonFiniteParameters { index: usize },
+    #[error("need an initial position and at least one keyframe, got {0} positions")]
+    NotEnoughPositions(usize),
+    #[error("times of all positions sum up to zero")]
+    ZeroTotalTime,
+}
+
+/// Keyframe motion as exported by the Nao Devils motion editor
+#[derive(Clone, Debug, Deserialize)]
+pub struct Motion2 {
+    pub header: Motion2Header,
+    pub position: Vec<Motion2Position>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Motion2Header {
+    /// Joint index (in NAO joint order) of each entry in the parameters of a position
+    pub joints: Vec<usize>,
+    /// Duration of the whole motion in milliseconds
+    pub time: f32,
+    pub title: String,
+    pub version: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Motion2Position {
+    pub parameters: Vec<f32>,
+    /// Relative duration, all durations are scaled to sum up to the time of the header
+    pub time: f32,
+}
+
+impl Motion2 {
+    pub fn from_path(motion2_path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(&motion2_path)
+            .wrap_err_with(|| format!("failed to open motion2 file {:?}", motion2_path.as_ref()))?;
+        from_reader(file)
+            .wrap_err_with(|| format!("failed to parse motion2 file {:?}", motion2_path.as_ref()))
+    }
+
+    pub fn validate(&self) -> Result<(), Motion2Error> {
+        if self.header.version != "2.0" {
+            return Err(Motion2Error::UnsupportedVersion(
+                self.header.version.clone(),
+            ));
+        }
+        let mut joints = self.header.joints.clone();
+        joints.sort_unstable();
+        if !joints.into_iter().eq(0..NUMBER_OF_JOINTS) {
+            return Err(Motion2Error::InvalidJoints(self.header.joints.clone()));
+        }
+        for (index, position) in self.position.iter().enumerate() {
+            if position.parameters.len() != NUMBER_OF_JOINTS {
+                return Err(Motion2Error::WrongNumberOfParameters {
+                    index,
+                    actual: position.parameters.len(),
+                });
+            }
+            if !position
+                .parameters
+                .iter()
+                .all(|parameter| parameter.is_finite())
+            {
+                return Err(Motion2Error::NonFiniteParameters { index });
+            }
+        }
+        if self.position.len() < 2 {
+            return Err(Motion2Error::NotEnoughPositions(self.position.len()));
+        }
+        if self.total_relative_time() <= 0.0 {
+            return Err(Motion2Error::ZeroTotalTime);
+        }
+        Ok(())
+    }
+
+    fn total_relative_time(&self) -> f32 {
+        self.position.iter().map(|position| position.time).sum()
+    }
+
+    fn joints(&self, position: &Motion2Position) -> Joints<f32> {
+        let mut positions = [0.0; NUMBER_OF_JOINTS];
+        for (joint, parameter) in self.header.joints.iter().zip(&position.parameters) {
+            positions[*joint] = *parameter;
+        }
+        joints_from_nao_order(positions)
+    }
+}
+
+impl TryFrom<Motion2> for MotionFile<Joints<f32>> {
+    type Error = Motion2Error;
+
+    /// The first position becomes the initial positions, all following positions are keyframes
+    /// of a single unconditioned frame
+    fn try_from(motion2: Motion2) -> Result<Self, Self::Error> {
+        motion2.validate()?;
+        let total_relative_time = motion2.total_relative_time();
+        let keyframes = motion2.position[1..]
+            .iter()
+            .map(|position| KeyFrame {
+                duration: Duration::from_secs_f32(
+                    position.time / total_relative_time * motion2.header.time / 1000.0,
+                ),
+                positions: motion2.joints(position),
+            })
+            .collect();
+        Ok(MotionFile {
+            interpolation_mode: Interpolation::default(),
+            initial_positions: motion2.joints(&motion2.position[0]),
+            motion: vec![MotionFileFrame {
+                name: Some(motion2.header.title),
+                entry_condition: None,
+                interrupt_conditions: Vec::new(),
+                keyframes,
+                exit_condition: None,
+            }],
+        })
+    }
+}
+
+impl MotionFile<Joints<f32>> {
+    pub fn from_motion2_path(motion2_path: impl AsRef<Path>) -> Result<Self> {
+        let motion2 = Motion2::from_path(&motion2_path)?;
+        motion2
+            .try_into()
+            .wrap_err_with(|| format!("invalid motion2 file {:?}", motion2_path.as_ref()))
+    }
+}
+
+fn joints_from_nao_order(positions: [f32; NUMBER_OF_JOINTS]) -> Joints<f32> {
+    Joints {
+        head: HeadJoints {
+            yaw: positions[0],
+            pitch: positions[1],
+        },
+        left_arm: ArmJoints {
+            shoulder_pitch: positions[2],
+            shoulder_roll: positions[3],
+            elbow_yaw: positions[4],
+            elbow_roll: positions[5],
+            wrist_yaw: positions[6],
+            hand: positions[7],
+        },
+        left_leg: LegJoints {
+            hip_yaw_pitch: positions[8],
+            hip_roll: positions[9],
+            hip_pitch: positions[10],
+            knee_pitch: positions[11],
+            ankle_pitch: positions[12],
+            ankle_roll: positions[13],
+        },
+        right_leg: LegJoints {
+            hip_yaw_pitch: positions[14],
+            hip_roll: positions[15],
+            hip_pitch: positions[16],
+            knee_pitch: positions[17],
+            ankle_pitch: positions[18],
+            ankle_roll: positions[19],
+        },
+        right_arm: ArmJoints {
+            shoulder_pitch: positions[20],
+            shoulder_roll: positions[21],
+            elbow_yaw: positions[22],
+            elbow_roll: positions[23],
+            wrist_yaw: positions[24],
+            hand: positions[25],
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn durations_in_seconds(motion_file: &MotionFile<Joints<f32>>) -> Vec<f32> {
+        motion_file.motion[0]
+            .keyframes
+            .iter()
+            .map(|keyframe| keyframe.duration.as_secs_f32())
+            .collect()
+    }
+
+    fn assert_durations(actual: Vec<f32>, expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() < 1e-6,
+                "duration {actual} differs from {expected}"
+            );
+        }
+    }
+
+    fn motion2(joints: Vec<usize>, relative_times: &[f32]) -> Motion2 {
+        Motion2 {
+            header: Motion2Header {
+                joints,
+                time: 1000.0,
+                title: "test".to_string(),
+                version: "2.0".to_string(),
+            },
+            position: relative_times
+                .iter()
+                .map(|&time| Motion2Position {
+                    parameters: (0..NUMBER_OF_JOINTS).map(|index| index as f32).collect(),
+                    time,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn valid_motion2_is_accepted() {
+        motion2((0..NUMBER_OF_JOINTS).collect(), &[1.0, 1.0])
+            .validate()
+            .unwrap();
+    }
+
+    #[test]
+    fn unsupported_version_is_rejected() {
+        let mut motion2 = motion2((0..NUMBER_OF_JOINTS).collect(), &[1.0, 1.0]);
+        motion2.header.version = "1.0".to_string();
+
+        assert!(matches!(
+            motion2.validate(),
+            Err(Motion2Error::UnsupportedVersion(version)) if version == "1.0"
+        ));
+    }
+
+    #[test]
+    fn joints_have_to_be_a_permutation() {
+        let mut duplicate_joints: Vec<_> = (0..NUMBER_OF_JOINTS).collect();
+        duplicate_joints[25] = 24;
+        let missing_joints: Vec<_> = (0..NUMBER_OF_JOINTS - 1).collect();
+
+        for joints in [duplicate_joints, missing_joints] {
+            assert!(matches!(
+                motion2(joints, &[1.0, 1.0]).validate(),
+                Err(Motion2Error::InvalidJoints(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn wrong_number_of_parameters_is_rejected() {
+        let mut motion2 = motion2((0..NUMBER_OF_JOINTS).collect(), &[1.0, 1.0]);
+        motion2.position[1].parameters.pop();
+
+        assert!(matches!(
+            motion2.validate(),
+            Err(Motion2Error::WrongNumberOfParameters {
+                index: 1,
+                actual: 25
+            })
+        ));
+    }
+
+    #[test]
+    fn non_finite_parameters_are_rejected() {
+        for parameter in [f32::NAN, f32::INFINITY] {
+            let mut motion2 = motion2((0..NUMBER_OF_JOINTS).collect(), &[1.0, 1.0]);
+            motion2.position[1].parameters[3] = parameter;
+
+            assert!(matches!(
+                motion2.validate(),
+                Err(Motion2Error::NonFiniteParameters { index: 1 })
+            ));
+        }
+    }
+
+    #[test]
+    fn single_position_and_zero_time_are_rejected() {
+        assert!(matches!(
+            motion2((0..NUMBER_OF_JOINTS).collect(), &[1.0]).validate(),
+            Err(Motion2Error::NotEnoughPositions(1))
+        ));
+        assert!(matches!(
+            motion2((0..NUMBER_OF_JOINTS).collect(), &[0.0, 0.0]).validate(),
+            Err(Motion2Error::ZeroTotalTime)
+        ));
+    }
+
+    #[test]
+    fn parameters_are_assigned_to_permuted_joints() {
+        let motion_file: MotionFile<Joints<f32>> =
+            motion2((0..NUMBER_OF_JOINTS).rev().collect(), &[1.0, 1.0])
+                .try_into()
+                .unwrap();
+
+        assert_eq!(motion_file.initial_positions.head.yaw, 25.0);
+        assert_eq!(motion_file.initial_positions.left_leg.hip_yaw_pitch, 17.0);
+        assert_eq!(motion_file.initial_positions.right_arm.hand, 0.0);
+    }
+
+    #[test]
+    fn durations_are_scaled_to_header_time() {
+        let motion_file: MotionFile<Joints<f32>> =
+            motion2((0..NUMBER_OF_JOINTS).collect(), &[2.0, 3.0, 5.0])
+                .try_into()
+                .unwrap();
+
+        assert_durations(durations_in_seconds(&motion_file), &[0.3, 0.5]);
+    }
+
+    #[test]
+    fn shipped_motion2_file_is_converted() {
+        let motion_file =
+            MotionFile::from_motion2_path("../../etc/motions/sitDown.motion2").unwrap();
+
+        assert_eq!(motion_file.motion.len(), 1);
+        assert_eq!(motion_file.motion[0].name.as_deref(), Some("sitDown"));
+        assert_eq!(motion_file.initial_positions.head.yaw, 0.009365857);
+        assert_durations(durations_in_seconds(&motion_file), &[0.5, 0.5]);
+    }
+}
@@ -25,7 +25,7 @@ use parameters::{
 };
 use semver::Version;
 use serde::Deserialize;
-use serde_json::{from_slice, from_str, to_string_pretty, to_value, Value};
+use serde_json::{from_slice, from_str, json, to_string_pretty, to_value, Value};
 use tempfile::{tempdir, TempDir};
 use tokio::{
     fs::{
@@ -235,7 +235,10 @@ impl Repository {
             from_str(&file_contents).wrap_err("failed to deserialize framework.json")?;
 
         hardware_json["communication_addresses"] = if enable {
-            Value::String("[::]:1337".to_string())
+            json!({
+                "[::]:1337": "Operator",
+                "[::]:1338": "Observer",
+            })
         } else {
             Value::Null
         };
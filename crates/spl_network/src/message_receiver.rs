@@ -1,8 +1,9 @@
 use color_eyre::{eyre::WrapErr, Result};
 use context_attribute::context;
-use framework::MainOutput;
+use framework::{AdditionalOutput, MainOutput};
 use hardware::NetworkInterface;
 use serde::{Deserialize, Serialize};
+use spl_network_messages::IncompatibleHulkMessage;
 use types::messages::IncomingMessage;
 
 #[derive(Deserialize, Serialize)]
@@ -14,11 +15,13 @@ pub struct CreationContext {}
 #[context]
 pub struct CycleContext {
     hardware_interface: HardwareInterface,
+    incompatible_message: AdditionalOutput<Option<IncompatibleHulkMessage>, "incompatible_message">,
 }
 
 #[context]
 pub struct MainOutputs {
-    pub message: MainOutput<IncomingMessage>,
+    /// Received message, `None` if it was an incompatible team message
+    pub message: MainOutput<Option<IncomingMessage>>,
 }
 
 impl MessageReceiver {
@@ -26,11 +29,21 @@ impl MessageReceiver {
         Ok(Self {})
     }
 
-    pub fn cycle(&mut self, context: CycleContext<impl NetworkInterface>) -> Result<MainOutputs> {
+    pub fn cycle(
+        &mut self,
+        mut context: CycleContext<impl NetworkInterface>,
+    ) -> Result<MainOutputs> {
         let message = context
             .hardware_interface
             .read_from_network()
             .wrap_err("failed to read from network")?;
+        let (message, incompatible_message) = match message {
+            IncomingMessage::IncompatibleSpl(incompatibility) => (None, Some(incompatibility)),
+            message => (Some(message), None),
+        };
+        context
+            .incompatible_message
+            .fill_if_subscribed(|| incompatible_message);
         Ok(MainOutputs {
             message: message.into(),
         })
@@ -0,0 +1,430 @@
+use std::time::Duration;
+
+use color_eyre::{
+    eyre::{bail, eyre},
+    Report, Result,
+};
+use nalgebra::{point, vector, Isometry2, Matrix2, Point2, Vector2};
+use serde::{Deserialize, Serialize};
+use serialize_hierarchy::SerializeHierarchy;
+
+use crate::{PlayerNumber, Role};
+
+/// Leads every encoded `HulkMessage`, followed by the `HULK_MESSAGE_VERSION` of the sender
+pub const HULK_MESSAGE_MAGIC: [u8; 2] = *b"HK";
+/// Has to be incremented whenever the encoding of `HulkMessage` changes
+pub const HULK_MESSAGE_VERSION: u8 = 1;
+
+const FALLEN: u8 = 1 << 0;
+const HAS_WALK_TARGET: u8 = 1 << 1;
+const HAS_BALL_POSITION: u8 = 1 << 2;
+const HAS_TIME_TO_REACH_KICK_POSITION: u8 = 1 << 3;
+
+/// Millimeters per meter, positions and velocities are transmitted in (per second) millimeters
+const POSITION_SCALE: f32 = 1000.0;
+/// Angles are transmitted in units of 1/10000 radians
+const ANGLE_SCALE: f32 = 10000.0;
+/// Correlation coefficients are transmitted in units of 1/127
+const CORRELATION_SCALE: f32 = 127.0;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct HulkMessage {
+    pub player_number: PlayerNumber,
+    pub fallen: bool,
+    pub role: Role,
+    pub robot_to_field: Isometry2<f32>,
+    /// Destination in field coordinates the robot currently walks to
+    pub walk_target: Option<Point2<f32>>,
+    pub ball_position: Option<NetworkBallPosition>,
+    pub time_to_reach_kick_position: Option<Duration>,
+}
+
+/// Ball estimate of the sender, relative to its robot coordinate frame
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct NetworkBallPosition {
+    pub relative_position: Point2<f32>,
+    pub velocity: Vector2<f32>,
+    #[serialize_hierarchy(leaf)]
+    pub covariance: Matrix2<f32>,
+    pub age: Duration,
+}
+
+/// Envelope of a received team message that this build is not able to parse
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, SerializeHierarchy)]
+pub enum IncompatibleHulkMessage {
+    /// The message does not start with `HULK_MESSAGE_MAGIC`, e.g. because it was sent by a build
+    /// without versioned team messages
+    MissingMagic,
+    /// The message was encoded with a different `HULK_MESSAGE_VERSION`
+    UnknownVersion(u8),
+}
+
+impl HulkMessage {
+    /// Checks the envelope of an encoded message without parsing its payload
+    pub fn check_compatibility(buffer: &[u8]) -> Result<(), IncompatibleHulkMessage> {
+        match buffer {
+            [first, second, version, ..] if [*first, *second] == HULK_MESSAGE_MAGIC => {
+                if *version == HULK_MESSAGE_VERSION {
+                    Ok(())
+                } else {
+                    Err(IncompatibleHulkMessage::UnknownVersion(*version))
+                }
+            }
+            _ => Err(IncompatibleHulkMessage::MissingMagic),
+        }
+    }
+}
+
+/// Compact little endian encoding behind `HULK_MESSAGE_MAGIC` and `HULK_MESSAGE_VERSION`:
+/// positions are quantized to millimeters in `i16`, angles to 1/10000 radians, durations to
+/// milliseconds and the ball covariance to standard deviations and a correlation coefficient.
+/// Optional fields are only encoded if present.
+impl From<HulkMessage> for Vec<u8> {
+    fn from(message: HulkMessage) -> Self {
+        let mut flags = 0;
+        if message.fallen {
+            flags |= FALLEN;
+        }
+        if message.walk_target.is_some() {
+            flags |= HAS_WALK_TARGET;
+        }
+        if message.ball_position.is_some() {
+            flags |= HAS_BALL_POSITION;
+        }
+        if message.time_to_reach_kick_position.is_some() {
+            flags |= HAS_TIME_TO_REACH_KICK_POSITION;
+        }
+
+        let mut buffer = HULK_MESSAGE_MAGIC.to_vec();
+        buffer.extend_from_slice(&[
+            HULK_MESSAGE_VERSION,
+            player_number_to_u8(message.player_number),
+            flags,
+            role_to_u8(message.role),
+        ]);
+        write_position(
+            &mut buffer,
+            message.robot_to_field.translation.vector.into(),
+        );
+        write_i16(
+            &mut buffer,
+            message.robot_to_field.rotation.angle() * ANGLE_SCALE,
+        );
+        if let Some(walk_target) = message.walk_target {
+            write_position(&mut buffer, walk_target);
+        }
+        if let Some(ball_position) = message.ball_position {
+            write_position(&mut buffer, ball_position.relative_position);
+            write_i16(&mut buffer, ball_position.velocity.x * POSITION_SCALE);
+            write_i16(&mut buffer, ball_position.velocity.y * POSITION_SCALE);
+            let standard_deviation_x = ball_position.covariance[(0, 0)].max(0.0).sqrt();
+            let standard_deviation_y = ball_position.covariance[(1, 1)].max(0.0).sqrt();
+            let correlation = if standard_deviation_x > 0.0 && standard_deviation_y > 0.0 {
+                ball_position.covariance[(0, 1)] / (standard_deviation_x * standard_deviation_y)
+            } else {
+                0.0
+            };
+            write_u16(&mut buffer, standard_deviation_x * POSITION_SCALE);
+            write_u16(&mut buffer, standard_deviation_y * POSITION_SCALE);
+            buffer.push((correlation.clamp(-1.0, 1.0) * CORRELATION_SCALE).round() as i8 as u8);
+            write_u16(&mut buffer, ball_position.age.as_millis() as f32);
+        }
+        if let Some(time_to_reach_kick_position) = message.time_to_reach_kick_position {
+            let milliseconds = time_to_reach_kick_position
+                .as_millis()
+                .min(u32::MAX as u128) as u32;
+            buffer.extend_from_slice(&milliseconds.to_le_bytes());
+        }
+        buffer
+    }
+}
+
+impl TryFrom<&[u8]> for HulkMessage {
+    type Error = Report;
+
+    fn try_from(buffer: &[u8]) -> Result<Self> {
+        if let Err(incompatibility) = Self::check_compatibility(buffer) {
+            bail!("incompatible message: {incompatibility:?}");
+        }
+        let mut reader = Reader {
+            buffer: &buffer[HULK_MESSAGE_MAGIC.len() + 1..],
+        };
+        let player_number = player_number_from_u8(reader.read_u8()?)?;
+        let flags = reader.read_u8()?;
+        let role = role_from_u8(reader.read_u8()?)?;
+        let translation = reader.read_position()?;
+        let angle = reader.read_i16()? as f32 / ANGLE_SCALE;
+        let walk_target = if flags & HAS_WALK_TARGET != 0 {
+            Some(reader.read_position()?)
+        } else {
+            None
+        };
+        let ball_position = if flags & HAS_BALL_POSITION != 0 {
+            let relative_position = reader.read_position()?;
+            let velocity = vector![
+                reader.read_i16()? as f32 / POSITION_SCALE,
+                reader.read_i16()? as f32 / POSITION_SCALE
+            ];
+            let standard_deviation_x = reader.read_u16()? as f32 / POSITION_SCALE;
+            let standard_deviation_y = reader.read_u16()? as f32 / POSITION_SCALE;
+            let correlation = reader.read_u8()? as i8 as f32 / CORRELATION_SCALE;
+            let covariance_xy = correlation * standard_deviation_x * standard_deviation_y;
+            Some(NetworkBallPosition {
+                relative_position,
+                velocity,
+                covariance: Matrix2::new(
+                    standard_deviation_x.powi(2),
+                    covariance_xy,
+                    covariance_xy,
+                    standard_deviation_y.powi(2),
+                ),
+                age: Duration::from_millis(reader.read_u16()? as u64),
+            })
+        } else {
+            None
+        };
+        let time_to_reach_kick_position = if flags & HAS_TIME_TO_REACH_KICK_POSITION != 0 {
+            Some(Duration::from_millis(reader.read_u32()? as u64))
+        } else {
+            None
+        };
+        if !reader.buffer.is_empty() {
+            bail!("{} unexpected trailing bytes", reader.buffer.len());
+        }
+        Ok(Self {
+            player_number,
+            fallen: flags & FALLEN != 0,
+            role,
+            robot_to_field: Isometry2::new(translation.coords, angle),
+            walk_target,
+            ball_position,
+            time_to_reach_kick_position,
+        })
+    }
+}
+
+fn write_i16(buffer: &mut Vec<u8>, value: f32) {
+    // `as` saturates at the limits of the target type
+    buffer.extend_from_slice(&(value.round() as i16).to_le_bytes());
+}
+
+fn write_u16(buffer: &mut Vec<u8>, value: f32) {
+    buffer.extend_from_slice(&(value.round() as u16).to_le_bytes());
+}
+
+fn write_position(buffer: &mut Vec<u8>, position: Point2<f32>) {
+    write_i16(buffer, position.x * POSITION_SCALE);
+    write_i16(buffer, position.y * POSITION_SCALE);
+}
+
+struct Reader<'buffer> {
+    buffer: &'buffer [u8],
+}
+
+impl Reader<'_> {
+    fn read<const SIZE: usize>(&mut self) -> Result<[u8; SIZE]> {
+        if self.buffer.len() < SIZE {
+            return Err(eyre!("buffer too small"));
+        }
+        let (bytes, remaining) = self.buffer.split_at(SIZE);
+        self.buffer = remaining;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read::<1>()?[0])
+    }
+
+    fn read_i16(&mut self) -> Result<i16> {
+        Ok(i16::from_le_bytes(self.read()?))
+    }
+
+    fn read_u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.read()?))
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.read()?))
+    }
+
+    fn read_position(&mut self) -> Result<Point2<f32>> {
+        Ok(point![
+            self.read_i16()? as f32 / POSITION_SCALE,
+            self.read_i16()? as f32 / POSITION_SCALE
+        ])
+    }
+}
+
+fn player_number_to_u8(player_number: PlayerNumber) -> u8 {
+    match player_number {
+        PlayerNumber::One => 1,
+        PlayerNumber::Two => 2,
+        PlayerNumber::Three => 3,
+        PlayerNumber::Four => 4,
+        PlayerNumber::Five => 5,
+        PlayerNumber::Six => 6,
+        PlayerNumber::Seven => 7,
+    }
+}
+
+fn player_number_from_u8(player_number: u8) -> Result<PlayerNumber> {
+    Ok(match player_number {
+        1 => PlayerNumber::One,
+        2 => PlayerNumber::Two,
+        3 => PlayerNumber::Three,
+        4 => PlayerNumber::Four,
+        5 => PlayerNumber::Five,
+        6 => PlayerNumber::Six,
+        7 => PlayerNumber::Seven,
+        _ => bail!("unexpected player number {player_number}"),
+    })
+}
+
+fn role_to_u8(role: Role) -> u8 {
+    match role {
+        Role::DefenderLeft => 0,
+        Role::DefenderRight => 1,
+        Role::Keeper => 2,
+        Role::Loser => 3,
+        Role::MidfielderLeft => 4,
+        Role::MidfielderRight => 5,
+        Role::ReplacementKeeper => 6,
+        Role::Searcher => 7,
+        Role::Striker => 8,
+        Role::StrikerSupporter => 9,
+    }
+}
+
+fn role_from_u8(role: u8) -> Result<Role> {
+    Ok(match role {
+        0 => Role::DefenderLeft,
+        1 => Role::DefenderRight,
+        2 => Role::Keeper,
+        3 => Role::Loser,
+        4 => Role::MidfielderLeft,
+        5 => Role::MidfielderRight,
+        6 => Role::ReplacementKeeper,
+        7 => Role::Searcher,
+        8 => Role::Striker,
+        9 => Role::StrikerSupporter,
+        _ => bail!("unexpected role {role}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    fn full_message() -> HulkMessage {
+        HulkMessage {
+            player_number: PlayerNumber::Seven,
+            fallen: true,
+            role: Role::StrikerSupporter,
+            robot_to_field: Isometry2::new(vector![-4.5, 3.0], FRAC_PI_2),
+            walk_target: Some(point![1.25, -0.5]),
+            ball_position: Some(NetworkBallPosition {
+                relative_position: point![0.8, -0.3],
+                velocity: vector![-1.5, 0.25],
+                covariance: Matrix2::new(0.04, -0.006, -0.006, 0.01),
+                age: Duration::from_millis(1500),
+            }),
+            time_to_reach_kick_position: Some(Duration::from_millis(12345)),
+        }
+    }
+
+    #[test]
+    fn maximum_hulk_message_size() {
+        let buffer: Vec<u8> = full_message().into();
+        assert!(buffer.len() <= 128)
+    }
+
+    #[test]
+    fn hulk_message_round_trip() {
+        let message = full_message();
+        let buffer: Vec<u8> = message.into();
+        let parsed = HulkMessage::try_from(buffer.as_slice()).unwrap();
+
+        assert_eq!(parsed.player_number, message.player_number);
+        assert_eq!(parsed.fallen, message.fallen);
+        assert_eq!(parsed.role, message.role);
+        assert_relative_eq!(
+            parsed.robot_to_field,
+            message.robot_to_field,
+            epsilon = 0.001
+        );
+        assert_relative_eq!(
+            parsed.walk_target.unwrap(),
+            message.walk_target.unwrap(),
+            epsilon = 0.001
+        );
+        let parsed_ball = parsed.ball_position.unwrap();
+        let ball = message.ball_position.unwrap();
+        assert_relative_eq!(
+            parsed_ball.relative_position,
+            ball.relative_position,
+            epsilon = 0.001
+        );
+        assert_relative_eq!(parsed_ball.velocity, ball.velocity, epsilon = 0.001);
+        assert_relative_eq!(parsed_ball.covariance, ball.covariance, epsilon = 0.001);
+        assert_eq!(parsed_ball.age, ball.age);
+        assert_eq!(
+            parsed.time_to_reach_kick_position,
+            message.time_to_reach_kick_position
+        );
+    }
+
+    #[test]
+    fn absent_fields_are_not_encoded() {
+        let message = HulkMessage {
+            walk_target: None,
+            ball_position: None,
+            time_to_reach_kick_position: None,
+            ..full_message()
+        };
+        let buffer: Vec<u8> = message.into();
+        assert_eq!(buffer.len(), 12);
+
+        let parsed = HulkMessage::try_from(buffer.as_slice()).unwrap();
+        assert!(parsed.walk_target.is_none());
+        assert!(parsed.ball_position.is_none());
+        assert!(parsed.time_to_reach_kick_position.is_none());
+    }
+
+    #[test]
+    fn truncated_message_is_rejected() {
+        let buffer: Vec<u8> = full_message().into();
+        assert!(HulkMessage::try_from(&buffer[..buffer.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn other_versions_are_incompatible() {
+        let mut buffer: Vec<u8> = full_message().into();
+        assert_eq!(HulkMessage::check_compatibility(&buffer), Ok(()));
+
+        buffer[HULK_MESSAGE_MAGIC.len()] = HULK_MESSAGE_VERSION + 1;
+        assert_eq!(
+            HulkMessage::check_compatibility(&buffer),
+            Err(IncompatibleHulkMessage::UnknownVersion(
+                HULK_MESSAGE_VERSION + 1
+            ))
+        );
+        assert!(HulkMessage::try_from(buffer.as_slice()).is_err());
+    }
+
+    #[test]
+    fn messages_without_magic_are_incompatible() {
+        let buffer: Vec<u8> = full_message().into();
+        assert_eq!(
+            HulkMessage::check_compatibility(&buffer[1..]),
+            Err(IncompatibleHulkMessage::MissingMagic)
+        );
+        assert_eq!(
+            HulkMessage::check_compatibility(&[]),
+            Err(IncompatibleHulkMessage::MissingMagic)
+        );
+    }
+}
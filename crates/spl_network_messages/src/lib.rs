@@ -1,6 +1,7 @@
 mod bindings;
 mod game_controller_return_message;
 mod game_controller_state_message;
+mod hulk_message;
 mod visual_referee_message;
 
 use std::{
@@ -8,7 +9,8 @@ use std::{
     time::Duration,
 };
 
-use nalgebra::{Isometry2, Point2};
+use enum_iterator::Sequence;
+use nalgebra::Point2;
 use serde::{Deserialize, Serialize};
 
 pub use game_controller_return_message::GameControllerReturnMessage;
@@ -16,18 +18,13 @@ pub use game_controller_state_message::{
     GameControllerStateMessage, GamePhase, GameState, Half, Penalty, PenaltyShoot, Player,
     SubState, Team, TeamColor, TeamState,
 };
+pub use hulk_message::{
+    HulkMessage, IncompatibleHulkMessage, NetworkBallPosition, HULK_MESSAGE_MAGIC,
+    HULK_MESSAGE_VERSION,
+};
 use serialize_hierarchy::SerializeHierarchy;
 pub use visual_referee_message::{VisualRefereeDecision, VisualRefereeMessage};
 
-#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
-pub struct HulkMessage {
-    pub player_number: PlayerNumber,
-    pub fallen: bool,
-    pub robot_to_field: Isometry2<f32>,
-    pub ball_position: Option<BallPosition>,
-    pub time_to_reach_kick_position: Option<Duration>,
-}
-
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
 pub struct BallPosition {
     pub relative_position: Point2<f32>,
@@ -66,26 +63,19 @@ impl Display for PlayerNumber {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::time::Duration;
-
-    use nalgebra::Isometry2;
-
-    use crate::{BallPosition, HulkMessage, PlayerNumber};
-
-    #[test]
-    fn maximum_hulk_message_size() {
-        let test_message = HulkMessage {
-            player_number: PlayerNumber::Seven,
-            fallen: false,
-            robot_to_field: Isometry2::identity(),
-            ball_position: Some(BallPosition {
-                relative_position: nalgebra::OPoint::origin(),
-                age: Duration::MAX,
-            }),
-            time_to_reach_kick_position: Some(Duration::MAX),
-        };
-        assert!(bincode::serialize(&test_message).unwrap().len() <= 128)
-    }
+#[derive(
+    Default, Clone, Copy, Debug, Deserialize, Eq, PartialEq, Sequence, Serialize, SerializeHierarchy,
+)]
+pub enum Role {
+    DefenderLeft,
+    DefenderRight,
+    Keeper,
+    Loser,
+    MidfielderLeft,
+    MidfielderRight,
+    ReplacementKeeper,
+    Searcher,
+    #[default]
+    Striker,
+    StrikerSupporter,
 }
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
 
-#[derive(Debug, Clone, Copy, SerializeHierarchy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, SerializeHierarchy, Serialize, Deserialize)]
 pub enum Action {
     Unstiff,
     SitDown,
@@ -13,6 +13,7 @@ pub enum Action {
     LookAround,
     InterceptBall,
     Calibrate,
+    Catch,
     Dribble,
     DefendGoal,
     DefendKickOff,
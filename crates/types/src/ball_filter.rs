@@ -40,6 +40,10 @@ impl Hypothesis {
         BallPosition {
             position: Point2::from(selected_state.mean.xy()),
             velocity: vector![selected_state.mean.z, selected_state.mean.w],
+            covariance: selected_state
+                .covariance
+                .fixed_view::<2, 2>(0, 0)
+                .into_owned(),
             last_seen: self.last_update,
         }
     }
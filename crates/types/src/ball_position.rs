@@ -1,6 +1,6 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use nalgebra::{Point2, Vector2};
+use nalgebra::{Matrix2, Point2, Vector2};
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
 
@@ -8,6 +8,8 @@ use serialize_hierarchy::SerializeHierarchy;
 pub struct BallPosition {
     pub position: Point2<f32>,
     pub velocity: Vector2<f32>,
+    #[serialize_hierarchy(leaf)]
+    pub covariance: Matrix2<f32>,
     pub last_seen: SystemTime,
 }
 
@@ -16,6 +18,7 @@ impl Default for BallPosition {
         Self {
             position: Default::default(),
             velocity: Default::default(),
+            covariance: Default::default(),
             last_seen: UNIX_EPOCH,
         }
     }
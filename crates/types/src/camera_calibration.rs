@@ -0,0 +1,27 @@
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+use serialize_hierarchy::SerializeHierarchy;
+
+use crate::{camera_matrix::CameraMatrix, line::Line2};
+
+/// Lines detected in one image in pixel coordinates together with the camera matrix of the image
+#[derive(Clone, Debug, Deserialize, Serialize, SerializeHierarchy)]
+pub struct CalibrationMeasurement {
+    pub camera_matrix: CameraMatrix,
+    pub lines_in_image: Vec<Line2>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct CameraCalibrationResult {
+    pub number_of_measurements: usize,
+    pub termination: String,
+    pub number_of_evaluations: usize,
+    pub objective_function: f32,
+    pub residuals: Vec<f32>,
+    /// Values for `camera_matrix_parameters.correction_in_robot` in degrees
+    pub correction_in_robot: Vector3<f32>,
+    /// Values for `camera_matrix_parameters.vision_top.extrinsic_rotations` in degrees
+    pub extrinsic_rotations_top: Vector3<f32>,
+    /// Values for `camera_matrix_parameters.vision_bottom.extrinsic_rotations` in degrees
+    pub extrinsic_rotations_bottom: Vector3<f32>,
+}
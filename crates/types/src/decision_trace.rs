@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{action::Action, primary_state::PrimaryState};
+
+/// Evaluated actions of one behavior cycle in priority order, the last entry is the selected one
+pub type DecisionTrace = Vec<ActionDecision>;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ActionDecision {
+    pub action: Action,
+    pub outcome: ActionOutcome,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ActionOutcome {
+    Selected,
+    Rejected { rejection: Rejection },
+}
+
+/// Why an action did not produce a motion command
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Rejection {
+    UnsuitablePrimaryState {
+        primary_state: PrimaryState,
+    },
+    NotFallen,
+    NotFalling,
+    NoGroundContact,
+    NoLocalization,
+    NoBall,
+    BallIsNotFree,
+    NoKickDecisions,
+    NoPenaltyShotDirection,
+    PenaltyKick,
+    PenaltyShootout,
+    /// Only the striker stands still while searching the ball in a penalty shootout
+    NotSearchingBallInPenaltyShootout,
+    BallIsNoInterceptionCandidate,
+    MaximumStepSizeIsZero,
+    InterceptionPointTooFar,
+    BallIsNotApproachingGoal,
+    BallMissesGoal,
+    BallPassesOutOfReach,
+    BallArrivesTooLate,
+}
@@ -1,11 +1,12 @@
 use nalgebra::{Point2, Vector2};
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
+use spl_network_messages::Team;
 
 #[derive(Default, Clone, Debug, Deserialize, Serialize, SerializeHierarchy)]
 pub struct DetectedRobots {
     pub in_image: Vec<BoundingBox>,
-    pub on_ground: Vec<Point2<f32>>,
+    pub on_ground: Vec<RobotOnGround>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, SerializeHierarchy)]
@@ -14,4 +15,12 @@ pub struct BoundingBox {
     pub size: Vector2<f32>,
     pub probability: f32,
     pub distance: f32,
+    /// Team estimated from the jersey color, uncertain if the jersey could not be classified
+    pub team: Team,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, SerializeHierarchy)]
+pub struct RobotOnGround {
+    pub position: Point2<f32>,
+    pub team: Team,
 }
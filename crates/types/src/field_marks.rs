@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     field_dimensions::FieldDimensions,
     line::{Line, Line2},
+    line_intersections::IntersectionKind,
 };
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
@@ -412,3 +413,86 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
         },
     ]
 }
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum PointLandmarkKind {
+    GoalPost,
+    Intersection(IntersectionKind),
+    PenaltyMark,
+}
+
+/// Field feature that is measured as a single point, in contrast to the lines of a `FieldMark`
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct PointLandmark {
+    pub position: Point2<f32>,
+    pub kind: PointLandmarkKind,
+}
+
+pub fn point_landmarks_from_field_dimensions(
+    field_dimensions: &FieldDimensions,
+) -> Vec<PointLandmark> {
+    let half_length = field_dimensions.length / 2.0;
+    let half_width = field_dimensions.width / 2.0;
+    let goal_post_x = half_length + field_dimensions.goal_post_diameter / 2.0;
+    let goal_post_y =
+        field_dimensions.goal_inner_width / 2.0 + field_dimensions.goal_post_diameter / 2.0;
+    let goal_box_x = half_length - field_dimensions.goal_box_area_length;
+    let goal_box_y = field_dimensions.goal_box_area_width / 2.0;
+    let penalty_area_x = half_length - field_dimensions.penalty_area_length;
+    let penalty_area_y = field_dimensions.penalty_area_width / 2.0;
+    let center_circle_radius = field_dimensions.center_circle_diameter / 2.0;
+
+    let landmark = |x: f32, y: f32, kind| PointLandmark {
+        position: point![x, y],
+        kind,
+    };
+    let intersection = |x: f32, y: f32, kind| landmark(x, y, PointLandmarkKind::Intersection(kind));
+    let mut landmarks = vec![
+        intersection(0.0, half_width, IntersectionKind::T),
+        intersection(0.0, -half_width, IntersectionKind::T),
+        intersection(0.0, center_circle_radius, IntersectionKind::X),
+        intersection(0.0, -center_circle_radius, IntersectionKind::X),
+    ];
+    for x_sign in [-1.0, 1.0] {
+        landmarks.push(landmark(
+            x_sign * (half_length - field_dimensions.penalty_marker_distance),
+            0.0,
+            PointLandmarkKind::PenaltyMark,
+        ));
+        for y_sign in [-1.0, 1.0] {
+            landmarks.extend([
+                landmark(
+                    x_sign * goal_post_x,
+                    y_sign * goal_post_y,
+                    PointLandmarkKind::GoalPost,
+                ),
+                intersection(
+                    x_sign * half_length,
+                    y_sign * half_width,
+                    IntersectionKind::L,
+                ),
+                intersection(
+                    x_sign * half_length,
+                    y_sign * goal_box_y,
+                    IntersectionKind::T,
+                ),
+                intersection(
+                    x_sign * goal_box_x,
+                    y_sign * goal_box_y,
+                    IntersectionKind::L,
+                ),
+                intersection(
+                    x_sign * half_length,
+                    y_sign * penalty_area_y,
+                    IntersectionKind::T,
+                ),
+                intersection(
+                    x_sign * penalty_area_x,
+                    y_sign * penalty_area_y,
+                    IntersectionKind::L,
+                ),
+            ]);
+        }
+    }
+    landmarks
+}
@@ -2,7 +2,7 @@ use std::time::SystemTime;
 
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
-use spl_network_messages::{GamePhase, GameState, Penalty, SubState, Team};
+use spl_network_messages::{GamePhase, GameState, Penalty, SubState, Team, TeamColor, TeamState};
 
 use crate::players::Players;
 
@@ -16,4 +16,27 @@ pub struct GameControllerState {
     pub remaining_amount_of_messages: u16,
     pub sub_state: Option<SubState>,
     pub hulks_team_is_home_after_coin_toss: bool,
+    pub hulks_team_colors: TeamColors,
+    pub opponent_team_colors: TeamColors,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, SerializeHierarchy)]
+pub struct TeamColors {
+    pub field_player: TeamColor,
+    pub goal_keeper: TeamColor,
+}
+
+impl TeamColors {
+    pub fn contains(&self, color: TeamColor) -> bool {
+        self.field_player == color || self.goal_keeper == color
+    }
+}
+
+impl From<&TeamState> for TeamColors {
+    fn from(team_state: &TeamState) -> Self {
+        Self {
+            field_player: team_state.field_player_color,
+            goal_keeper: team_state.goal_keeper_color,
+        }
+    }
 }
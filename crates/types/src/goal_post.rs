@@ -0,0 +1,14 @@
+use nalgebra::{Matrix2, Point2};
+use serde::{Deserialize, Serialize};
+use serialize_hierarchy::SerializeHierarchy;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct GoalPost {
+    /// Foot point of the post in ground coordinates
+    pub position: Point2<f32>,
+    /// Covariance of `position` resulting from the pixel uncertainty of the foot point
+    #[serialize_hierarchy(leaf)]
+    pub covariance: Matrix2<f32>,
+    /// Foot point of the post in image coordinates
+    pub position_in_image: Point2<f32>,
+}
@@ -2,12 +2,35 @@ use nalgebra::Isometry2;
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
 
-use crate::{motion_command::KickVariant, support_foot::Side};
+use crate::{
+    motion_command::{KickVariant, StandingKickVariant},
+    parameters::{InWalkKicksParameters, KickInfoParameters, StandingKicksParameters},
+    support_foot::Side,
+};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, SerializeHierarchy)]
 pub struct KickDecision {
-    pub variant: KickVariant,
+    pub kick: KickType,
     pub kicking_side: Side,
     pub kick_pose: Isometry2<f32>,
     pub strength: f32,
 }
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, SerializeHierarchy)]
+pub enum KickType {
+    InWalk { variant: KickVariant },
+    Standing { variant: StandingKickVariant },
+}
+
+impl KickType {
+    pub fn info<'parameters>(
+        &self,
+        in_walk_kicks: &'parameters InWalkKicksParameters,
+        standing_kicks: &'parameters StandingKicksParameters,
+    ) -> &'parameters KickInfoParameters {
+        match self {
+            KickType::InWalk { variant } => &in_walk_kicks[*variant],
+            KickType::Standing { variant } => &standing_kicks[*variant],
+        }
+    }
+}
@@ -5,11 +5,13 @@ pub mod ball;
 pub mod ball_filter;
 pub mod ball_position;
 pub mod buttons;
+pub mod camera_calibration;
 pub mod camera_matrix;
 pub mod camera_position;
 pub mod color;
 pub mod condition_input;
 pub mod cycle_time;
+pub mod decision_trace;
 pub mod detected_feet;
 pub mod detected_robots;
 pub mod fall_state;
@@ -21,6 +23,7 @@ pub mod filtered_game_state;
 pub mod filtered_segments;
 pub mod filtered_whistle;
 pub mod game_controller_state;
+pub mod goal_post;
 pub mod grayscale_image;
 pub mod hardware;
 pub mod horizon;
@@ -37,6 +40,7 @@ pub mod led;
 pub mod limb;
 pub mod line;
 pub mod line_data;
+pub mod line_intersections;
 pub mod localization;
 pub mod message_event;
 pub mod messages;
@@ -49,6 +53,7 @@ pub mod obstacles;
 pub mod orientation_filter;
 pub mod parameters;
 pub mod path_obstacles;
+pub mod penalty_marks;
 pub mod penalty_shot_direction;
 pub mod perspective_grid_candidates;
 pub mod planned_path;
@@ -0,0 +1,25 @@
+use nalgebra::Point2;
+use serde::{Deserialize, Serialize};
+use serialize_hierarchy::SerializeHierarchy;
+
+/// Shape of a line intersection, named after the letter the two lines form
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, SerializeHierarchy)]
+pub enum IntersectionKind {
+    /// Both lines end at the intersection
+    L,
+    /// One line ends on the other one
+    T,
+    /// Both lines cross each other
+    X,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, SerializeHierarchy)]
+pub struct LineIntersection {
+    pub position: Point2<f32>,
+    pub kind: IntersectionKind,
+}
+
+#[derive(Clone, Default, Debug, Deserialize, Serialize, SerializeHierarchy)]
+pub struct LineIntersections {
+    pub intersections_in_robot: Vec<LineIntersection>,
+}
@@ -1,13 +1,16 @@
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
 use spl_network_messages::{
-    GameControllerReturnMessage, GameControllerStateMessage, HulkMessage, VisualRefereeMessage,
+    GameControllerReturnMessage, GameControllerStateMessage, HulkMessage, IncompatibleHulkMessage,
+    VisualRefereeMessage,
 };
 
 #[derive(Clone, Debug, Deserialize, Serialize, SerializeHierarchy)]
 pub enum IncomingMessage {
     GameController(GameControllerStateMessage),
     Spl(HulkMessage),
+    /// Team message of a build with a different encoding, only reported by the message receiver
+    IncompatibleSpl(IncompatibleHulkMessage),
 }
 
 impl Default for IncomingMessage {
@@ -24,6 +24,7 @@ pub enum MotionCommand {
     SitDown {
         head: HeadMotion,
     },
+    SquatCatch,
     Stand {
         head: HeadMotion,
         is_energy_saving: bool,
@@ -31,6 +32,9 @@ pub enum MotionCommand {
     StandUp {
         facing: Facing,
     },
+    StationaryCatch {
+        side: Side,
+    },
     #[default]
     Unstiff,
     Walk {
@@ -46,6 +50,10 @@ pub enum MotionCommand {
         kicking_side: Side,
         strength: f32,
     },
+    Kick {
+        variant: StandingKickVariant,
+        side: Side,
+    },
 }
 
 impl MotionCommand {
@@ -60,7 +68,10 @@ impl MotionCommand {
             MotionCommand::ArmsUpSquat
             | MotionCommand::FallProtection { .. }
             | MotionCommand::Jump { .. }
-            | MotionCommand::StandUp { .. } => None,
+            | MotionCommand::Kick { .. }
+            | MotionCommand::SquatCatch
+            | MotionCommand::StandUp { .. }
+            | MotionCommand::StationaryCatch { .. } => None,
         }
     }
 }
@@ -78,6 +89,7 @@ pub enum HeadMotion {
     LookLeftAndRightOf {
         target: Point2<f32>,
     },
+    Calibrate,
     Unstiff,
 }
 
@@ -102,6 +114,13 @@ pub enum KickVariant {
     Side,
 }
 
+/// Standing kicks played from keyframe motion files
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, SerializeHierarchy)]
+pub enum StandingKickVariant {
+    Forward,
+    Outer,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, SerializeHierarchy)]
 pub enum Facing {
     Down,
@@ -18,11 +18,15 @@ pub enum MotionType {
     Initial,
     JumpLeft,
     JumpRight,
+    Kick,
     Penalized,
     SitDown,
+    SquatCatch,
     Stand,
     StandUpBack,
     StandUpFront,
+    StationaryCatchLeft,
+    StationaryCatchRight,
     Unstiff,
     Walk,
 }
@@ -42,11 +46,15 @@ pub struct MotionSafeExits {
     initial: bool,
     jump_left: bool,
     jump_right: bool,
+    kick: bool,
     penalized: bool,
     sit_down: bool,
+    squat_catch: bool,
     stand_up_back: bool,
     stand_up_front: bool,
     stand: bool,
+    stationary_catch_left: bool,
+    stationary_catch_right: bool,
     unstiff: bool,
     walk: bool,
 }
@@ -61,11 +69,15 @@ impl Default for MotionSafeExits {
             initial: true,
             jump_left: false,
             jump_right: false,
+            kick: false,
             penalized: true,
             sit_down: false,
+            squat_catch: false,
             stand_up_back: false,
             stand_up_front: false,
             stand: true,
+            stationary_catch_left: false,
+            stationary_catch_right: false,
             unstiff: true,
             walk: false,
         }
@@ -83,12 +95,16 @@ impl Index<MotionType> for MotionSafeExits {
             MotionType::Initial => &self.initial,
             MotionType::JumpLeft => &self.jump_left,
             MotionType::JumpRight => &self.jump_right,
+            MotionType::Kick => &self.kick,
             MotionType::FallProtection => &self.fall_protection,
             MotionType::Penalized => &self.penalized,
             MotionType::SitDown => &self.sit_down,
+            MotionType::SquatCatch => &self.squat_catch,
             MotionType::Stand => &self.stand,
             MotionType::StandUpBack => &self.stand_up_back,
             MotionType::StandUpFront => &self.stand_up_front,
+            MotionType::StationaryCatchLeft => &self.stationary_catch_left,
+            MotionType::StationaryCatchRight => &self.stationary_catch_right,
             MotionType::Unstiff => &self.unstiff,
             MotionType::Walk => &self.walk,
         }
@@ -104,12 +120,16 @@ impl IndexMut<MotionType> for MotionSafeExits {
             MotionType::Initial => &mut self.initial,
             MotionType::JumpLeft => &mut self.jump_left,
             MotionType::JumpRight => &mut self.jump_right,
+            MotionType::Kick => &mut self.kick,
             MotionType::FallProtection => &mut self.fall_protection,
             MotionType::Penalized => &mut self.penalized,
             MotionType::SitDown => &mut self.sit_down,
+            MotionType::SquatCatch => &mut self.squat_catch,
             MotionType::Stand => &mut self.stand,
             MotionType::StandUpBack => &mut self.stand_up_back,
             MotionType::StandUpFront => &mut self.stand_up_front,
+            MotionType::StationaryCatchLeft => &mut self.stationary_catch_left,
+            MotionType::StationaryCatchRight => &mut self.stationary_catch_right,
             MotionType::Unstiff => &mut self.unstiff,
             MotionType::Walk => &mut self.walk,
         }
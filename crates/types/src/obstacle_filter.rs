@@ -1,6 +1,7 @@
-use std::time::SystemTime;
+use std::{cmp::Ordering, time::SystemTime};
 
 use serde::{Deserialize, Serialize};
+use spl_network_messages::Team;
 
 use crate::{
     multivariate_normal_distribution::MultivariateNormalDistribution, obstacles::ObstacleKind,
@@ -12,4 +13,70 @@ pub struct Hypothesis {
     pub measurement_count: usize,
     pub last_update: SystemTime,
     pub obstacle_kind: ObstacleKind,
+    pub hulks_measurement_count: usize,
+    pub opponent_measurement_count: usize,
+}
+
+impl Hypothesis {
+    /// Team most measurements were associated with, uncertain unless it got at least
+    /// `minimum_count` measurements making up at least `minimum_fraction` of all team measurements
+    pub fn team(&self, minimum_count: usize, minimum_fraction: f32) -> Team {
+        let (team, count) = match self
+            .hulks_measurement_count
+            .cmp(&self.opponent_measurement_count)
+        {
+            Ordering::Greater => (Team::Hulks, self.hulks_measurement_count),
+            Ordering::Less => (Team::Opponent, self.opponent_measurement_count),
+            Ordering::Equal => return Team::Uncertain,
+        };
+        let total_count = self.hulks_measurement_count + self.opponent_measurement_count;
+        if count >= minimum_count && count as f32 >= minimum_fraction * total_count as f32 {
+            team
+        } else {
+            Team::Uncertain
+        }
+    }
+
+    pub fn count_team_measurement(&mut self, team: Team) {
+        match team {
+            Team::Hulks => self.hulks_measurement_count += 1,
+            Team::Opponent => self.opponent_measurement_count += 1,
+            Team::Uncertain => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use nalgebra::{Matrix2, Vector2};
+
+    use super::*;
+
+    fn hypothesis(hulks_measurement_count: usize, opponent_measurement_count: usize) -> Hypothesis {
+        Hypothesis {
+            state: MultivariateNormalDistribution {
+                mean: Vector2::zeros(),
+                covariance: Matrix2::identity(),
+            },
+            measurement_count: hulks_measurement_count + opponent_measurement_count,
+            last_update: UNIX_EPOCH,
+            obstacle_kind: ObstacleKind::Robot,
+            hulks_measurement_count,
+            opponent_measurement_count,
+        }
+    }
+
+    #[test]
+    fn single_vote_majority_is_uncertain() {
+        assert_eq!(hypothesis(1, 0).team(5, 0.8), Team::Uncertain);
+        assert_eq!(hypothesis(6, 5).team(5, 0.8), Team::Uncertain);
+    }
+
+    #[test]
+    fn clear_majority_determines_team() {
+        assert_eq!(hypothesis(9, 1).team(5, 0.8), Team::Hulks);
+        assert_eq!(hypothesis(0, 5).team(5, 0.8), Team::Opponent);
+    }
 }
@@ -1,6 +1,7 @@
 use nalgebra::Point2;
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
+use spl_network_messages::Team;
 
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
 pub enum ObstacleKind {
@@ -17,6 +18,7 @@ pub struct Obstacle {
     pub position: Point2<f32>,
     pub radius_at_foot_height: f32,
     pub radius_at_hip_height: f32,
+    pub team: Team,
 }
 
 impl Obstacle {
@@ -26,6 +28,7 @@ impl Obstacle {
             position,
             radius_at_foot_height: radius,
             radius_at_hip_height: radius,
+            team: Team::Uncertain,
         }
     }
 
@@ -39,6 +42,7 @@ impl Obstacle {
             position,
             radius_at_foot_height,
             radius_at_hip_height,
+            team: Team::Uncertain,
         }
     }
 
@@ -48,6 +52,7 @@ impl Obstacle {
             position,
             radius_at_foot_height: radius,
             radius_at_hip_height: radius,
+            team: Team::Uncertain,
         }
     }
 }
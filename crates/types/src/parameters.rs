@@ -4,11 +4,14 @@ use std::{path::PathBuf, time::Duration};
 use nalgebra::{Point2, Vector2, Vector3, Vector4};
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
+use spl_network_messages::TeamColor;
 
 use crate::{
+    action::Action,
+    color::YCbCr444,
     joints::{arm::ArmJoints, head::HeadJoints, leg::LegJoints},
     kick_step::KickStep,
-    motion_command::{KickVariant, MotionCommand},
+    motion_command::{KickVariant, MotionCommand, StandingKickVariant},
     roles::Role,
     step_plan::Step,
 };
@@ -32,6 +35,7 @@ pub struct StepPlannerParameters {
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
 pub struct BehaviorParameters {
+    pub action_lists: ActionListsParameters,
     pub injected_motion_command: Option<MotionCommand>,
     pub lost_ball: LostBallParameters,
     pub optional_roles: Vec<Role>,
@@ -42,9 +46,65 @@ pub struct BehaviorParameters {
     pub search: SearchParameters,
     pub look_action: LookActionParameters,
     pub intercept_ball: InterceptBallParameters,
+    pub catch: CatchParameters,
     pub initial_lookaround_duration: Duration,
 }
 
+/// Priority lists of actions, the first applicable action is executed
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, SerializeHierarchy)]
+pub struct ActionListsParameters {
+    /// Tried before the actions of the role, independent of the role
+    pub common: Vec<Action>,
+    pub defender_left: Vec<SituationalActions>,
+    pub defender_right: Vec<SituationalActions>,
+    pub keeper: Vec<SituationalActions>,
+    pub loser: Vec<SituationalActions>,
+    pub midfielder_left: Vec<SituationalActions>,
+    pub midfielder_right: Vec<SituationalActions>,
+    pub replacement_keeper: Vec<SituationalActions>,
+    pub searcher: Vec<SituationalActions>,
+    pub striker: Vec<SituationalActions>,
+    pub striker_supporter: Vec<SituationalActions>,
+}
+
+impl ActionListsParameters {
+    pub fn role(&self, role: Role) -> &[SituationalActions] {
+        match role {
+            Role::DefenderLeft => &self.defender_left,
+            Role::DefenderRight => &self.defender_right,
+            Role::Keeper => &self.keeper,
+            Role::Loser => &self.loser,
+            Role::MidfielderLeft => &self.midfielder_left,
+            Role::MidfielderRight => &self.midfielder_right,
+            Role::ReplacementKeeper => &self.replacement_keeper,
+            Role::Searcher => &self.searcher,
+            Role::Striker => &self.striker,
+            Role::StrikerSupporter => &self.striker_supporter,
+        }
+    }
+}
+
+/// Actions of a role, only the first entry whose situation applies is used
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SituationalActions {
+    pub situation: GameSituation,
+    pub actions: Vec<Action>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum GameSituation {
+    Always,
+    /// The ball may be played, also if the game state is unknown
+    BallIsFree,
+    /// Ready for a kick-off of the own team
+    OwnKickOff,
+    /// Ready for a penalty kick of the own team
+    OwnPenaltyKick,
+    /// Ready for a penalty kick of the opponent team
+    OpponentPenaltyKick,
+    PenaltyShootout,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
 pub struct LookActionParameters {
     pub angle_threshold: f32,
@@ -80,9 +140,9 @@ pub struct SearchParameters {
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
 pub struct InWalkKicksParameters {
-    pub forward: InWalkKickInfoParameters,
-    pub turn: InWalkKickInfoParameters,
-    pub side: InWalkKickInfoParameters,
+    pub forward: KickInfoParameters,
+    pub turn: KickInfoParameters,
+    pub side: KickInfoParameters,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
@@ -95,7 +155,7 @@ pub struct FindKickTargetsParameters {
 }
 
 impl Index<KickVariant> for InWalkKicksParameters {
-    type Output = InWalkKickInfoParameters;
+    type Output = KickInfoParameters;
 
     fn index(&self, variant: KickVariant) -> &Self::Output {
         match variant {
@@ -106,8 +166,39 @@ impl Index<KickVariant> for InWalkKicksParameters {
     }
 }
 
+/// Passes are played to teammates whose lane from the ball is not blocked by other obstacles
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct PassingParameters {
+    pub enable: bool,
+    pub distance: Range<f32>,
+    /// Minimum distance the receiver has to be closer to the opponent goal line than the ball
+    pub minimum_forward_progress: f32,
+    /// Reduction of the kick pose cost of passes compared to other kicks
+    pub preference: f32,
+    pub strength: f32,
+}
+
+/// Standing kicks are only chosen for kick targets at least `minimum_target_distance` away
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct StandingKicksParameters {
+    pub forward: KickInfoParameters,
+    pub outer: KickInfoParameters,
+    pub minimum_target_distance: f32,
+}
+
+impl Index<StandingKickVariant> for StandingKicksParameters {
+    type Output = KickInfoParameters;
+
+    fn index(&self, variant: StandingKickVariant) -> &Self::Output {
+        match variant {
+            StandingKickVariant::Forward => &self.forward,
+            StandingKickVariant::Outer => &self.outer,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
-pub struct InWalkKickInfoParameters {
+pub struct KickInfoParameters {
     pub offset: Vector2<f32>,
     pub shot_angle: f32,
     pub reached_thresholds: Vector3<f32>,
@@ -145,6 +236,18 @@ pub struct InterceptBallParameters {
     pub maximum_intercept_distance: f32,
 }
 
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct CatchParameters {
+    pub minimum_ball_velocity_towards_goal: f32,
+    /// Added to both sides of the goal when predicting whether the ball crosses the goal line
+    pub goal_margin: f32,
+    /// Balls passing closer than this to the robot are caught with a squat instead of sideways
+    pub squat_catch_width: f32,
+    pub maximum_catch_distance: f32,
+    /// Catching earlier would leave the goal before the ball arrives
+    pub maximum_time_to_reach_robot: f32,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
 pub struct PathPlanningParameters {
     pub arc_walking_speed: f32,
@@ -262,6 +365,61 @@ pub struct SplNetworkParameters {
     pub striker_trusts_team_ball: Duration,
 }
 
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, SerializeHierarchy,
+)]
+pub enum RoleAssignmentMode {
+    #[default]
+    StateMachine,
+    /// Assigns roles to all teammates such that the sum of their costs is minimal
+    Utility,
+}
+
+/// All costs are measured in seconds
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct UtilityRoleAssignmentParameters {
+    pub message_interval: Duration,
+    /// Teammates without a message for this long are not assigned a role
+    pub teammate_timeout: Duration,
+    /// Converts distances to anchors into walking times
+    pub walking_speed: f32,
+    pub fallen_cost: f32,
+    /// Subtracted from the cost of the previously assigned or announced role to prevent oscillations
+    pub role_keeping_bonus: f32,
+    pub maximum_cost: f32,
+    pub anchors: RoleAnchorsParameters,
+}
+
+/// Positions in field coordinates a robot walks to when taking over a role
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct RoleAnchorsParameters {
+    pub defender_left: Point2<f32>,
+    pub defender_right: Point2<f32>,
+    pub keeper: Point2<f32>,
+    pub loser: Point2<f32>,
+    pub midfielder_left: Point2<f32>,
+    pub midfielder_right: Point2<f32>,
+    pub searcher: Point2<f32>,
+    pub striker: Point2<f32>,
+    pub striker_supporter: Point2<f32>,
+}
+
+impl RoleAnchorsParameters {
+    pub fn role(&self, role: Role) -> Point2<f32> {
+        match role {
+            Role::DefenderLeft => self.defender_left,
+            Role::DefenderRight => self.defender_right,
+            Role::Keeper | Role::ReplacementKeeper => self.keeper,
+            Role::Loser => self.loser,
+            Role::MidfielderLeft => self.midfielder_left,
+            Role::MidfielderRight => self.midfielder_right,
+            Role::Searcher => self.searcher,
+            Role::Striker => self.striker,
+            Role::StrikerSupporter => self.striker_supporter,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
 pub enum MedianModeParameters {
     #[default]
@@ -325,6 +483,11 @@ pub struct ObstacleFilterParameters {
     pub network_robot_measurement_noise: Vector2<f32>,
     pub initial_covariance: Vector2<f32>,
     pub measurement_count_threshold: usize,
+    /// Minimum number of jersey or network measurements of a team before an obstacle is assigned
+    /// to it
+    pub minimum_team_measurement_count: usize,
+    /// Minimum fraction of all team measurements the assigned team has to reach
+    pub minimum_team_measurement_fraction: f32,
     pub use_feet_detection_measurements: bool,
     pub use_robot_detection_measurements: bool,
     pub use_sonar_measurements: bool,
@@ -345,6 +508,19 @@ pub struct FallStateEstimationParameters {
     pub falling_angle_threshold_forward: Vector2<f32>,
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct CameraCalibrationParameters {
+    pub head_positions: Vec<HeadJoints<f32>>,
+    pub settle_duration: Duration,
+    pub capture_duration: Duration,
+    pub minimum_number_of_measurements: usize,
+    /// Measurements are evenly subsampled to at most this many before solving, which bounds the
+    /// duration of the solver running in the Control cycle
+    pub maximum_number_of_measurements: usize,
+    /// Maximum angle between a projected line and the expected field line direction in radians
+    pub maximum_line_angle_deviation: f32,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
 pub struct CameraMatrixParameters {
     pub extrinsic_rotations: Vector3<f32>,
@@ -364,3 +540,130 @@ pub struct FallProtectionParameters {
     pub arm_stiffness: f32,
     pub leg_stiffness: f32,
 }
+
+/// Search over the whole field which adds pose hypotheses after the existing ones stopped matching
+/// the observed lines
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct GlobalRelocalizationParameters {
+    pub enable: bool,
+    /// Low-pass factor of the fraction of good matches of the best hypothesis
+    pub match_quality_low_pass_factor: f32,
+    /// Relocalization is triggered if the filtered match quality drops below this value
+    pub trigger_match_quality: f32,
+    pub minimum_number_of_lines: usize,
+    /// Distance in meters between initial particles
+    pub grid_resolution: f32,
+    pub number_of_orientations: usize,
+    pub number_of_particles: usize,
+    pub number_of_iterations: usize,
+    /// Standard deviation in meters of the distance between measured lines and field marks
+    pub measurement_noise: f32,
+    /// Maximum perturbation of resampled particles in x, y and orientation
+    pub resampling_noise: Vector3<f32>,
+    pub number_of_hypotheses: usize,
+    /// Minimum distance in meters between the returned hypotheses
+    pub minimum_hypothesis_distance: f32,
+    /// Seed of the random number generator used for resampling
+    pub seed: u64,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct JerseyClassificationParameters {
+    pub enable: bool,
+    /// Horizontal extent of the jersey relative to the bounding box, 0.0 is its left border
+    pub horizontal_range: Range<f32>,
+    /// Vertical extent of the jersey relative to the bounding box, 0.0 is its top border
+    pub vertical_range: Range<f32>,
+    /// Distance in pixels between sampled pixels in both directions
+    pub sample_stride: usize,
+    /// Factor applied to the squared luminance difference, which is less reliable than chroma
+    pub luminance_weight: f32,
+    /// Maximum weighted distance of a sample to a reference color to vote for it
+    pub maximum_color_distance: f32,
+    /// Minimum fraction of all samples that have to vote for the winning color
+    pub minimum_matching_fraction: f32,
+    pub reference_colors: JerseyReferenceColors,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct JerseyReferenceColors {
+    #[serialize_hierarchy(leaf)]
+    pub blue: YCbCr444,
+    #[serialize_hierarchy(leaf)]
+    pub red: YCbCr444,
+    #[serialize_hierarchy(leaf)]
+    pub yellow: YCbCr444,
+    #[serialize_hierarchy(leaf)]
+    pub black: YCbCr444,
+    #[serialize_hierarchy(leaf)]
+    pub white: YCbCr444,
+    #[serialize_hierarchy(leaf)]
+    pub green: YCbCr444,
+    #[serialize_hierarchy(leaf)]
+    pub orange: YCbCr444,
+    #[serialize_hierarchy(leaf)]
+    pub purple: YCbCr444,
+    #[serialize_hierarchy(leaf)]
+    pub brown: YCbCr444,
+    #[serialize_hierarchy(leaf)]
+    pub gray: YCbCr444,
+}
+
+impl Index<TeamColor> for JerseyReferenceColors {
+    type Output = YCbCr444;
+
+    fn index(&self, color: TeamColor) -> &Self::Output {
+        match color {
+            TeamColor::Blue => &self.blue,
+            TeamColor::Red => &self.red,
+            TeamColor::Yellow => &self.yellow,
+            TeamColor::Black => &self.black,
+            TeamColor::White => &self.white,
+            TeamColor::Green => &self.green,
+            TeamColor::Orange => &self.orange,
+            TeamColor::Purple => &self.purple,
+            TeamColor::Brown => &self.brown,
+            TeamColor::Gray => &self.gray,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct GoalPostDetectionParameters {
+    pub enable: bool,
+    pub minimum_luminance: u8,
+    /// Maximum summed deviation of cb and cr from the neutral value 128
+    pub maximum_chromaticity: u8,
+    /// Minimum number of pixels a post segment has to reach above the field border
+    pub minimum_height_above_field_border: f32,
+    /// Maximum horizontal distance in pixels between scan lines of the same post
+    pub maximum_scan_line_gap: f32,
+    /// Values below two are raised to two, since the width of a single scan line is unknown
+    pub minimum_number_of_scan_lines: usize,
+    /// Maximum width of a post in ground coordinates in meters
+    pub maximum_width: f32,
+    /// Standard deviation in pixels of the detected foot point
+    pub pixel_standard_deviation: Vector2<f32>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct LineIntersectionDetectionParameters {
+    pub enable: bool,
+    /// Maximum deviation in radians of the angle between two lines from a right angle
+    pub maximum_angle_deviation_from_orthogonal: f32,
+    /// Maximum distance in meters of the intersection point to each of the line segments
+    pub maximum_distance_to_segment: f32,
+    /// Lines with an end point closer than this distance in meters to the intersection point
+    /// are considered to end there
+    pub end_point_distance: f32,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct PenaltyMarkDetectionParameters {
+    pub enable: bool,
+    /// Allowed length in meters of a segment projected to the ground
+    pub projected_segment_length: Range<f32>,
+    pub maximum_cluster_distance: f32,
+    pub minimum_number_of_segments: usize,
+    pub maximum_distance_to_robot: f32,
+}
@@ -0,0 +1,8 @@
+use nalgebra::Point2;
+use serde::{Deserialize, Serialize};
+use serialize_hierarchy::SerializeHierarchy;
+
+#[derive(Clone, Default, Debug, Deserialize, Serialize, SerializeHierarchy)]
+pub struct PenaltyMarks {
+    pub positions_in_robot: Vec<Point2<f32>>,
+}
@@ -1,4 +1,4 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use nalgebra::{Isometry2, Point2, Vector2};
 use serde::{Deserialize, Serialize};
@@ -24,6 +24,7 @@ pub struct WorldState {
     pub kick_decisions: Option<Vec<KickDecision>>,
     pub instant_kick_decisions: Option<Vec<KickDecision>>,
     pub robot: RobotState,
+    pub teammates: Vec<TeammateState>,
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, SerializeHierarchy)]
@@ -58,3 +59,16 @@ pub struct RobotState {
     pub has_ground_contact: bool,
     pub player_number: PlayerNumber,
 }
+
+/// Latest state of another robot of the own team, as received via team messages
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, SerializeHierarchy)]
+pub struct TeammateState {
+    pub player_number: PlayerNumber,
+    pub role: Role,
+    pub robot_to_field: Isometry2<f32>,
+    /// Only announced by strikers, unless roles are assigned by utility where every robot sends
+    pub walk_target: Option<Point2<f32>>,
+    pub fallen: bool,
+    pub time_to_reach_kick_position: Option<Duration>,
+    pub last_update: SystemTime,
+}
@@ -0,0 +1,297 @@
+use color_eyre::Result;
+use context_attribute::context;
+use framework::{AdditionalOutput, MainOutput};
+use nalgebra::{point, vector, Matrix2, Point2, Vector2};
+use projection::Projection;
+use serde::{Deserialize, Serialize};
+use types::{
+    camera_matrix::CameraMatrix,
+    color::{Intensity, YCbCr444},
+    field_border::FieldBorder,
+    goal_post::GoalPost,
+    image_segments::{ImageSegments, ScanLine},
+    parameters::GoalPostDetectionParameters,
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct GoalPostDetection {}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    foot_points: AdditionalOutput<Vec<Point2<f32>>, "goal_post_detection.foot_points">,
+
+    parameters: Parameter<GoalPostDetectionParameters, "goal_post_detection.$cycler_instance">,
+
+    camera_matrix: RequiredInput<Option<CameraMatrix>, "camera_matrix?">,
+    field_border: RequiredInput<Option<FieldBorder>, "field_border?">,
+    image_segments: Input<ImageSegments, "image_segments">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub goal_posts: MainOutput<Option<Vec<GoalPost>>>,
+}
+
+impl GoalPostDetection {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {})
+    }
+
+    pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
+        let parameters = context.parameters;
+        if !parameters.enable {
+            return Ok(MainOutputs::default());
+        }
+
+        let foot_points = find_foot_points(
+            &context.image_segments.scan_grid.vertical_scan_lines,
+            context.field_border,
+            parameters,
+        );
+        context
+            .foot_points
+            .fill_if_subscribed(|| foot_points.clone());
+
+        let goal_posts = cluster_foot_points(&foot_points, parameters.maximum_scan_line_gap)
+            .into_iter()
+            .filter(|cluster| {
+                has_enough_scan_lines(cluster, parameters.minimum_number_of_scan_lines)
+            })
+            .filter_map(|cluster| {
+                goal_post_from_cluster(&cluster, context.camera_matrix, parameters)
+            })
+            .collect();
+        Ok(MainOutputs {
+            goal_posts: Some(goal_posts).into(),
+        })
+    }
+}
+
+/// Lowest points of bright, uncolored segment runs reaching from above the field border into the
+/// field, at most one per scan line
+fn find_foot_points(
+    scan_lines: &[ScanLine],
+    field_border: &FieldBorder,
+    parameters: &GoalPostDetectionParameters,
+) -> Vec<Point2<f32>> {
+    scan_lines
+        .iter()
+        .filter_map(|scan_line| {
+            let position = scan_line.position as f32;
+            let border_height = field_border_height(field_border, position)?;
+            let mut runs: Vec<(u16, u16)> = Vec::new();
+            for segment in scan_line
+                .segments
+                .iter()
+                .filter(|segment| is_post_colored(segment.color, segment.field_color, parameters))
+            {
+                match runs.last_mut() {
+                    Some((_, end)) if *end == segment.start => *end = segment.end,
+                    _ => runs.push((segment.start, segment.end)),
+                }
+            }
+            runs.into_iter()
+                .filter(|(start, end)| {
+                    (*start as f32) < border_height - parameters.minimum_height_above_field_border
+                        && *end as f32 > border_height
+                })
+                .map(|(_, end)| point![position, end as f32])
+                .last()
+        })
+        .collect()
+}
+
+/// Image row of the field border at the given column, points below are inside the field
+fn field_border_height(field_border: &FieldBorder, column: f32) -> Option<f32> {
+    field_border
+        .border_lines
+        .iter()
+        .map(|line| line.0.y + (column - line.0.x) * line.slope())
+        .reduce(f32::max)
+}
+
+fn is_post_colored(
+    color: YCbCr444,
+    field_color: Intensity,
+    parameters: &GoalPostDetectionParameters,
+) -> bool {
+    let chromaticity = color.cb.abs_diff(128) as u16 + color.cr.abs_diff(128) as u16;
+    field_color == Intensity::Low
+        && color.y >= parameters.minimum_luminance
+        && chromaticity <= parameters.maximum_chromaticity as u16
+}
+
+fn cluster_foot_points(foot_points: &[Point2<f32>], maximum_gap: f32) -> Vec<Vec<Point2<f32>>> {
+    let mut clusters: Vec<Vec<Point2<f32>>> = Vec::new();
+    for foot_point in foot_points {
+        match clusters.last_mut() {
+            Some(cluster) if foot_point.x - cluster.last().unwrap().x <= maximum_gap => {
+                cluster.push(*foot_point)
+            }
+            _ => clusters.push(vec![*foot_point]),
+        }
+    }
+    clusters
+}
+
+/// A cluster needs at least two scan lines, otherwise its width cannot be checked and any bright
+/// column crossing the field border, e.g. a leg, would be accepted as a post
+fn has_enough_scan_lines(cluster: &[Point2<f32>], minimum_number_of_scan_lines: usize) -> bool {
+    cluster.len() >= minimum_number_of_scan_lines.max(2)
+}
+
+fn goal_post_from_cluster(
+    cluster: &[Point2<f32>],
+    camera_matrix: &CameraMatrix,
+    parameters: &GoalPostDetectionParameters,
+) -> Option<GoalPost> {
+    let number_of_points = cluster.len() as f32;
+    let position_in_image = Point2::from(
+        cluster
+            .iter()
+            .map(|point| point.coords)
+            .sum::<Vector2<f32>>()
+            / number_of_points,
+    );
+    let left = camera_matrix
+        .pixel_to_ground(point![cluster.first()?.x, position_in_image.y])
+        .ok()?;
+    let right = camera_matrix
+        .pixel_to_ground(point![cluster.last()?.x, position_in_image.y])
+        .ok()?;
+    if (right - left).norm() > parameters.maximum_width {
+        return None;
+    }
+
+    let position = camera_matrix.pixel_to_ground(position_in_image).ok()?;
+    let covariance = ground_covariance(
+        camera_matrix,
+        position_in_image,
+        parameters.pixel_standard_deviation,
+    )?;
+    Some(GoalPost {
+        position,
+        covariance,
+        position_in_image,
+    })
+}
+
+/// Propagates the pixel uncertainty into ground coordinates using a numerically derived Jacobian
+/// of the projection, whose columns are already scaled by the standard deviations
+fn ground_covariance(
+    camera_matrix: &CameraMatrix,
+    pixel: Point2<f32>,
+    pixel_standard_deviation: Vector2<f32>,
+) -> Option<Matrix2<f32>> {
+    let column = |offset: Vector2<f32>| -> Option<Vector2<f32>> {
+        let positive = camera_matrix.pixel_to_ground(pixel + offset).ok()?;
+        let negative = camera_matrix.pixel_to_ground(pixel - offset).ok()?;
+        Some((positive - negative) / 2.0)
+    };
+    let scaled_jacobian = Matrix2::from_columns(&[
+        column(vector![pixel_standard_deviation.x, 0.0])?,
+        column(vector![0.0, pixel_standard_deviation.y])?,
+    ]);
+    Some(scaled_jacobian * scaled_jacobian.transpose())
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{
+        image_segments::{EdgeType, Segment},
+        line::Line,
+    };
+
+    use super::*;
+
+    fn parameters() -> GoalPostDetectionParameters {
+        GoalPostDetectionParameters {
+            enable: true,
+            minimum_luminance: 150,
+            maximum_chromaticity: 30,
+            minimum_height_above_field_border: 20.0,
+            maximum_scan_line_gap: 8.0,
+            minimum_number_of_scan_lines: 2,
+            maximum_width: 0.3,
+            pixel_standard_deviation: vector![2.0, 2.0],
+        }
+    }
+
+    fn segment(start: u16, end: u16, luminance: u8) -> Segment {
+        Segment {
+            start,
+            end,
+            start_edge_type: EdgeType::Rising,
+            end_edge_type: EdgeType::Falling,
+            color: YCbCr444::new(luminance, 128, 128),
+            field_color: Intensity::Low,
+        }
+    }
+
+    fn horizontal_field_border(height: f32) -> FieldBorder {
+        FieldBorder {
+            border_lines: vec![Line(point![0.0, height], point![640.0, height])],
+        }
+    }
+
+    #[test]
+    fn post_crossing_the_field_border_yields_its_lowest_point() {
+        let scan_lines = [
+            ScanLine {
+                position: 100,
+                segments: vec![
+                    segment(0, 40, 50),
+                    segment(40, 90, 200),
+                    segment(90, 120, 220),
+                ],
+            },
+            ScanLine {
+                position: 108,
+                segments: vec![segment(0, 40, 50), segment(40, 70, 200)],
+            },
+            ScanLine {
+                position: 116,
+                segments: vec![segment(0, 85, 50), segment(85, 130, 200)],
+            },
+        ];
+
+        let foot_points =
+            find_foot_points(&scan_lines, &horizontal_field_border(80.0), &parameters());
+
+        assert_eq!(foot_points, vec![point![100.0, 120.0]]);
+    }
+
+    #[test]
+    fn distant_foot_points_form_separate_clusters() {
+        let foot_points = [
+            point![100.0, 120.0],
+            point![108.0, 121.0],
+            point![200.0, 118.0],
+        ];
+
+        let clusters = cluster_foot_points(&foot_points, 8.0);
+
+        assert_eq!(
+            clusters,
+            vec![
+                vec![point![100.0, 120.0], point![108.0, 121.0]],
+                vec![point![200.0, 118.0]]
+            ]
+        );
+    }
+
+    #[test]
+    fn single_scan_line_clusters_are_never_posts() {
+        let single = [point![100.0, 120.0]];
+        let pair = [point![100.0, 120.0], point![108.0, 121.0]];
+
+        assert!(!has_enough_scan_lines(&single, 1));
+        assert!(!has_enough_scan_lines(&single, 0));
+        assert!(has_enough_scan_lines(&pair, 1));
+        assert!(!has_enough_scan_lines(&pair, 3));
+    }
+}
@@ -0,0 +1,211 @@
+use std::ops::Range;
+
+use spl_network_messages::{Team, TeamColor};
+use types::{
+    color::YCbCr444, detected_robots::BoundingBox, game_controller_state::TeamColors,
+    parameters::JerseyClassificationParameters, ycbcr422_image::YCbCr422Image,
+};
+
+/// Estimates the team of a detected robot from the jersey region of its bounding box.
+///
+/// Every sampled pixel votes for the closest jersey color currently in play. The team wearing the
+/// color with the most votes is returned if enough samples voted for it. A color worn by both
+/// teams is ambiguous and results in an uncertain team.
+pub fn classify_team(
+    image: &YCbCr422Image,
+    bounding_box: &BoundingBox,
+    hulks_colors: TeamColors,
+    opponent_colors: TeamColors,
+    parameters: &JerseyClassificationParameters,
+) -> Team {
+    let mut colors: Vec<TeamColor> = Vec::new();
+    for color in [
+        hulks_colors.field_player,
+        hulks_colors.goal_keeper,
+        opponent_colors.field_player,
+        opponent_colors.goal_keeper,
+    ] {
+        if !colors.contains(&color) {
+            colors.push(color);
+        }
+    }
+
+    let samples = jersey_samples(image, bounding_box, parameters);
+    let mut votes = vec![0; colors.len()];
+    for sample in &samples {
+        let closest_color = colors
+            .iter()
+            .map(|color| {
+                color_distance(
+                    *sample,
+                    parameters.reference_colors[*color],
+                    parameters.luminance_weight,
+                )
+            })
+            .enumerate()
+            .filter(|(_, distance)| *distance <= parameters.maximum_color_distance)
+            .min_by(|(_, left), (_, right)| left.total_cmp(right));
+        if let Some((index, _)) = closest_color {
+            votes[index] += 1;
+        }
+    }
+
+    let Some((winning_color, number_of_votes)) = colors
+        .into_iter()
+        .zip(votes)
+        .max_by_key(|(_, number_of_votes)| *number_of_votes)
+    else {
+        return Team::Uncertain;
+    };
+    if samples.is_empty()
+        || (number_of_votes as f32) < parameters.minimum_matching_fraction * samples.len() as f32
+    {
+        return Team::Uncertain;
+    }
+    match (
+        hulks_colors.contains(winning_color),
+        opponent_colors.contains(winning_color),
+    ) {
+        (true, false) => Team::Hulks,
+        (false, true) => Team::Opponent,
+        _ => Team::Uncertain,
+    }
+}
+
+fn jersey_samples(
+    image: &YCbCr422Image,
+    bounding_box: &BoundingBox,
+    parameters: &JerseyClassificationParameters,
+) -> Vec<YCbCr444> {
+    let top_left = bounding_box.center - bounding_box.size / 2.0;
+    let pixel_range = |relative_range: &Range<f32>, offset: f32, size: f32| {
+        let start = (offset + relative_range.start * size).max(0.0) as u32;
+        let end = (offset + relative_range.end * size).max(0.0) as u32;
+        start..end
+    };
+    let columns = pixel_range(
+        &parameters.horizontal_range,
+        top_left.x,
+        bounding_box.size.x,
+    );
+    let rows = pixel_range(&parameters.vertical_range, top_left.y, bounding_box.size.y);
+    let stride = parameters.sample_stride.max(1);
+    rows.step_by(stride)
+        .flat_map(|y| {
+            columns
+                .clone()
+                .step_by(stride)
+                .filter_map(move |x| image.try_at(x, y))
+        })
+        .collect()
+}
+
+fn color_distance(sample: YCbCr444, reference: YCbCr444, luminance_weight: f32) -> f32 {
+    let difference = |left: u8, right: u8| left as f32 - right as f32;
+    (luminance_weight * difference(sample.y, reference.y).powi(2)
+        + difference(sample.cb, reference.cb).powi(2)
+        + difference(sample.cr, reference.cr).powi(2))
+    .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{point, vector};
+    use types::color::YCbCr422;
+
+    use super::*;
+
+    fn parameters() -> JerseyClassificationParameters {
+        JerseyClassificationParameters {
+            enable: true,
+            horizontal_range: 0.25..0.75,
+            vertical_range: 0.3..0.6,
+            sample_stride: 2,
+            luminance_weight: 0.25,
+            maximum_color_distance: 40.0,
+            minimum_matching_fraction: 0.3,
+            reference_colors: Default::default(),
+        }
+    }
+
+    fn parameters_with_reference_colors() -> JerseyClassificationParameters {
+        let mut parameters = parameters();
+        parameters.reference_colors.blue = YCbCr444::new(62, 183, 105);
+        parameters.reference_colors.red = YCbCr444::new(75, 103, 203);
+        parameters.reference_colors.black = YCbCr444::new(30, 128, 128);
+        parameters.reference_colors.yellow = YCbCr444::new(188, 45, 151);
+        parameters
+    }
+
+    fn uniform_image(color: YCbCr444) -> YCbCr422Image {
+        let pixel = YCbCr422 {
+            y1: color.y,
+            cb: color.cb,
+            y2: color.y,
+            cr: color.cr,
+        };
+        YCbCr422Image::from_ycbcr_buffer(32, 64, vec![pixel; 32 * 64])
+    }
+
+    fn bounding_box() -> BoundingBox {
+        BoundingBox {
+            center: point![32.0, 32.0],
+            size: vector![20.0, 40.0],
+            probability: 1.0,
+            distance: 2.0,
+            team: Team::Uncertain,
+        }
+    }
+
+    fn colors(field_player: TeamColor, goal_keeper: TeamColor) -> TeamColors {
+        TeamColors {
+            field_player,
+            goal_keeper,
+        }
+    }
+
+    #[test]
+    fn jersey_color_of_one_team_yields_that_team() {
+        let image = uniform_image(YCbCr444::new(80, 105, 195));
+
+        let team = classify_team(
+            &image,
+            &bounding_box(),
+            colors(TeamColor::Blue, TeamColor::Yellow),
+            colors(TeamColor::Red, TeamColor::Black),
+            &parameters_with_reference_colors(),
+        );
+
+        assert_eq!(team, Team::Opponent);
+    }
+
+    #[test]
+    fn color_worn_by_both_teams_is_uncertain() {
+        let image = uniform_image(YCbCr444::new(30, 128, 128));
+
+        let team = classify_team(
+            &image,
+            &bounding_box(),
+            colors(TeamColor::Blue, TeamColor::Black),
+            colors(TeamColor::Red, TeamColor::Black),
+            &parameters_with_reference_colors(),
+        );
+
+        assert_eq!(team, Team::Uncertain);
+    }
+
+    #[test]
+    fn colors_far_from_all_references_are_uncertain() {
+        let image = uniform_image(YCbCr444::new(230, 128, 128));
+
+        let team = classify_team(
+            &image,
+            &bounding_box(),
+            colors(TeamColor::Blue, TeamColor::Yellow),
+            colors(TeamColor::Red, TeamColor::Black),
+            &parameters_with_reference_colors(),
+        );
+
+        assert_eq!(team, Team::Uncertain);
+    }
+}
@@ -3,10 +3,14 @@ pub mod camera_matrix_extractor;
 pub mod feet_detection;
 pub mod field_border_detection;
 pub mod field_color_detection;
+pub mod goal_post_detection;
 pub mod image_receiver;
 pub mod image_segmenter;
+mod jersey_classifier;
 pub mod limb_projector;
 pub mod line_detection;
+pub mod line_intersection_detection;
+pub mod penalty_mark_detection;
 pub mod perspective_grid_candidates_provider;
 mod ransac;
 pub mod robot_detection;
@@ -8,6 +8,7 @@ use ordered_float::NotNan;
 use projection::Projection;
 use serde::{Deserialize, Serialize};
 use types::{
+    camera_calibration::CalibrationMeasurement,
     camera_matrix::CameraMatrix,
     filtered_segments::FilteredSegments,
     image_segments::{EdgeType, Segment},
@@ -56,6 +57,7 @@ pub struct CycleContext {
 #[context]
 #[derive(Default)]
 pub struct MainOutputs {
+    pub calibration_measurement: MainOutput<Option<CalibrationMeasurement>>,
     pub line_data: MainOutput<Option<LineData>>,
 }
 
@@ -81,6 +83,7 @@ impl LineDetection {
         }
         let mut ransac = Ransac::new(line_points);
         let mut lines_in_robot = Vec::new();
+        let mut lines_in_image = Vec::new();
         for _ in 0..*context.maximum_number_of_lines {
             if ransac.unused_points.len() < *context.minimum_number_of_points_on_line {
                 break;
@@ -185,18 +188,22 @@ impl LineDetection {
             }
 
             lines_in_robot.push(line_in_robot);
-            if context.lines_in_image.is_subscribed() {
-                image_lines
-                    .lines
-                    .push(Line(start_point_in_image, end_point_in_image));
-            }
+            lines_in_image.push(Line(start_point_in_image, end_point_in_image));
         }
         let line_data = LineData {
             lines_in_robot,
             used_vertical_filtered_segments,
         };
+        if context.lines_in_image.is_subscribed() {
+            image_lines.lines = lines_in_image.clone();
+        }
         context.lines_in_image.fill_if_subscribed(|| image_lines);
         Ok(MainOutputs {
+            calibration_measurement: Some(CalibrationMeasurement {
+                camera_matrix: context.camera_matrix.clone(),
+                lines_in_image,
+            })
+            .into(),
             line_data: Some(line_data).into(),
         })
     }
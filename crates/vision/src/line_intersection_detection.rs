@@ -0,0 +1,173 @@
+use color_eyre::Result;
+use context_attribute::context;
+use framework::MainOutput;
+use itertools::Itertools;
+use nalgebra::{distance, Point2};
+use serde::{Deserialize, Serialize};
+use types::{
+    line::Line2,
+    line_data::LineData,
+    line_intersections::{IntersectionKind, LineIntersection, LineIntersections},
+    parameters::LineIntersectionDetectionParameters,
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct LineIntersectionDetection {}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    parameters: Parameter<
+        LineIntersectionDetectionParameters,
+        "line_intersection_detection.$cycler_instance",
+    >,
+
+    line_data: RequiredInput<Option<LineData>, "line_data?">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub line_intersections: MainOutput<Option<LineIntersections>>,
+}
+
+impl LineIntersectionDetection {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {})
+    }
+
+    pub fn cycle(&mut self, context: CycleContext) -> Result<MainOutputs> {
+        if !context.parameters.enable {
+            return Ok(MainOutputs::default());
+        }
+
+        let intersections_in_robot = context
+            .line_data
+            .lines_in_robot
+            .iter()
+            .tuple_combinations()
+            .filter_map(|(first, second)| intersect(*first, *second, context.parameters))
+            .collect();
+        Ok(MainOutputs {
+            line_intersections: Some(LineIntersections {
+                intersections_in_robot,
+            })
+            .into(),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum SegmentContact {
+    EndPoint,
+    Interior,
+}
+
+fn intersect(
+    first: Line2,
+    second: Line2,
+    parameters: &LineIntersectionDetectionParameters,
+) -> Option<LineIntersection> {
+    if first.signed_acute_angle_to_orthogonal(second).abs()
+        > parameters.maximum_angle_deviation_from_orthogonal
+    {
+        return None;
+    }
+    let position = first.intersection(&second);
+    let kind = match (
+        segment_contact(first, position, parameters)?,
+        segment_contact(second, position, parameters)?,
+    ) {
+        (SegmentContact::EndPoint, SegmentContact::EndPoint) => IntersectionKind::L,
+        (SegmentContact::EndPoint, SegmentContact::Interior)
+        | (SegmentContact::Interior, SegmentContact::EndPoint) => IntersectionKind::T,
+        (SegmentContact::Interior, SegmentContact::Interior) => IntersectionKind::X,
+    };
+    Some(LineIntersection { position, kind })
+}
+
+fn segment_contact(
+    line: Line2,
+    intersection: Point2<f32>,
+    parameters: &LineIntersectionDetectionParameters,
+) -> Option<SegmentContact> {
+    if line.squared_distance_to_segment(intersection).sqrt()
+        > parameters.maximum_distance_to_segment
+    {
+        return None;
+    }
+    let distance_to_end_point =
+        distance(&line.0, &intersection).min(distance(&line.1, &intersection));
+    if distance_to_end_point < parameters.end_point_distance {
+        Some(SegmentContact::EndPoint)
+    } else {
+        Some(SegmentContact::Interior)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::point;
+    use types::line::Line;
+
+    use super::*;
+
+    fn parameters() -> LineIntersectionDetectionParameters {
+        LineIntersectionDetectionParameters {
+            enable: true,
+            maximum_angle_deviation_from_orthogonal: 0.2,
+            maximum_distance_to_segment: 0.2,
+            end_point_distance: 0.3,
+        }
+    }
+
+    #[test]
+    fn orthogonal_lines_are_classified_by_their_end_points() {
+        let horizontal = Line(point![0.0, 0.0], point![2.0, 0.0]);
+
+        let corner = intersect(
+            horizontal,
+            Line(point![0.1, 0.0], point![0.0, 1.0]),
+            &parameters(),
+        )
+        .unwrap();
+        assert_eq!(corner.kind, IntersectionKind::L);
+
+        let junction = intersect(
+            horizontal,
+            Line(point![1.0, 0.1], point![1.0, 1.5]),
+            &parameters(),
+        )
+        .unwrap();
+        assert_eq!(junction.kind, IntersectionKind::T);
+        assert!(distance(&junction.position, &point![1.0, 0.0]) < 1e-5);
+
+        let crossing = intersect(
+            horizontal,
+            Line(point![1.0, -1.0], point![1.0, 1.0]),
+            &parameters(),
+        )
+        .unwrap();
+        assert_eq!(crossing.kind, IntersectionKind::X);
+    }
+
+    #[test]
+    fn distant_or_parallel_lines_do_not_intersect() {
+        let horizontal = Line(point![0.0, 0.0], point![2.0, 0.0]);
+
+        assert!(intersect(
+            horizontal,
+            Line(point![1.0, 1.0], point![1.0, 2.0]),
+            &parameters()
+        )
+        .is_none());
+        assert!(intersect(
+            horizontal,
+            Line(point![0.0, 1.0], point![2.0, 1.2]),
+            &parameters()
+        )
+        .is_none());
+    }
+}
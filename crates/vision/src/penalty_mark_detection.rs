@@ -0,0 +1,176 @@
+use color_eyre::Result;
+use context_attribute::context;
+use filtering::mean_clustering::MeanClustering;
+use framework::{AdditionalOutput, MainOutput};
+use nalgebra::{distance, point, Point2};
+use projection::Projection;
+use serde::{Deserialize, Serialize};
+use types::{
+    ball::Ball,
+    camera_matrix::CameraMatrix,
+    detected_feet::CountedCluster,
+    filtered_segments::FilteredSegments,
+    image_segments::{EdgeType, ScanLine},
+    line_data::LineData,
+    parameters::PenaltyMarkDetectionParameters,
+    penalty_marks::PenaltyMarks,
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct PenaltyMarkDetection {}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    candidates_in_ground:
+        AdditionalOutput<Vec<Point2<f32>>, "penalty_mark_detection.candidates_in_ground">,
+
+    parameters:
+        Parameter<PenaltyMarkDetectionParameters, "penalty_mark_detection.$cycler_instance">,
+
+    balls: Input<Option<Vec<Ball>>, "balls?">,
+    camera_matrix: RequiredInput<Option<CameraMatrix>, "camera_matrix?">,
+    filtered_segments: Input<FilteredSegments, "filtered_segments">,
+    line_data: RequiredInput<Option<LineData>, "line_data?">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub penalty_marks: MainOutput<Option<PenaltyMarks>>,
+}
+
+impl PenaltyMarkDetection {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {})
+    }
+
+    pub fn cycle(&mut self, mut context: CycleContext) -> Result<MainOutputs> {
+        let parameters = context.parameters;
+        if !parameters.enable {
+            return Ok(MainOutputs::default());
+        }
+
+        let candidates_in_ground: Vec<_> = context
+            .filtered_segments
+            .scan_grid
+            .vertical_scan_lines
+            .iter()
+            .flat_map(|scan_line| {
+                find_candidates(
+                    scan_line,
+                    context.camera_matrix,
+                    context.line_data,
+                    context
+                        .balls
+                        .map(|balls| balls.as_slice())
+                        .unwrap_or_default(),
+                    parameters,
+                )
+            })
+            .collect();
+        context
+            .candidates_in_ground
+            .fill_if_subscribed(|| candidates_in_ground.clone());
+
+        let positions_in_robot =
+            cluster(&candidates_in_ground, parameters.maximum_cluster_distance)
+                .into_iter()
+                .filter(|cluster| cluster.samples >= parameters.minimum_number_of_segments)
+                .map(|cluster| cluster.mean)
+                .collect();
+        Ok(MainOutputs {
+            penalty_marks: Some(PenaltyMarks { positions_in_robot }).into(),
+        })
+    }
+}
+
+/// Centers in ground coordinates of bright segments that are enclosed by field color and short
+/// enough to be part of a penalty mark
+fn find_candidates(
+    scan_line: &ScanLine,
+    camera_matrix: &CameraMatrix,
+    line_data: &LineData,
+    balls: &[Ball],
+    parameters: &PenaltyMarkDetectionParameters,
+) -> Vec<Point2<f32>> {
+    let segments = &scan_line.segments;
+    let position = scan_line.position;
+    segments
+        .iter()
+        .enumerate()
+        .filter(|(index, segment)| {
+            let is_enclosed_by_field = (*index == 0 || segments[index - 1].end != segment.start)
+                && segments
+                    .get(index + 1)
+                    .map_or(true, |next| next.start != segment.end);
+            let is_bright = segment.start_edge_type == EdgeType::Rising
+                && segment.end_edge_type == EdgeType::Falling;
+            let is_on_line = line_data
+                .used_vertical_filtered_segments
+                .contains(&point![position, segment.start]);
+            let is_on_ball = balls.iter().any(|ball| {
+                ball.image_location
+                    .contains(point![position as f32, segment.center() as f32])
+            });
+            is_enclosed_by_field && is_bright && !is_on_line && !is_on_ball
+        })
+        .filter_map(|(_, segment)| {
+            let start = camera_matrix
+                .pixel_to_ground(point![position as f32, segment.start as f32])
+                .ok()?;
+            let end = camera_matrix
+                .pixel_to_ground(point![position as f32, segment.end as f32])
+                .ok()?;
+            let center = start + (end - start) / 2.0;
+            let is_within_range = parameters
+                .projected_segment_length
+                .contains(&distance(&start, &end))
+                && center.coords.norm() < parameters.maximum_distance_to_robot;
+            is_within_range.then_some(center)
+        })
+        .collect()
+}
+
+fn cluster(points: &[Point2<f32>], maximum_cluster_distance: f32) -> Vec<CountedCluster> {
+    let mut clusters: Vec<CountedCluster> = Vec::new();
+    for point in points {
+        let nearest_cluster = clusters
+            .iter_mut()
+            .map(|cluster| {
+                let distance = distance(&cluster.mean, point);
+                (cluster, distance)
+            })
+            .filter(|(_, distance)| *distance < maximum_cluster_distance)
+            .min_by(|(_, left_distance), (_, right_distance)| {
+                left_distance.total_cmp(right_distance)
+            });
+        match nearest_cluster {
+            Some((cluster, _)) => cluster.push(*point),
+            None => clusters.push(CountedCluster {
+                mean: *point,
+                samples: 1,
+            }),
+        }
+    }
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn close_candidates_are_merged_into_one_mark() {
+        let candidates = [point![2.0, 0.0], point![2.04, 0.02], point![3.0, 1.0]];
+
+        let clusters = cluster(&candidates, 0.1);
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].samples, 2);
+        assert!(distance(&clusters[0].mean, &point![2.02, 0.01]) < 1e-5);
+        assert_eq!(clusters[1].samples, 1);
+    }
+}
@@ -12,13 +12,18 @@ use itertools::Itertools;
 use nalgebra::{vector, Isometry3, Vector2};
 use projection::Projection;
 use serde::{Deserialize, Serialize};
+use spl_network_messages::Team;
 use types::{
     camera_matrix::CameraMatrix,
-    detected_robots::{BoundingBox, DetectedRobots},
+    detected_robots::{BoundingBox, DetectedRobots, RobotOnGround},
+    game_controller_state::GameControllerState,
     grayscale_image::GrayscaleImage,
+    parameters::JerseyClassificationParameters,
     ycbcr422_image::YCbCr422Image,
 };
 
+use crate::jersey_classifier::classify_team;
+
 const NUMBER_OF_SCALINGS: usize = 4;
 const PARAMETERS_PER_BOX: usize = 6;
 const BOX_SCALINGS: [Vector2<f32>; NUMBER_OF_SCALINGS] = [
@@ -46,6 +51,7 @@ pub struct CycleContext {
     image: Input<YCbCr422Image, "image">,
     camera_matrix: RequiredInput<Option<CameraMatrix>, "camera_matrix?">,
     robot_to_ground: RequiredInput<Option<Isometry3<f32>>, "Control", "robot_to_ground?">,
+    game_controller_state: Input<Option<GameControllerState>, "Control", "game_controller_state?">,
     luminance_image: AdditionalOutput<GrayscaleImage, "robot_detection.luminance_image">,
     object_threshold: Parameter<f32, "robot_detection.$cycler_instance.object_threshold">,
     enable: Parameter<bool, "robot_detection.$cycler_instance.enable">,
@@ -57,6 +63,10 @@ pub struct CycleContext {
         Parameter<f32, "robot_detection.$cycler_instance.lowest_bottom_pixel_position">,
     allowed_projected_robot_height:
         Parameter<Range<f32>, "robot_detection.$cycler_instance.allowed_projected_robot_height">,
+    jersey_classification: Parameter<
+        JerseyClassificationParameters,
+        "robot_detection.$cycler_instance.jersey_classification",
+    >,
 }
 
 #[context]
@@ -119,11 +129,30 @@ impl RobotDetection {
             );
         }
 
+        if let Some(game_controller_state) = context
+            .game_controller_state
+            .filter(|_| context.jersey_classification.enable)
+        {
+            for bounding_box in filtered_detections.iter_mut() {
+                bounding_box.team = classify_team(
+                    context.image,
+                    bounding_box,
+                    game_controller_state.hulks_team_colors,
+                    game_controller_state.opponent_team_colors,
+                    context.jersey_classification,
+                );
+            }
+        }
+
         let on_ground = filtered_detections
             .iter()
             .filter_map(|bounding_box| {
                 let box_bottom = bounding_box.center + vector![0.0, bounding_box.size.y / 2.0];
-                context.camera_matrix.pixel_to_ground(box_bottom).ok()
+                let position = context.camera_matrix.pixel_to_ground(box_bottom).ok()?;
+                Some(RobotOnGround {
+                    position,
+                    team: bounding_box.team,
+                })
             })
             .collect();
 
@@ -319,5 +348,6 @@ fn box_from_network_data(
             .component_div(&grid_size),
         probability,
         distance: distance * OUTPUT_SCALING,
+        team: Team::Uncertain,
     }
 }
@@ -13,15 +13,17 @@ spawn_robot(5)
 spawn_robot(6)
 spawn_robot(7)
 
+assert_goal_before("Hulks", 10000)
+assert_robots_stay_on_field()
+assert_role_invariants()
+
 local game_end_time = 10000
-local goal_scored = false
 
 function on_goal()
     print("Goal scored, resetting ball!")
     print("Ball: " .. inspect(state.ball))
     print("Ball was at x: " .. state.ball.position[1] .. " y: " .. state.ball.position[2])
     state.ball = nil
-    goal_scored = true
     game_end_time = state.cycle_count + 200
 end
 
@@ -57,9 +59,6 @@ function on_cycle()
     end
 
     if state.cycle_count == game_end_time then
-        if not goal_scored then
-          error("No goal was scored!")
-        end
         state.finished = true
     end
 end
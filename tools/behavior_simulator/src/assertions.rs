@@ -0,0 +1,112 @@
+use color_eyre::{eyre::bail, Result};
+use spl_network_messages::Team;
+use types::{filtered_game_state::FilteredGameState, roles::Role};
+
+use crate::state::State;
+
+/// Cycles two robots may share the keeper or striker role while the team negotiates roles
+const MAXIMUM_ROLE_CONFLICT_CYCLES: usize = 100;
+
+#[derive(Clone, Debug)]
+pub enum Assertion {
+    GoalBefore { team: Team, cycle_count: usize },
+    RobotsStayOnField,
+    RoleInvariants { conflicting_cycles: usize },
+    NoCrowdingAtBall { distance: f32 },
+}
+
+impl Assertion {
+    /// Checks the assertion after every cycle
+    pub fn check(&mut self, state: &State) -> Result<()> {
+        match self {
+            Assertion::GoalBefore { team, cycle_count } => {
+                if state.cycle_count >= *cycle_count {
+                    check_goal_before(state, *team, *cycle_count)?;
+                }
+            }
+            Assertion::RobotsStayOnField => {
+                for (player_number, robot) in &state.robots {
+                    if robot.is_penalized {
+                        continue;
+                    }
+                    let field_dimensions = &robot.parameters.field_dimensions;
+                    let position = robot.position();
+                    if position.x.abs()
+                        > field_dimensions.length / 2.0 + field_dimensions.border_strip_width
+                        || position.y.abs()
+                            > field_dimensions.width / 2.0 + field_dimensions.border_strip_width
+                    {
+                        bail!(
+                            "robot {player_number:?} left the field at ({}, {})",
+                            position.x,
+                            position.y
+                        );
+                    }
+                }
+            }
+            Assertion::RoleInvariants { conflicting_cycles } => {
+                if !matches!(state.filtered_game_state, FilteredGameState::Playing { .. }) {
+                    *conflicting_cycles = 0;
+                    return Ok(());
+                }
+                let active_roles = || {
+                    state
+                        .robots
+                        .values()
+                        .filter(|robot| !robot.is_penalized)
+                        .map(|robot| robot.database.main_outputs.role)
+                };
+                let keepers = active_roles().filter(|role| *role == Role::Keeper).count();
+                let strikers = active_roles().filter(|role| *role == Role::Striker).count();
+                if keepers <= 1 && strikers <= 1 {
+                    *conflicting_cycles = 0;
+                    return Ok(());
+                }
+                *conflicting_cycles += 1;
+                if *conflicting_cycles > MAXIMUM_ROLE_CONFLICT_CYCLES {
+                    bail!("{keepers} keepers and {strikers} strikers for more than {MAXIMUM_ROLE_CONFLICT_CYCLES} cycles");
+                }
+            }
+            Assertion::NoCrowdingAtBall { distance } => {
+                let Some(ball) = &state.ball else {
+                    return Ok(());
+                };
+                let robots_at_ball: Vec<_> = state
+                    .robots
+                    .iter()
+                    .filter(|(_, robot)| {
+                        !robot.is_penalized && (robot.position() - ball.position).norm() < *distance
+                    })
+                    .map(|(player_number, _)| player_number)
+                    .collect();
+                if robots_at_ball.len() > 1 {
+                    bail!("robots {robots_at_ball:?} are closer than {distance}m to the ball");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the assertion once the scenario is finished
+    pub fn check_finished(&self, state: &State) -> Result<()> {
+        match self {
+            Assertion::GoalBefore { team, cycle_count } => {
+                check_goal_before(state, *team, *cycle_count)
+            }
+            Assertion::RobotsStayOnField
+            | Assertion::RoleInvariants { .. }
+            | Assertion::NoCrowdingAtBall { .. } => Ok(()),
+        }
+    }
+}
+
+fn check_goal_before(state: &State, team: Team, cycle_count: usize) -> Result<()> {
+    let has_scored = state
+        .goals
+        .iter()
+        .any(|goal| goal.team == team && goal.cycle_count < cycle_count);
+    if !has_scored {
+        bail!("{team:?} did not score a goal before cycle {cycle_count}");
+    }
+    Ok(())
+}
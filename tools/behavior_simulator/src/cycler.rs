@@ -62,8 +62,10 @@ impl BehaviorCycler {
         .wrap_err("failed to create node `ActiveVision`")?;
         let ball_state_composer = BallStateComposer::new(ball_state_composer::CreationContext {})
             .wrap_err("failed to create node `BallStateComposer`")?;
-        let behavior = Behavior::new(node::CreationContext::new())
-            .wrap_err("failed to create node `Behavior`")?;
+        let behavior = Behavior::new(node::CreationContext::new(
+            &parameters.behavior.action_lists,
+        ))
+        .wrap_err("failed to create node `Behavior`")?;
         let kick_selector = KickSelector::new(kick_selector::CreationContext {})
             .wrap_err("failed to create node `KickSelector`")?;
         let look_around = control::motion::look_around::LookAround::new(
@@ -101,7 +103,7 @@ impl BehaviorCycler {
         own_database: &mut Database,
         cycler_state: &mut CyclerState,
         parameters: &Parameters,
-        incoming_messages: BTreeMap<SystemTime, Vec<&IncomingMessage>>,
+        incoming_messages: BTreeMap<SystemTime, Vec<Option<&IncomingMessage>>>,
     ) -> Result<()> {
         if own_database
             .main_outputs
@@ -146,11 +148,14 @@ impl BehaviorCycler {
                         temporary: Default::default(),
                     },
                     &mut cycler_state.time_to_reach_kick_position,
+                    &mut cycler_state.walk_target,
                     &parameters.field_dimensions,
                     parameters.role_assignment.forced_role.as_ref(),
                     &parameters
                         .role_assignment
                         .keeper_replacementkeeper_switch_time,
+                    &parameters.role_assignment.mode,
+                    &parameters.role_assignment.utility,
                     &parameters.localization.initial_poses,
                     &parameters.behavior.optional_roles,
                     &parameters.player_number,
@@ -162,6 +167,7 @@ impl BehaviorCycler {
             own_database.main_outputs.network_robot_obstacles =
                 main_outputs.network_robot_obstacles.value;
             own_database.main_outputs.role = main_outputs.role.value;
+            own_database.main_outputs.teammates = main_outputs.teammates.value;
         }
         {
             let main_outputs = self
@@ -208,6 +214,7 @@ impl BehaviorCycler {
                             &own_database.main_outputs.obstacles,
                             &parameters.field_dimensions,
                             &parameters.in_walk_kicks,
+                            &parameters.standing_kicks,
                             &parameters.kick_selector.angle_distance_weight,
                             &parameters.kick_selector.max_kick_around_obstacle_angle,
                             &parameters.kick_selector.kick_pose_obstacle_radius,
@@ -257,6 +264,7 @@ impl BehaviorCycler {
                     &own_database.main_outputs.primary_state,
                     &own_database.main_outputs.role,
                     &own_database.main_outputs.position_of_interest,
+                    &own_database.main_outputs.teammates,
                 ))
                 .wrap_err("failed to execute cycle of node `WorldStateComposer`")?;
             own_database.main_outputs.world_state = main_outputs.world_state.value;
@@ -274,11 +282,17 @@ impl BehaviorCycler {
                         &mut own_database.additional_outputs.dribble_path_obstacles,
                     ),
                     AdditionalOutput::new(true, &mut own_database.additional_outputs.active_action),
+                    AdditionalOutput::new(
+                        true,
+                        &mut own_database.additional_outputs.decision_trace,
+                    ),
+                    &mut cycler_state.walk_target,
                     &true,
                     &own_database.main_outputs.world_state,
                     &own_database.main_outputs.cycle_time,
                     &parameters.behavior,
                     &parameters.in_walk_kicks,
+                    &parameters.standing_kicks,
                     &parameters.field_dimensions,
                     &parameters.behavior.lost_ball,
                     &parameters.behavior.intercept_ball,
@@ -1,8 +1,12 @@
 use hardware::{NetworkInterface, RecordingInterface, TimeInterface};
 
+pub mod assertions;
 pub mod cycler;
 pub mod interfake;
+pub mod network_model;
+pub mod report;
 pub mod robot;
+pub mod sensor_model;
 pub mod server;
 pub mod simulator;
 pub mod state;
@@ -2,17 +2,25 @@ use std::{io::stdout, path::PathBuf, time::Instant};
 
 use chrono::Local;
 use clap::Parser;
-use color_eyre::{eyre::Context, install, Result};
+use color_eyre::{
+    eyre::{bail, Context},
+    install, Result,
+};
 use fern::{Dispatch, InitError};
-use log::LevelFilter;
+use log::{error, info, LevelFilter};
 use tokio_util::sync::CancellationToken;
 
-use behavior_simulator::{server, simulator::Simulator};
+use behavior_simulator::{
+    report::{find_scenarios, run_scenario, write_json, write_junit},
+    server,
+    simulator::Simulator,
+};
 
 #[derive(Parser)]
 enum Arguments {
     Run(RunArguments),
     Serve(ServeArguments),
+    Test(TestArguments),
 }
 
 #[derive(Parser)]
@@ -27,6 +35,21 @@ struct ServeArguments {
     scenario_file: PathBuf,
 }
 
+#[derive(Parser)]
+struct TestArguments {
+    /// Scenarios to run, all scenarios in tests/behavior if empty
+    scenario_files: Vec<PathBuf>,
+    /// Write a JUnit XML summary to this file
+    #[arg(long)]
+    junit: Option<PathBuf>,
+    /// Write a JSON summary to this file
+    #[arg(long)]
+    json: Option<PathBuf>,
+    /// Fail scenarios that did not finish within this many cycles
+    #[arg(long, default_value_t = 20000)]
+    maximum_cycle_count: usize,
+}
+
 fn setup_logger(is_verbose: bool) -> Result<(), InitError> {
     Dispatch::new()
         .format(|out, message, record| {
@@ -56,6 +79,7 @@ fn main() -> Result<()> {
     match arguments {
         Arguments::Run(arguments) => run(arguments),
         Arguments::Serve(arguments) => serve(arguments),
+        Arguments::Test(arguments) => test(arguments),
     }
 }
 
@@ -82,8 +106,45 @@ fn serve(arguments: ServeArguments) -> Result<()> {
     }
 
     server::run(
-        Some(arguments.listen_address),
+        Some(arguments.listen_address.into()),
         keep_running,
         arguments.scenario_file,
     )
 }
+
+fn test(arguments: TestArguments) -> Result<()> {
+    let scenario_files = if arguments.scenario_files.is_empty() {
+        find_scenarios("tests/behavior")?
+    } else {
+        arguments.scenario_files
+    };
+
+    let results: Vec<_> = scenario_files
+        .iter()
+        .map(|scenario_file| {
+            let result = run_scenario(scenario_file, arguments.maximum_cycle_count);
+            match &result.failure {
+                None => info!(
+                    "{} passed after {} cycles in {:.2} seconds",
+                    result.name, result.cycle_count, result.duration_seconds
+                ),
+                Some(failure) => error!("{} failed: {failure}", result.name),
+            }
+            result
+        })
+        .collect();
+
+    if let Some(path) = arguments.junit {
+        write_junit(&results, path)?;
+    }
+    if let Some(path) = arguments.json {
+        write_json(&results, path)?;
+    }
+
+    let failures = results.iter().filter(|result| !result.is_success()).count();
+    if failures > 0 {
+        bail!("{failures} of {} scenarios failed", results.len());
+    }
+    info!("all {} scenarios passed", results.len());
+    Ok(())
+}
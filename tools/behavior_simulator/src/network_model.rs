@@ -0,0 +1,47 @@
+use std::time::{Duration, SystemTime};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use spl_network_messages::{HulkMessage, PlayerNumber};
+
+/// Describes how team messages travel between simulated robots, the default is a perfect network
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NetworkModel {
+    /// Probability that a message does not reach one of the other robots
+    pub drop_rate: f32,
+    /// Minimum time in s between sending and receiving a message
+    pub latency: f32,
+    /// Maximum additional random delay in s on top of the latency
+    pub latency_jitter: f32,
+}
+
+pub struct InFlightMessage {
+    pub sender: PlayerNumber,
+    pub receiver: PlayerNumber,
+    pub message: HulkMessage,
+    pub delivery_time: SystemTime,
+}
+
+impl NetworkModel {
+    /// Decides whether and when a message sent at `now` reaches `receiver`
+    pub fn transmit(
+        &self,
+        sender: PlayerNumber,
+        receiver: PlayerNumber,
+        message: HulkMessage,
+        now: SystemTime,
+        rng: &mut impl Rng,
+    ) -> Option<InFlightMessage> {
+        if rng.gen::<f32>() < self.drop_rate {
+            return None;
+        }
+        let delay = self.latency + rng.gen::<f32>() * self.latency_jitter;
+        Some(InFlightMessage {
+            sender,
+            receiver,
+            message,
+            delivery_time: now + Duration::from_secs_f32(delay.max(0.0)),
+        })
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    fmt::Write as _,
+    fs::{read_dir, write, File},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use color_eyre::{eyre::WrapErr, Result};
+use serde::Serialize;
+
+use crate::{simulator::Simulator, state::Goal};
+
+#[derive(Debug, Serialize)]
+pub struct ScenarioResult {
+    pub name: String,
+    pub path: PathBuf,
+    pub duration_seconds: f32,
+    pub cycle_count: usize,
+    pub goals: Vec<Goal>,
+    pub failure: Option<String>,
+}
+
+impl ScenarioResult {
+    pub fn is_success(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Collects all Lua scenarios in `directory`, sorted by file name
+pub fn find_scenarios(directory: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+    let directory = directory.as_ref();
+    let mut scenarios = Vec::new();
+    for entry in read_dir(directory).wrap_err_with(|| format!("failed to read {directory:?}"))? {
+        let path = entry
+            .wrap_err_with(|| format!("failed to read entry of {directory:?}"))?
+            .path();
+        if path.extension().is_some_and(|extension| extension == "lua") {
+            scenarios.push(path);
+        }
+    }
+    scenarios.sort();
+    Ok(scenarios)
+}
+
+pub fn run_scenario(path: impl AsRef<Path>, maximum_cycle_count: usize) -> ScenarioResult {
+    let path = path.as_ref();
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let start = Instant::now();
+
+    let mut simulator = None;
+    let result = (|| {
+        let simulator = simulator.insert(Simulator::try_new()?);
+        simulator.maximum_cycle_count = Some(maximum_cycle_count);
+        simulator.execute_script(path)?;
+        simulator.run().wrap_err("failed to run simulation")
+    })();
+    let (cycle_count, goals) = simulator
+        .map(|simulator| {
+            let state = simulator.state.lock();
+            (state.cycle_count, state.goals.clone())
+        })
+        .unwrap_or_default();
+
+    ScenarioResult {
+        name,
+        path: path.to_path_buf(),
+        duration_seconds: start.elapsed().as_secs_f32(),
+        cycle_count,
+        goals,
+        failure: result.err().map(|error| format!("{error:#}")),
+    }
+}
+
+pub fn write_json(results: &[ScenarioResult], path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let file = File::create(path).wrap_err_with(|| format!("failed to create {path:?}"))?;
+    serde_json::to_writer_pretty(file, results)
+        .wrap_err_with(|| format!("failed to write JSON summary to {path:?}"))
+}
+
+pub fn write_junit(results: &[ScenarioResult], path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let tests = results.len();
+    let failures = results.iter().filter(|result| !result.is_success()).count();
+    let time: f32 = results.iter().map(|result| result.duration_seconds).sum();
+
+    let mut xml = String::new();
+    writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        xml,
+        r#"<testsuites tests="{tests}" failures="{failures}" time="{time:.3}">"#
+    )?;
+    writeln!(
+        xml,
+        r#"  <testsuite name="behavior_simulator" tests="{tests}" failures="{failures}" time="{time:.3}">"#
+    )?;
+    for result in results {
+        let name = escape_xml(&result.name);
+        let time = result.duration_seconds;
+        match &result.failure {
+            Some(failure) => {
+                writeln!(
+                    xml,
+                    r#"    <testcase name="{name}" classname="behavior_simulator" time="{time:.3}">"#
+                )?;
+                writeln!(xml, r#"      <failure message="{}"/>"#, escape_xml(failure))?;
+                writeln!(xml, "    </testcase>")?;
+            }
+            None => writeln!(
+                xml,
+                r#"    <testcase name="{name}" classname="behavior_simulator" time="{time:.3}"/>"#
+            )?,
+        }
+    }
+    writeln!(xml, "  </testsuite>")?;
+    writeln!(xml, "</testsuites>")?;
+
+    write(path, xml).wrap_err_with(|| format!("failed to write JUnit summary to {path:?}"))
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -7,7 +7,7 @@ use std::{
 
 use color_eyre::{eyre::WrapErr, Result};
 use control::localization::generate_initial_pose;
-use nalgebra::vector;
+use nalgebra::{vector, Isometry2, Point2, Vector2};
 use parameters::directory::deserialize;
 use spl_network_messages::PlayerNumber;
 use types::{camera_matrix::CameraMatrix, messages::IncomingMessage};
@@ -15,6 +15,7 @@ use types::{camera_matrix::CameraMatrix, messages::IncomingMessage};
 use crate::{
     cycler::{BehaviorCycler, Database},
     interfake::Interfake,
+    state::Ball,
     structs::{control::CyclerState, Parameters},
 };
 
@@ -27,6 +28,12 @@ pub struct Robot {
     pub is_penalized: bool,
     pub last_kick_time: Duration,
     pub ball_last_seen: Option<SystemTime>,
+    /// Walking velocity in field coordinates of the last simulation step
+    pub velocity: Vector2<f32>,
+    /// Last ball detection in field coordinates, remembered until the ball filter timeout
+    pub last_detected_ball: Option<Ball>,
+    /// Error of the estimated pose relative to the ground truth pose
+    pub pose_error: Isometry2<f32>,
 }
 
 impl Robot {
@@ -68,10 +75,16 @@ impl Robot {
             is_penalized: false,
             last_kick_time: Duration::default(),
             ball_last_seen: None,
+            velocity: Vector2::zeros(),
+            last_detected_ball: None,
+            pose_error: Isometry2::identity(),
         })
     }
 
-    pub fn cycle(&mut self, messages: BTreeMap<SystemTime, Vec<&IncomingMessage>>) -> Result<()> {
+    pub fn cycle(
+        &mut self,
+        messages: BTreeMap<SystemTime, Vec<Option<&IncomingMessage>>>,
+    ) -> Result<()> {
         self.cycler.cycle(
             &mut self.database,
             &mut self.cycler_state,
@@ -80,6 +93,16 @@ impl Robot {
         )
     }
 
+    pub fn position(&self) -> Point2<f32> {
+        self.database
+            .main_outputs
+            .robot_to_field
+            .expect("simulated robots should always have a known pose")
+            .translation
+            .vector
+            .into()
+    }
+
     pub fn field_of_view(&self) -> f32 {
         let image_size = vector![640.0, 480.0];
         let focal_lengths = self
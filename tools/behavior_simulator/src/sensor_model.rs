@@ -0,0 +1,141 @@
+use geometry::line_segment::LineSegment;
+use nalgebra::{vector, Isometry2, Point2, UnitComplex, Vector2};
+use rand::Rng;
+use rand_distr::{Distribution, Normal};
+use serde::{Deserialize, Serialize};
+
+/// Describes what simulated robots perceive of the ground truth, the default is an ideal sensor
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SensorModel {
+    /// Maximum distance at which the ball is detected in m
+    pub maximum_ball_distance: f32,
+    /// Maximum distance at which other robots are detected in m
+    pub maximum_robot_distance: f32,
+    /// Whether other robots hide the ball and robots behind them
+    pub occlusion: bool,
+    /// Whether visible robots replace the obstacles of a robot each cycle
+    pub detect_robots: bool,
+    /// Standard deviation of a detected ball position per meter of distance
+    pub ball_position_noise: f32,
+    /// Standard deviation of a detected robot position per meter of distance
+    pub robot_position_noise: f32,
+    /// Probability of missing a visible ball in a cycle
+    pub ball_false_negative_rate: f32,
+    /// Probability of missing a visible robot in a cycle
+    pub robot_false_negative_rate: f32,
+    /// Standard deviation of the pose estimate translation random walk in m/sqrt(s)
+    pub pose_drift_translation: f32,
+    /// Standard deviation of the pose estimate rotation random walk in rad/sqrt(s)
+    pub pose_drift_rotation: f32,
+    /// Time in s after which the pose error decays to 1/e, i.e. how fast the localization corrects drift
+    pub pose_drift_time_constant: f32,
+}
+
+impl Default for SensorModel {
+    fn default() -> Self {
+        Self {
+            maximum_ball_distance: 3.0,
+            maximum_robot_distance: 3.0,
+            occlusion: false,
+            detect_robots: false,
+            ball_position_noise: 0.0,
+            robot_position_noise: 0.0,
+            ball_false_negative_rate: 0.0,
+            robot_false_negative_rate: 0.0,
+            pose_drift_translation: 0.0,
+            pose_drift_rotation: 0.0,
+            pose_drift_time_constant: 10.0,
+        }
+    }
+}
+
+/// Radius of a robot when hiding objects behind it
+const OCCLUDING_ROBOT_RADIUS: f32 = 0.15;
+
+impl SensorModel {
+    /// Whether `target` in robot coordinates is inside the camera frustum and not hidden by any of `occluders`
+    pub fn is_visible(
+        &self,
+        target: Point2<f32>,
+        maximum_distance: f32,
+        head_yaw: f32,
+        field_of_view: f32,
+        occluders: &[Point2<f32>],
+    ) -> bool {
+        let target_in_head = UnitComplex::from_angle(head_yaw).inverse() * target;
+        let angle_to_target = target_in_head.coords.angle(&Vector2::x_axis());
+        let is_inside_frustum = angle_to_target.abs() < field_of_view / 2.0
+            && target_in_head.coords.norm() < maximum_distance;
+        if !is_inside_frustum {
+            return false;
+        }
+        if !self.occlusion {
+            return true;
+        }
+        let line_of_sight = LineSegment::new(Point2::origin(), target);
+        !occluders.iter().any(|occluder| {
+            (occluder - target).norm() > f32::EPSILON
+                && occluder.coords.norm() < target.coords.norm()
+                && line_of_sight.shortest_distance_to_point(*occluder) < OCCLUDING_ROBOT_RADIUS
+        })
+    }
+
+    pub fn is_ball_missed(&self, rng: &mut impl Rng) -> bool {
+        rng.gen::<f32>() < self.ball_false_negative_rate
+    }
+
+    pub fn is_robot_missed(&self, rng: &mut impl Rng) -> bool {
+        rng.gen::<f32>() < self.robot_false_negative_rate
+    }
+
+    pub fn add_ball_noise(&self, position: Point2<f32>, rng: &mut impl Rng) -> Point2<f32> {
+        add_distance_dependent_noise(position, self.ball_position_noise, rng)
+    }
+
+    pub fn add_robot_noise(&self, position: Point2<f32>, rng: &mut impl Rng) -> Point2<f32> {
+        add_distance_dependent_noise(position, self.robot_position_noise, rng)
+    }
+
+    /// Advances the error of the pose estimate relative to the ground truth by one time step
+    pub fn drift_pose_error(
+        &self,
+        pose_error: Isometry2<f32>,
+        time_step: f32,
+        rng: &mut impl Rng,
+    ) -> Isometry2<f32> {
+        let decay = (-time_step / self.pose_drift_time_constant).exp();
+        let translation_standard_deviation = self.pose_drift_translation * time_step.sqrt();
+        let translation_step = vector![
+            sample_normal(translation_standard_deviation, rng),
+            sample_normal(translation_standard_deviation, rng)
+        ];
+        let rotation_step = sample_normal(self.pose_drift_rotation * time_step.sqrt(), rng);
+        Isometry2::new(
+            pose_error.translation.vector * decay + translation_step,
+            pose_error.rotation.angle() * decay + rotation_step,
+        )
+    }
+}
+
+fn add_distance_dependent_noise(
+    position: Point2<f32>,
+    noise_per_meter: f32,
+    rng: &mut impl Rng,
+) -> Point2<f32> {
+    let standard_deviation = noise_per_meter * position.coords.norm();
+    position
+        + vector![
+            sample_normal(standard_deviation, rng),
+            sample_normal(standard_deviation, rng)
+        ]
+}
+
+fn sample_normal(standard_deviation: f32, rng: &mut impl Rng) -> f32 {
+    if standard_deviation <= 0.0 {
+        return 0.0;
+    }
+    Normal::new(0.0, standard_deviation)
+        .expect("standard deviation should be finite and positive")
+        .sample(rng)
+}
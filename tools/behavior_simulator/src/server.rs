@@ -11,10 +11,10 @@ use crate::{
     state::Ball,
 };
 use color_eyre::{eyre::bail, owo_colors::OwoColorize, Result};
-use framework::{multiple_buffer_with_slots, Reader, Writer};
+use framework::{multiple_buffer_with_slots, CommunicationAddresses, Reader, Writer};
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
-use tokio::{net::ToSocketAddrs, select, sync::Notify, time::interval};
+use tokio::{select, sync::Notify, time::interval};
 use tokio_util::sync::CancellationToken;
 use types::{field_dimensions::FieldDimensions, players::Players};
 
@@ -87,13 +87,14 @@ async fn timeline_server(
 }
 
 pub fn run(
-    addresses: Option<impl ToSocketAddrs + Send + Sync + 'static>,
+    addresses: Option<CommunicationAddresses>,
     keep_running: CancellationToken,
     scenario_file: impl AsRef<Path>,
 ) -> Result<()> {
     let parameter_slots = 3; // 2 for communication writer + 1 reader for timeline_server
     let communication_server = communication::server::Runtime::<Parameters>::start(
         addresses,
+        None,
         "tools/behavior_simulator",
         "behavior_simulator".to_string(),
         "behavior_simulator".to_string(),
@@ -2,15 +2,17 @@ use std::{fs::read_to_string, path::Path, sync::Arc, time::Duration};
 
 use crate::{cycler::Database, robot::to_player_number, state::Ball};
 use color_eyre::{
-    eyre::{eyre, WrapErr},
+    eyre::{bail, eyre, WrapErr},
     Result,
 };
 use mlua::{Error as LuaError, Function, Lua, LuaSerdeExt, SerializeOptions, Value};
 use nalgebra::{Isometry2, Point2, Vector2};
 use parking_lot::Mutex;
+use spl_network_messages::Team;
 use types::{obstacles::Obstacle, players::Players};
 
 use crate::{
+    assertions::Assertion,
     robot::Robot,
     state::{Event, LuaRobot, State},
 };
@@ -25,6 +27,9 @@ pub struct Frame {
 pub struct Simulator {
     pub state: Arc<Mutex<State>>,
     pub frames: Vec<Frame>,
+    /// Aborts the simulation with an error if the scenario did not finish within this many cycles
+    pub maximum_cycle_count: Option<usize>,
+    assertions: Arc<Mutex<Vec<Assertion>>>,
     lua: Lua,
 }
 
@@ -52,10 +57,15 @@ impl Simulator {
             .set("error", error)
             .wrap_err("failed to insert create_robot")?;
 
+        let assertions = Arc::new(Mutex::new(Vec::new()));
+        register_assertion_functions(&lua, &assertions)?;
+
         Ok(Self {
             state,
             lua,
             frames: Vec::new(),
+            maximum_cycle_count: None,
+            assertions,
         })
     }
 
@@ -92,9 +102,29 @@ impl Simulator {
                 ball: state.ball.clone(),
             });
 
+            for assertion in self.assertions.lock().iter_mut() {
+                assertion
+                    .check(&state)
+                    .wrap_err_with(|| format!("assertion failed in cycle {}", state.cycle_count))?;
+            }
+
             if state.finished {
+                for assertion in self.assertions.lock().iter() {
+                    assertion
+                        .check_finished(&state)
+                        .wrap_err("assertion failed after scenario finished")?;
+                }
                 break;
             }
+            if self
+                .maximum_cycle_count
+                .is_some_and(|maximum_cycle_count| state.cycle_count >= maximum_cycle_count)
+            {
+                bail!(
+                    "scenario did not finish within {} cycles",
+                    state.cycle_count
+                );
+            }
         }
 
         Ok(())
@@ -269,3 +299,66 @@ impl Simulator {
             .wrap_err("failed to load lua state")
     }
 }
+
+fn register_assertion_functions(lua: &Lua, assertions: &Arc<Mutex<Vec<Assertion>>>) -> Result<()> {
+    let assert_goal_before = lua
+        .create_function({
+            let assertions = assertions.clone();
+            move |lua, (team, cycle_count): (Value, usize)| {
+                let team: Team = lua.from_value(team)?;
+                assertions
+                    .lock()
+                    .push(Assertion::GoalBefore { team, cycle_count });
+                Ok(())
+            }
+        })
+        .wrap_err("failed to create function assert_goal_before")?;
+    lua.globals()
+        .set("assert_goal_before", assert_goal_before)
+        .wrap_err("failed to insert assert_goal_before")?;
+
+    let assert_robots_stay_on_field = lua
+        .create_function({
+            let assertions = assertions.clone();
+            move |_lua, ()| {
+                assertions.lock().push(Assertion::RobotsStayOnField);
+                Ok(())
+            }
+        })
+        .wrap_err("failed to create function assert_robots_stay_on_field")?;
+    lua.globals()
+        .set("assert_robots_stay_on_field", assert_robots_stay_on_field)
+        .wrap_err("failed to insert assert_robots_stay_on_field")?;
+
+    let assert_role_invariants = lua
+        .create_function({
+            let assertions = assertions.clone();
+            move |_lua, ()| {
+                assertions.lock().push(Assertion::RoleInvariants {
+                    conflicting_cycles: 0,
+                });
+                Ok(())
+            }
+        })
+        .wrap_err("failed to create function assert_role_invariants")?;
+    lua.globals()
+        .set("assert_role_invariants", assert_role_invariants)
+        .wrap_err("failed to insert assert_role_invariants")?;
+
+    let assert_no_crowding_at_ball = lua
+        .create_function({
+            let assertions = assertions.clone();
+            move |_lua, distance: f32| {
+                assertions
+                    .lock()
+                    .push(Assertion::NoCrowdingAtBall { distance });
+                Ok(())
+            }
+        })
+        .wrap_err("failed to create function assert_no_crowding_at_ball")?;
+    lua.globals()
+        .set("assert_no_crowding_at_ball", assert_no_crowding_at_ball)
+        .wrap_err("failed to insert assert_no_crowding_at_ball")?;
+
+    Ok(())
+}
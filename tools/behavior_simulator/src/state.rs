@@ -1,24 +1,26 @@
 use std::{
     collections::{BTreeMap, HashMap},
     mem::take,
-    time::{Duration, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use color_eyre::Result;
 use geometry::line_segment::LineSegment;
-use nalgebra::{vector, Isometry2, Point2, UnitComplex, Vector2};
+use nalgebra::{point, vector, Isometry2, Matrix2, Point2, Translation2, UnitComplex, Vector2};
+use rand::{rngs::StdRng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
-use spl_network_messages::{GamePhase, GameState, HulkMessage, PlayerNumber, Team};
+use spl_network_messages::{GamePhase, GameState, HulkMessage, PlayerNumber, Team, TeamColor};
 use types::motion_command::KickVariant;
 use types::motion_command::{HeadMotion, OrientationMode};
 use types::planned_path::PathSegment;
 use types::{
     ball_position::BallPosition,
     filtered_game_state::FilteredGameState,
-    game_controller_state::GameControllerState,
+    game_controller_state::{GameControllerState, TeamColors},
     messages::{IncomingMessage, OutgoingMessage},
     motion_command::MotionCommand,
+    obstacles::Obstacle,
     players::Players,
     primary_state::PrimaryState,
     support_foot::Side,
@@ -26,15 +28,34 @@ use types::{
 
 use crate::{
     cycler::Database,
+    network_model::{InFlightMessage, NetworkModel},
     robot::Robot,
+    sensor_model::SensorModel,
     structs::{control::AdditionalOutputs, Parameters},
 };
 
+const BALL_RADIUS: f32 = 0.05;
+const ROBOT_RADIUS: f32 = 0.1;
+const GOAL_POST_RADIUS: f32 = 0.05;
+/// Deceleration of a rolling ball caused by the carpet in m/s^2
+const BALL_ROLLING_DECELERATION: f32 = 0.8;
+const BALL_ROBOT_RESTITUTION: f32 = 0.3;
+const BALL_GOAL_POST_RESTITUTION: f32 = 0.6;
+/// Maximum distance between the ball and its position expected by the kick for the kick to hit it
+const KICK_RANGE: f32 = 0.12;
+const ROBOT_OBSTACLE_RADIUS: f32 = 0.2;
+
 pub enum Event {
     Cycle,
     Goal,
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct Goal {
+    pub team: Team,
+    pub cycle_count: usize,
+}
+
 #[derive(Default, Clone, Deserialize, Serialize, SerializeHierarchy)]
 pub struct Ball {
     pub position: Point2<f32>,
@@ -50,6 +71,12 @@ pub struct State {
     pub finished: bool,
     pub game_controller_state: GameControllerState,
     pub filtered_game_state: FilteredGameState,
+    pub sensor_model: SensorModel,
+    pub network_model: NetworkModel,
+    pub messages_in_flight: Vec<InFlightMessage>,
+    pub rng: StdRng,
+    pub goals: Vec<Goal>,
+    ball_was_in_goal: bool,
 }
 
 impl State {
@@ -59,7 +86,8 @@ impl State {
         let mut events = vec![Event::Cycle];
 
         self.move_robots(time_step);
-        self.cycle_robots(now)?;
+        self.separate_robots();
+        self.cycle_robots(now, time_step)?;
         events.extend(self.move_ball(time_step));
 
         self.time_elapsed += time_step;
@@ -119,6 +147,9 @@ impl State {
                         obstacle.position =
                             robot_to_field.inverse() * previous_robot_to_field * obstacle.position;
                     }
+                    robot.velocity = (robot_to_field.translation.vector
+                        - previous_robot_to_field.translation.vector)
+                        / time_step.as_secs_f32();
 
                     head
                 }
@@ -128,15 +159,23 @@ impl State {
                     kicking_side,
                     strength,
                 } => {
+                    robot.velocity = Vector2::zeros();
                     if let Some(ball) = self.ball.as_mut() {
                         let side = match kicking_side {
                             Side::Left => 1.0,
                             Side::Right => -1.0,
                         };
 
-                        // TODO: Check if ball is even in range
-                        // let kick_location = robot_to_field * ();
-                        if (self.time_elapsed - robot.last_kick_time).as_secs_f32() > 1.0 {
+                        // the kick offset is the kick pose relative to the ball, mirrored for the right foot
+                        let kick_offset = robot.parameters.in_walk_kicks[*kick].offset;
+                        let expected_ball_in_robot = point![-kick_offset.x, -kick_offset.y * side];
+                        let ball_in_robot = robot_to_field.inverse() * ball.position;
+                        let is_ball_in_range =
+                            (ball_in_robot - expected_ball_in_robot).norm() < KICK_RANGE;
+
+                        if is_ball_in_range
+                            && (self.time_elapsed - robot.last_kick_time).as_secs_f32() > 1.0
+                        {
                             let direction = match kick {
                                 KickVariant::Forward => vector![1.0, 0.0],
                                 KickVariant::Turn => vector![0.707, 0.707 * side],
@@ -148,12 +187,50 @@ impl State {
                     }
                     head
                 }
-                MotionCommand::SitDown { head } => head,
+                MotionCommand::Kick { variant, side } => {
+                    robot.velocity = Vector2::zeros();
+                    if let Some(ball) = self.ball.as_mut() {
+                        let side = match side {
+                            Side::Left => 1.0,
+                            Side::Right => -1.0,
+                        };
+
+                        let kick_info = &robot.parameters.standing_kicks[*variant];
+                        let expected_ball_in_robot =
+                            point![-kick_info.offset.x, -kick_info.offset.y * side];
+                        let ball_in_robot = robot_to_field.inverse() * ball.position;
+                        let is_ball_in_range =
+                            (ball_in_robot - expected_ball_in_robot).norm() < KICK_RANGE;
+
+                        if is_ball_in_range
+                            && (self.time_elapsed - robot.last_kick_time).as_secs_f32() > 1.0
+                        {
+                            // a rolling ball travels v^2 / (2 * deceleration) until it stops
+                            let speed =
+                                (2.0 * BALL_ROLLING_DECELERATION * kick_info.shot_distance).sqrt();
+                            let direction =
+                                UnitComplex::new(-kick_info.shot_angle * side) * Vector2::x();
+                            ball.velocity += *robot_to_field * direction * speed;
+                            robot.last_kick_time = self.time_elapsed;
+                        };
+                    }
+                    &HeadMotion::Center
+                }
+                MotionCommand::SitDown { head } => {
+                    robot.velocity = Vector2::zeros();
+                    head
+                }
                 MotionCommand::Stand {
                     head,
                     is_energy_saving: _,
-                } => head,
-                _ => &HeadMotion::Center,
+                } => {
+                    robot.velocity = Vector2::zeros();
+                    head
+                }
+                _ => {
+                    robot.velocity = Vector2::zeros();
+                    &HeadMotion::Center
+                }
             };
 
             let desired_head_yaw = match head_motion {
@@ -168,6 +245,7 @@ impl State {
                     target.coords.angle(&Vector2::x_axis())
                         + glance_factor * robot.parameters.look_at.glance_angle
                 }
+                HeadMotion::Calibrate => 0.0,
                 HeadMotion::Unstiff => 0.0,
             };
 
@@ -181,18 +259,83 @@ impl State {
         }
     }
 
-    fn cycle_robots(&mut self, now: std::time::SystemTime) -> Result<()> {
-        let incoming_messages = take(&mut self.messages);
+    fn separate_robots(&mut self) {
+        let positions: Vec<_> = self
+            .robots
+            .iter()
+            .filter(|(_, robot)| !robot.is_penalized)
+            .map(|(player_number, robot)| (*player_number, robot.position()))
+            .collect();
+
+        let mut corrections: HashMap<PlayerNumber, Vector2<f32>> = HashMap::new();
+        for (index, (player_number, position)) in positions.iter().enumerate() {
+            for (other_player_number, other_position) in &positions[index + 1..] {
+                let offset = other_position - position;
+                let distance = offset.norm();
+                let overlap = 2.0 * ROBOT_RADIUS - distance;
+                if overlap <= 0.0 || distance < f32::EPSILON {
+                    continue;
+                }
+                let push = offset / distance * overlap / 2.0;
+                *corrections.entry(*player_number).or_default() -= push;
+                *corrections.entry(*other_player_number).or_default() += push;
+            }
+        }
+
+        for (player_number, correction) in corrections {
+            let robot = self
+                .robots
+                .get_mut(&player_number)
+                .expect("corrections are only computed for existing robots");
+            let robot_to_field = robot
+                .database
+                .main_outputs
+                .robot_to_field
+                .as_mut()
+                .expect("simulated robots should always have a known pose");
+            let previous_robot_to_field = *robot_to_field;
+            robot_to_field.append_translation_mut(&Translation2::from(correction));
+            for obstacle in robot.database.main_outputs.obstacles.iter_mut() {
+                obstacle.position =
+                    robot_to_field.inverse() * previous_robot_to_field * obstacle.position;
+            }
+        }
+    }
+
+    fn cycle_robots(&mut self, now: SystemTime, time_step: Duration) -> Result<()> {
+        for (sender, message) in take(&mut self.messages) {
+            for receiver in self.robots.keys() {
+                if *receiver == sender {
+                    continue;
+                }
+                self.messages_in_flight.extend(self.network_model.transmit(
+                    sender,
+                    *receiver,
+                    message,
+                    now,
+                    &mut self.rng,
+                ));
+            }
+        }
+        let (delivered_messages, messages_in_flight) = take(&mut self.messages_in_flight)
+            .into_iter()
+            .partition::<Vec<_>, _>(|message| message.delivery_time <= now);
+        self.messages_in_flight = messages_in_flight;
+        let robot_positions: Vec<_> = self
+            .robots
+            .iter()
+            .filter(|(_, robot)| !robot.is_penalized)
+            .map(|(player_number, robot)| (*player_number, robot.position()))
+            .collect();
 
         for (player_number, robot) in self.robots.iter_mut() {
-            let incoming_messages: Vec<_> = incoming_messages
+            let incoming_messages: Vec<_> = delivered_messages
                 .iter()
-                .filter_map(|(sender, message)| {
-                    (sender != player_number).then_some(IncomingMessage::Spl(*message))
-                })
+                .filter(|message| message.receiver == *player_number)
+                .map(|message| IncomingMessage::Spl(message.message))
                 .collect();
             let messages_with_time =
-                BTreeMap::from_iter([(now, incoming_messages.iter().collect())]);
+                BTreeMap::from_iter([(now, incoming_messages.iter().map(Some).collect())]);
 
             robot.database.main_outputs.cycle_time.start_time = now;
 
@@ -201,33 +344,87 @@ impl State {
                 .main_outputs
                 .robot_to_field
                 .expect("simulated robots should always have a known pose");
-            let ball_visible = self.ball.as_ref().is_some_and(|ball| {
-                let ball_in_ground = robot_to_field.inverse() * ball.position;
-                let head_rotation = UnitComplex::from_angle(
-                    robot.database.main_outputs.sensor_data.positions.head.yaw,
-                );
-                let ball_in_head = head_rotation.inverse() * ball_in_ground;
-                let field_of_view = robot.field_of_view();
-                let angle_to_ball = ball_in_head.coords.angle(&Vector2::x_axis());
+            let head_yaw = robot.database.main_outputs.sensor_data.positions.head.yaw;
+            let field_of_view = robot.field_of_view();
+            let other_robots: Vec<_> = robot_positions
+                .iter()
+                .filter(|(other_player_number, _)| other_player_number != player_number)
+                .map(|(_, position)| robot_to_field.inverse() * position)
+                .collect();
 
-                angle_to_ball.abs() < field_of_view / 2.0 && ball_in_head.coords.norm() < 3.0
-            });
-            if ball_visible {
+            let detected_ball = self
+                .ball
+                .as_ref()
+                .filter(|ball| {
+                    self.sensor_model.is_visible(
+                        robot_to_field.inverse() * ball.position,
+                        self.sensor_model.maximum_ball_distance,
+                        head_yaw,
+                        field_of_view,
+                        &other_robots,
+                    )
+                })
+                .filter(|_| !self.sensor_model.is_ball_missed(&mut self.rng));
+            if let Some(ball) = detected_ball {
+                let ball_in_ground = self
+                    .sensor_model
+                    .add_ball_noise(robot_to_field.inverse() * ball.position, &mut self.rng);
                 robot.ball_last_seen = Some(now);
+                robot.last_detected_ball = Some(Ball {
+                    position: robot_to_field * ball_in_ground,
+                    velocity: ball.velocity,
+                });
             }
             robot.database.main_outputs.ball_position =
-                if robot.ball_last_seen.is_some_and(|last_seen| {
-                    now.duration_since(last_seen).expect("time ran backwards")
-                        < robot.parameters.ball_filter.hypothesis_timeout
-                }) {
-                    self.ball.as_ref().map(|ball| BallPosition {
-                        position: robot_to_field.inverse() * ball.position,
-                        velocity: robot_to_field.inverse() * ball.velocity,
-                        last_seen: now,
-                    })
-                } else {
-                    None
+                match (robot.ball_last_seen, robot.last_detected_ball.as_ref()) {
+                    (Some(last_seen), Some(ball))
+                        if now.duration_since(last_seen).expect("time ran backwards")
+                            < robot.parameters.ball_filter.hypothesis_timeout =>
+                    {
+                        let ball = predict_ball(
+                            ball,
+                            now.duration_since(last_seen).expect("time ran backwards"),
+                        );
+                        Some(BallPosition {
+                            position: robot_to_field.inverse() * ball.position,
+                            velocity: robot_to_field.inverse() * ball.velocity,
+                            covariance: Matrix2::zeros(),
+                            last_seen,
+                        })
+                    }
+                    _ => None,
                 };
+
+            if self.sensor_model.detect_robots {
+                robot.database.main_outputs.obstacles = other_robots
+                    .iter()
+                    .filter(|position| {
+                        self.sensor_model.is_visible(
+                            **position,
+                            self.sensor_model.maximum_robot_distance,
+                            head_yaw,
+                            field_of_view,
+                            &other_robots,
+                        )
+                    })
+                    .filter_map(|position| {
+                        if self.sensor_model.is_robot_missed(&mut self.rng) {
+                            return None;
+                        }
+                        Some(Obstacle::robot(
+                            self.sensor_model.add_robot_noise(*position, &mut self.rng),
+                            ROBOT_OBSTACLE_RADIUS,
+                            ROBOT_OBSTACLE_RADIUS,
+                        ))
+                    })
+                    .collect();
+            }
+
+            robot.pose_error = self.sensor_model.drift_pose_error(
+                robot.pose_error,
+                time_step.as_secs_f32(),
+                &mut self.rng,
+            );
             robot.database.main_outputs.primary_state =
                 match (robot.is_penalized, self.filtered_game_state) {
                     (true, _) => PrimaryState::Penalized,
@@ -240,10 +437,17 @@ impl State {
             robot.database.main_outputs.filtered_game_state = Some(self.filtered_game_state);
             robot.database.main_outputs.game_controller_state = Some(self.game_controller_state);
 
+            // the robot only knows its estimated pose while cycling, the simulation continues with the ground truth
+            robot.database.main_outputs.robot_to_field = Some(robot_to_field * robot.pose_error);
             robot.cycle(messages_with_time)?;
+            robot.database.main_outputs.robot_to_field = Some(robot_to_field);
 
             for message in robot.interface.take_outgoing_messages() {
                 if let OutgoingMessage::Spl(message) = message {
+                    // the game controller does not forward messages once the budget is exhausted
+                    if self.game_controller_state.remaining_amount_of_messages == 0 {
+                        continue;
+                    }
                     self.messages.push((*player_number, message));
                     self.game_controller_state.remaining_amount_of_messages -= 1
                 }
@@ -257,11 +461,52 @@ impl State {
         let mut events = Vec::new();
         if let Some(ball) = self.ball.as_mut() {
             ball.position += ball.velocity * time_step.as_secs_f32();
-            ball.velocity *= 0.98;
+            let speed = ball.velocity.norm();
+            let slowed_down_speed =
+                (speed - BALL_ROLLING_DECELERATION * time_step.as_secs_f32()).max(0.0);
+            ball.velocity = ball
+                .velocity
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default()
+                * slowed_down_speed;
+
+            for robot in self.robots.values().filter(|robot| !robot.is_penalized) {
+                collide_ball_with_circle(
+                    ball,
+                    robot.position(),
+                    ROBOT_RADIUS,
+                    robot.velocity,
+                    BALL_ROBOT_RESTITUTION,
+                );
+            }
+            for goal_post in goal_posts() {
+                collide_ball_with_circle(
+                    ball,
+                    goal_post,
+                    GOAL_POST_RADIUS,
+                    Vector2::zeros(),
+                    BALL_GOAL_POST_RESTITUTION,
+                );
+            }
 
-            if ball.position.x.abs() > 4.5 && ball.position.y < 0.75 {
+            let is_ball_in_goal = ball.position.x.abs() > 4.5 && ball.position.y.abs() < 0.75;
+            if is_ball_in_goal {
                 events.push(Event::Goal);
+                if !self.ball_was_in_goal {
+                    let team = if ball.position.x > 0.0 {
+                        Team::Hulks
+                    } else {
+                        Team::Opponent
+                    };
+                    self.goals.push(Goal {
+                        team,
+                        cycle_count: self.cycle_count,
+                    });
+                }
             }
+            self.ball_was_in_goal = is_ball_in_goal;
+        } else {
+            self.ball_was_in_goal = false;
         }
         events
     }
@@ -280,6 +525,9 @@ impl State {
 
             game_controller_state: self.game_controller_state,
             filtered_game_state: self.filtered_game_state,
+            sensor_model: self.sensor_model.clone(),
+            network_model: self.network_model.clone(),
+            goals: self.goals.clone(),
         }
     }
 
@@ -298,11 +546,60 @@ impl State {
 
         self.game_controller_state = lua_state.game_controller_state;
         self.filtered_game_state = lua_state.filtered_game_state;
+        self.sensor_model = lua_state.sensor_model;
+        self.network_model = lua_state.network_model;
+        self.goals = lua_state.goals;
 
         Ok(())
     }
 }
 
+/// Predicts where a rolling ball is after `duration` assuming it was not touched in between
+fn predict_ball(ball: &Ball, duration: Duration) -> Ball {
+    let speed = ball.velocity.norm();
+    let rolling_duration = duration
+        .as_secs_f32()
+        .min(speed / BALL_ROLLING_DECELERATION);
+    let direction = ball
+        .velocity
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_default();
+    let distance =
+        speed * rolling_duration - BALL_ROLLING_DECELERATION * rolling_duration.powi(2) / 2.0;
+    Ball {
+        position: ball.position + direction * distance,
+        velocity: direction * (speed - BALL_ROLLING_DECELERATION * rolling_duration),
+    }
+}
+
+fn goal_posts() -> [Point2<f32>; 4] {
+    let x = 4.5;
+    let y = 0.75 + GOAL_POST_RADIUS;
+    [point![x, y], point![x, -y], point![-x, y], point![-x, -y]]
+}
+
+/// Pushes the ball out of a circular obstacle and reflects its velocity relative to the obstacle
+fn collide_ball_with_circle(
+    ball: &mut Ball,
+    center: Point2<f32>,
+    radius: f32,
+    velocity: Vector2<f32>,
+    restitution: f32,
+) {
+    let offset = ball.position - center;
+    let distance = offset.norm();
+    let minimum_distance = radius + BALL_RADIUS;
+    if distance >= minimum_distance || distance < f32::EPSILON {
+        return;
+    }
+    let normal = offset / distance;
+    ball.position = center + normal * minimum_distance;
+    let approaching_speed = (ball.velocity - velocity).dot(&normal);
+    if approaching_speed < 0.0 {
+        ball.velocity -= (1.0 + restitution) * approaching_speed * normal;
+    }
+}
+
 impl Default for State {
     fn default() -> Self {
         let robots = HashMap::new();
@@ -324,6 +621,14 @@ impl Default for State {
             remaining_amount_of_messages: 1200,
             sub_state: None,
             hulks_team_is_home_after_coin_toss: false,
+            hulks_team_colors: TeamColors {
+                field_player: TeamColor::Blue,
+                goal_keeper: TeamColor::Yellow,
+            },
+            opponent_team_colors: TeamColors {
+                field_player: TeamColor::Red,
+                goal_keeper: TeamColor::Black,
+            },
         };
 
         Self {
@@ -335,6 +640,12 @@ impl Default for State {
             finished: false,
             game_controller_state,
             filtered_game_state: FilteredGameState::Initial,
+            sensor_model: SensorModel::default(),
+            network_model: NetworkModel::default(),
+            messages_in_flight: Vec::new(),
+            rng: StdRng::seed_from_u64(0),
+            goals: Vec::new(),
+            ball_was_in_goal: false,
         }
     }
 }
@@ -349,6 +660,9 @@ pub struct LuaState {
     pub finished: bool,
     pub game_controller_state: GameControllerState,
     pub filtered_game_state: FilteredGameState,
+    pub sensor_model: SensorModel,
+    pub network_model: NetworkModel,
+    pub goals: Vec<Goal>,
 }
 
 #[derive(Clone, Deserialize, Serialize)]
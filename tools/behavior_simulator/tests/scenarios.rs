@@ -21,7 +21,22 @@ fn test_golden_goal() -> Result<()> {
     test_scenario("../../tests/behavior/golden_goal.lua")
 }
 
+#[test]
+fn test_golden_goal_noisy_perception() -> Result<()> {
+    test_scenario("../../tests/behavior/golden_goal_noisy_perception.lua")
+}
+
+#[test]
+fn test_golden_goal_degraded_communication() -> Result<()> {
+    test_scenario("../../tests/behavior/golden_goal_degraded_communication.lua")
+}
+
 #[test]
 fn test_demonstration() -> Result<()> {
     test_scenario("../../tests/behavior/demonstration.lua")
 }
+
+#[test]
+fn test_golden_goal_utility_role_assignment() -> Result<()> {
+    test_scenario("../../tests/behavior/golden_goal_utility_role_assignment.lua")
+}
@@ -4,7 +4,7 @@ use clap::Parser;
 use color_eyre::{eyre::bail, Result};
 use communication::{
     client::{Communication, CyclerOutput, SubscriberMessage},
-    messages::Format,
+    messages::{Format, UpdatePolicy},
 };
 use log::{error, info};
 
@@ -28,7 +28,11 @@ async fn main() -> Result<()> {
     let output_to_subscribe = CyclerOutput::from_str(&arguments.path)?;
     let communication = Communication::new(Some(format!("ws://{}:1337", arguments.address)), true);
     let (_uuid, mut receiver) = communication
-        .subscribe_output(output_to_subscribe, Format::Textual)
+        .subscribe_output(
+            output_to_subscribe,
+            Format::Textual,
+            UpdatePolicy::EveryCycle,
+        )
         .await;
     while let Some(message) = receiver.recv().await {
         match message {
@@ -0,0 +1,353 @@
+use std::{collections::BTreeMap, fs::File, io::BufReader, path::Path, time::SystemTime};
+
+use bincode::deserialize_from;
+use color_eyre::{
+    eyre::{bail, WrapErr},
+    Result,
+};
+use control::{
+    localization::{CreationContext, CycleContext, Localization},
+    localization_recorder::RecordedCycleContext,
+};
+use framework::{AdditionalOutput, PerceptionInput};
+use nalgebra::{Isometry2, Matrix3, Vector2, Vector3};
+use parameters::json::{merge_json, nest_value_at_path};
+use serde::{Deserialize, Serialize};
+use serde_json::{from_value, Value};
+use spl_network_messages::PlayerNumber;
+use types::{
+    field_dimensions::FieldDimensions, initial_pose::InitialPose, localization::ScoredPose,
+    parameters::GlobalRelocalizationParameters, players::Players,
+};
+
+/// Subset of the robot parameters used by `Localization`
+#[derive(Clone, Debug, Deserialize)]
+pub struct EvaluationParameters {
+    pub field_dimensions: FieldDimensions,
+    pub player_number: PlayerNumber,
+    pub localization: LocalizationParameters,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct LocalizationParameters {
+    pub circle_measurement_noise: Vector2<f32>,
+    pub global_relocalization: GlobalRelocalizationParameters,
+    pub goal_post_association_distance: f32,
+    pub goal_post_measurement_noise: Vector2<f32>,
+    pub good_matching_threshold: f32,
+    pub gradient_convergence_threshold: f32,
+    pub gradient_descent_step_size: f32,
+    pub hypothesis_prediction_score_reduction_factor: f32,
+    pub hypothesis_retain_factor: f32,
+    pub hypothesis_score_base_increase: f32,
+    pub initial_hypothesis_covariance: Matrix3<f32>,
+    pub initial_hypothesis_score: f32,
+    pub initial_poses: Players<InitialPose>,
+    pub intersection_association_distance: f32,
+    pub intersection_measurement_noise: Vector2<f32>,
+    pub line_length_acceptance_factor: f32,
+    pub line_measurement_noise: Vector2<f32>,
+    pub maximum_amount_of_gradient_descent_iterations: usize,
+    pub maximum_amount_of_outer_iterations: usize,
+    pub minimum_fit_error: f32,
+    pub odometry_noise: Vector3<f32>,
+    pub penalty_mark_association_distance: f32,
+    pub penalty_mark_measurement_noise: Vector2<f32>,
+    pub score_per_good_match: f32,
+    pub use_goal_post_measurements: bool,
+    pub use_intersection_measurements: bool,
+    pub use_line_measurements: bool,
+    pub use_penalty_mark_measurements: bool,
+}
+
+/// Changes between consecutive poses above these thresholds are counted as pose jumps
+#[derive(Clone, Copy, Debug)]
+pub struct JumpThresholds {
+    pub translation: f32,
+    pub rotation: f32,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Evaluation {
+    pub number_of_frames: usize,
+    pub number_of_localized_frames: usize,
+    pub number_of_pose_jumps: usize,
+    /// Cycles in which the best hypothesis is not the one closest to the previous pose
+    pub number_of_hypothesis_switches: usize,
+    /// Translation errors in meters with respect to the reference track
+    pub mean_translation_error: f32,
+    pub maximum_translation_error: f32,
+    /// Rotation errors in radians with respect to the reference track
+    pub mean_rotation_error: f32,
+    pub maximum_rotation_error: f32,
+}
+
+pub fn read_recording(path: impl AsRef<Path>) -> Result<Vec<RecordedCycleContext>> {
+    let mut reader = BufReader::new(
+        File::open(path.as_ref())
+            .wrap_err_with(|| format!("failed to open {}", path.as_ref().display()))?,
+    );
+    let mut frames = Vec::new();
+    while let Ok(frame) = deserialize_from::<_, RecordedCycleContext>(&mut reader) {
+        frames.push(frame);
+    }
+    Ok(frames)
+}
+
+/// Runs `Localization::cycle` over all `frames` and compares the resulting poses against
+/// `reference_track`, which contains one optional pose per frame
+///
+/// The random number generator of the localization is seeded with `seed` such that evaluations of
+/// different parameters are reproducible and comparable.
+pub fn evaluate(
+    frames: &[RecordedCycleContext],
+    reference_track: &[Option<Isometry2<f32>>],
+    parameters: &EvaluationParameters,
+    jump_thresholds: JumpThresholds,
+    seed: u64,
+) -> Result<Evaluation> {
+    let localization_parameters = &parameters.localization;
+    let seeded_global_relocalization = GlobalRelocalizationParameters {
+        seed,
+        ..localization_parameters.global_relocalization.clone()
+    };
+    let mut localization = Localization::new(CreationContext::new(
+        &parameters.field_dimensions,
+        &seeded_global_relocalization,
+    ))
+    .wrap_err("failed to create localization")?;
+    let mut robot_to_field = Isometry2::identity();
+    let mut evaluation = Evaluation {
+        number_of_frames: frames.len(),
+        ..Default::default()
+    };
+    let mut last_robot_to_field: Option<Isometry2<f32>> = None;
+    let mut translation_error_sum = 0.0;
+    let mut rotation_error_sum = 0.0;
+    let mut number_of_compared_frames = 0;
+
+    for (frame, reference) in frames.iter().zip(reference_track) {
+        let mut correspondence_lines = None;
+        let mut fit_errors = None;
+        let mut match_quality = None;
+        let mut measured_lines_in_field = None;
+        let mut pose_hypotheses = None;
+        let mut updates = None;
+        let current_odometry_to_last_odometry: BTreeMap<_, _> = frame
+            .current_odometry_to_last_odometry
+            .iter()
+            .map(|(timestamp, odometry)| (*timestamp, odometry.as_ref()))
+            .collect();
+
+        let main_outputs = localization
+            .cycle(CycleContext::new(
+                AdditionalOutput::new(false, &mut correspondence_lines),
+                AdditionalOutput::new(false, &mut fit_errors),
+                AdditionalOutput::new(false, &mut match_quality),
+                AdditionalOutput::new(false, &mut measured_lines_in_field),
+                AdditionalOutput::new(true, &mut pose_hypotheses),
+                AdditionalOutput::new(false, &mut updates),
+                current_odometry_to_last_odometry.into(),
+                frame.game_controller_state.as_ref(),
+                &frame.has_ground_contact,
+                &frame.primary_state,
+                &localization_parameters.circle_measurement_noise,
+                &parameters.field_dimensions,
+                &localization_parameters.global_relocalization,
+                &localization_parameters.goal_post_association_distance,
+                &localization_parameters.goal_post_measurement_noise,
+                &localization_parameters.good_matching_threshold,
+                &localization_parameters.gradient_convergence_threshold,
+                &localization_parameters.gradient_descent_step_size,
+                &localization_parameters.hypothesis_prediction_score_reduction_factor,
+                &localization_parameters.hypothesis_retain_factor,
+                &localization_parameters.hypothesis_score_base_increase,
+                &localization_parameters.initial_hypothesis_covariance,
+                &localization_parameters.initial_hypothesis_score,
+                &localization_parameters.initial_poses,
+                &localization_parameters.intersection_association_distance,
+                &localization_parameters.intersection_measurement_noise,
+                &localization_parameters.line_length_acceptance_factor,
+                &localization_parameters.line_measurement_noise,
+                &localization_parameters.maximum_amount_of_gradient_descent_iterations,
+                &localization_parameters.maximum_amount_of_outer_iterations,
+                &localization_parameters.minimum_fit_error,
+                &localization_parameters.odometry_noise,
+                &localization_parameters.penalty_mark_association_distance,
+                &localization_parameters.penalty_mark_measurement_noise,
+                &parameters.player_number,
+                &localization_parameters.score_per_good_match,
+                &localization_parameters.use_goal_post_measurements,
+                &localization_parameters.use_intersection_measurements,
+                &localization_parameters.use_line_measurements,
+                &localization_parameters.use_penalty_mark_measurements,
+                None,
+                perception_input(
+                    &frame.goal_posts_bottom_persistent,
+                    &frame.goal_posts_bottom_temporary,
+                ),
+                perception_input(
+                    &frame.goal_posts_top_persistent,
+                    &frame.goal_posts_top_temporary,
+                ),
+                perception_input(
+                    &frame.line_data_bottom_persistent,
+                    &frame.line_data_bottom_temporary,
+                ),
+                perception_input(
+                    &frame.line_data_top_persistent,
+                    &frame.line_data_top_temporary,
+                ),
+                perception_input(
+                    &frame.line_intersections_bottom_persistent,
+                    &frame.line_intersections_bottom_temporary,
+                ),
+                perception_input(
+                    &frame.line_intersections_top_persistent,
+                    &frame.line_intersections_top_temporary,
+                ),
+                perception_input(
+                    &frame.penalty_marks_bottom_persistent,
+                    &frame.penalty_marks_bottom_temporary,
+                ),
+                perception_input(
+                    &frame.penalty_marks_top_persistent,
+                    &frame.penalty_marks_top_temporary,
+                ),
+                &mut robot_to_field,
+            ))
+            .wrap_err("failed to execute cycle of localization")?;
+
+        let Some(current_robot_to_field) = main_outputs.robot_to_field.value else {
+            last_robot_to_field = None;
+            continue;
+        };
+        evaluation.number_of_localized_frames += 1;
+
+        if let Some(last_robot_to_field) = last_robot_to_field {
+            let change = last_robot_to_field.inverse() * current_robot_to_field;
+            if change.translation.vector.norm() > jump_thresholds.translation
+                || change.rotation.angle().abs() > jump_thresholds.rotation
+            {
+                evaluation.number_of_pose_jumps += 1;
+            }
+            if is_hypothesis_switch(
+                pose_hypotheses.as_deref().unwrap_or_default(),
+                last_robot_to_field,
+                current_robot_to_field,
+            ) {
+                evaluation.number_of_hypothesis_switches += 1;
+            }
+        }
+        last_robot_to_field = Some(current_robot_to_field);
+
+        if let Some(reference) = reference {
+            let error = reference.inverse() * current_robot_to_field;
+            let translation_error = error.translation.vector.norm();
+            let rotation_error = error.rotation.angle().abs();
+            translation_error_sum += translation_error;
+            rotation_error_sum += rotation_error;
+            number_of_compared_frames += 1;
+            evaluation.maximum_translation_error =
+                evaluation.maximum_translation_error.max(translation_error);
+            evaluation.maximum_rotation_error =
+                evaluation.maximum_rotation_error.max(rotation_error);
+        }
+    }
+
+    if number_of_compared_frames > 0 {
+        evaluation.mean_translation_error =
+            translation_error_sum / number_of_compared_frames as f32;
+        evaluation.mean_rotation_error = rotation_error_sum / number_of_compared_frames as f32;
+    }
+    Ok(evaluation)
+}
+
+fn perception_input<'frame, T>(
+    persistent: &'frame BTreeMap<SystemTime, Vec<Option<T>>>,
+    temporary: &'frame BTreeMap<SystemTime, Vec<Option<T>>>,
+) -> PerceptionInput<Vec<Option<&'frame T>>> {
+    let to_references = |datas: &'frame BTreeMap<SystemTime, Vec<Option<T>>>| {
+        datas
+            .iter()
+            .map(|(timestamp, datas)| {
+                (*timestamp, datas.iter().map(|data| data.as_ref()).collect())
+            })
+            .collect()
+    };
+    PerceptionInput {
+        persistent: to_references(persistent),
+        temporary: to_references(temporary),
+    }
+}
+
+fn is_hypothesis_switch(
+    hypotheses: &[ScoredPose],
+    last_robot_to_field: Isometry2<f32>,
+    current_robot_to_field: Isometry2<f32>,
+) -> bool {
+    let distance_to_last = |robot_to_field: Isometry2<f32>| {
+        (robot_to_field.translation.vector - last_robot_to_field.translation.vector).norm()
+    };
+    let closest_to_last = hypotheses
+        .iter()
+        .map(|hypothesis| hypothesis.state.as_isometry())
+        .min_by(|left, right| distance_to_last(*left).total_cmp(&distance_to_last(*right)));
+    match closest_to_last {
+        Some(closest_to_last) => closest_to_last != current_robot_to_field,
+        None => false,
+    }
+}
+
+/// Candidate values for one parameter path, e.g. `localization.odometry_noise`
+#[derive(Clone, Debug)]
+pub struct GridDimension {
+    pub path: String,
+    pub values: Vec<Value>,
+}
+
+impl GridDimension {
+    /// Parses `path=[value, ...]` where values are JSON
+    pub fn parse(argument: &str) -> Result<Self> {
+        let Some((path, values)) = argument.split_once('=') else {
+            bail!("expected `path=[value, ...]`, got `{argument}`");
+        };
+        let values = match serde_json::from_str(values)
+            .wrap_err_with(|| format!("failed to parse values of `{path}`"))?
+        {
+            Value::Array(values) => values,
+            value => vec![value],
+        };
+        Ok(Self {
+            path: path.to_string(),
+            values,
+        })
+    }
+}
+
+/// All combinations of the grid values, each as a list of `(path, value)` overrides
+pub fn grid_combinations(grid: &[GridDimension]) -> Vec<Vec<(String, Value)>> {
+    grid.iter().fold(vec![vec![]], |combinations, dimension| {
+        combinations
+            .iter()
+            .flat_map(|combination| {
+                dimension.values.iter().map(move |value| {
+                    let mut combination = combination.clone();
+                    combination.push((dimension.path.clone(), value.clone()));
+                    combination
+                })
+            })
+            .collect()
+    })
+}
+
+pub fn parameters_with_overrides(
+    base: &Value,
+    overrides: &[(String, Value)],
+) -> Result<EvaluationParameters> {
+    let mut parameters = base.clone();
+    for (path, value) in overrides {
+        merge_json(&mut parameters, &nest_value_at_path(path, value.clone()));
+    }
+    from_value(parameters).wrap_err("failed to deserialize parameters")
+}
@@ -9,7 +9,7 @@ use std::{
 
 use bincode::deserialize_from;
 use clap::Parser;
-use color_eyre::Result;
+use color_eyre::{eyre::WrapErr, Result};
 use communication::server::Runtime;
 use control::{
     localization::{
@@ -21,6 +21,7 @@ use control::{
 use framework::{multiple_buffer_with_slots, Reader, Writer};
 use nalgebra::Isometry2;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use serialize_hierarchy::SerializeHierarchy;
 use tokio::{select, sync::Notify, time::interval};
 use tokio_util::sync::CancellationToken;
@@ -33,16 +34,112 @@ use types::{
     primary_state::PrimaryState,
 };
 
+use crate::evaluation::{
+    evaluate, grid_combinations, parameters_with_overrides, read_recording, GridDimension,
+    JumpThresholds,
+};
+
+mod evaluation;
+
+#[derive(Parser)]
+enum Arguments {
+    /// Visualize a recording in twix
+    Serve(ServeArguments),
+    /// Run the localization headlessly over a recording and report its quality
+    Evaluate(EvaluateArguments),
+}
+
 #[derive(Parser)]
-struct Arguments {
+struct ServeArguments {
     #[arg(short, long, default_value = "[::]:1337")]
     listen_address: String,
     log_file: PathBuf,
 }
 
+#[derive(Parser)]
+struct EvaluateArguments {
+    log_file: PathBuf,
+    #[arg(short, long, default_value = "etc/parameters/default.json")]
+    parameters: PathBuf,
+    /// Compare against the `robot_to_field` of this recording instead of the evaluated one
+    #[arg(long)]
+    reference: Option<PathBuf>,
+    /// Evaluate all combinations of parameter values, e.g.
+    /// `--grid 'localization.hypothesis_retain_factor=[0.05, 0.1]'`
+    #[arg(long)]
+    grid: Vec<String>,
+    #[arg(long, default_value_t = 0.5)]
+    jump_translation_threshold: f32,
+    #[arg(long, default_value_t = 0.5)]
+    jump_rotation_threshold: f32,
+    /// Seed of the random number generator used by the localization in every evaluation
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+}
+
 fn main() -> Result<()> {
-    let arguments = Arguments::parse();
+    match Arguments::parse() {
+        Arguments::Serve(arguments) => serve(arguments),
+        Arguments::Evaluate(arguments) => evaluate_recording(arguments),
+    }
+}
+
+fn evaluate_recording(arguments: EvaluateArguments) -> Result<()> {
+    let frames = read_recording(&arguments.log_file)?;
+    let reference_track: Vec<_> = match &arguments.reference {
+        Some(reference) => read_recording(reference)?
+            .iter()
+            .map(|frame| frame.robot_to_field)
+            .collect(),
+        None => frames.iter().map(|frame| frame.robot_to_field).collect(),
+    };
+    let base_parameters: Value = serde_json::from_reader(BufReader::new(
+        File::open(&arguments.parameters).wrap_err("failed to open parameters")?,
+    ))
+    .wrap_err("failed to parse parameters")?;
+    let grid = arguments
+        .grid
+        .iter()
+        .map(|dimension| GridDimension::parse(dimension))
+        .collect::<Result<Vec<_>>>()?;
+    let jump_thresholds = JumpThresholds {
+        translation: arguments.jump_translation_threshold,
+        rotation: arguments.jump_rotation_threshold,
+    };
+
+    let mut results = grid_combinations(&grid)
+        .into_iter()
+        .map(|overrides| {
+            let parameters = parameters_with_overrides(&base_parameters, &overrides)?;
+            let evaluation = evaluate(
+                &frames,
+                &reference_track,
+                &parameters,
+                jump_thresholds,
+                arguments.seed,
+            )?;
+            Ok((overrides, evaluation))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    results.sort_by(|(_, left), (_, right)| {
+        left.mean_translation_error
+            .total_cmp(&right.mean_translation_error)
+    });
+
+    for (overrides, evaluation) in results {
+        let overrides: serde_json::Map<_, _> = overrides.into_iter().collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "parameters": overrides,
+                "evaluation": evaluation,
+            })
+        );
+    }
+    Ok(())
+}
 
+fn serve(arguments: ServeArguments) -> Result<()> {
     let (
         keep_running,
         simulator_writer,
@@ -266,7 +363,8 @@ fn start_communication_server(
     let keep_running = CancellationToken::new();
 
     let communication_server = Runtime::<Parameters>::start(
-        Some(listen_address),
+        Some(listen_address.into()),
+        None,
         ".",
         "".to_string(),
         "".to_string(),
@@ -0,0 +1,149 @@
+use std::{path::PathBuf, str::FromStr, sync::Arc, time::Duration};
+
+use clap::{Args, Parser, Subcommand};
+use color_eyre::{eyre::bail, install, Result};
+use communication::{client::CyclerOutput, messages::Format};
+use log::{error, info};
+use tokio::{
+    io::{stdin, AsyncBufReadExt, BufReader},
+    select, spawn,
+    sync::{mpsc, watch},
+};
+
+use crate::{
+    logging::setup_logger,
+    player::{play, Playback, PlaybackCommand},
+    recorder::{output_subscription, record},
+    recording::{Recording, Subscription},
+    server::serve,
+};
+
+mod logging;
+mod player;
+mod recorder;
+mod recording;
+mod server;
+
+/// Records outputs and parameters of a robot and replays them to twix without the robot
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct CommandlineArguments {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Record subscriptions of a robot into a file until interrupted
+    Record(RecordArguments),
+    /// Serve a recording on the communication port with its original timing
+    Replay(ReplayArguments),
+}
+
+#[derive(Args, Debug)]
+struct RecordArguments {
+    #[clap(short, long, default_value = "localhost")]
+    address: String,
+    /// File to write the recording to
+    #[clap(short, long)]
+    output: PathBuf,
+    /// Outputs to record in textual format, e.g. `Control.main.ball_position`
+    outputs: Vec<String>,
+    /// Outputs to record in binary format, e.g. `VisionTop.additional.image`
+    #[clap(long = "binary")]
+    binary_outputs: Vec<String>,
+    /// Parameters to record, e.g. `field_dimensions`
+    #[clap(long = "parameter")]
+    parameters: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+struct ReplayArguments {
+    recording: PathBuf,
+    #[clap(short, long, default_value = "[::]:1337")]
+    address: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    setup_logger()?;
+    install()?;
+
+    match CommandlineArguments::parse().command {
+        Command::Record(arguments) => record_subscriptions(arguments).await,
+        Command::Replay(arguments) => replay(arguments).await,
+    }
+}
+
+async fn record_subscriptions(arguments: RecordArguments) -> Result<()> {
+    let mut subscriptions = Vec::new();
+    for output in arguments.outputs {
+        subscriptions.push(output_subscription(
+            CyclerOutput::from_str(&output)?,
+            Format::Textual,
+        ));
+    }
+    for output in arguments.binary_outputs {
+        subscriptions.push(output_subscription(
+            CyclerOutput::from_str(&output)?,
+            Format::Binary,
+        ));
+    }
+    subscriptions.extend(
+        arguments
+            .parameters
+            .into_iter()
+            .map(|path| Subscription::Parameter { path }),
+    );
+    if subscriptions.is_empty() {
+        bail!("nothing to record, expected at least one output or parameter");
+    }
+    record(&arguments.address, subscriptions, arguments.output).await
+}
+
+async fn replay(arguments: ReplayArguments) -> Result<()> {
+    let recording = Arc::new(Recording::load(&arguments.recording)?);
+    info!(
+        "loaded {} updates spanning {:.3}s, paused, commands: [p]ause/resume, [j]ump <seconds>, [q]uit",
+        recording.updates.len(),
+        recording.duration().as_secs_f32()
+    );
+
+    let (playback_sender, playback_receiver) = watch::channel(Playback::default());
+    let (command_sender, command_receiver) = mpsc::channel(1);
+    let player = spawn(play(recording.clone(), playback_sender, command_receiver));
+
+    select! {
+        result = serve(arguments.address, recording, playback_receiver) => result?,
+        result = read_commands(command_sender) => result?,
+    }
+    player.await?;
+    Ok(())
+}
+
+/// Forwards commands from stdin to the player until the user quits
+async fn read_commands(command_sender: mpsc::Sender<PlaybackCommand>) -> Result<()> {
+    let mut lines = BufReader::new(stdin()).lines();
+    while let Some(line) = lines.next_line().await? {
+        let mut words = line.split_whitespace();
+        let command = match (words.next().unwrap_or("p"), words.next()) {
+            ("p", None) => PlaybackCommand::TogglePause,
+            ("j", Some(seconds)) => match seconds.parse().map(Duration::try_from_secs_f32) {
+                Ok(Ok(time)) => PlaybackCommand::Seek { time },
+                _ => {
+                    error!("expected seconds since start of recording, got `{seconds}`");
+                    continue;
+                }
+            },
+            ("q", None) => break,
+            _ => {
+                error!("unknown command `{line}`");
+                continue;
+            }
+        };
+        if command_sender.send(command).await.is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use log::info;
+use tokio::{
+    select,
+    sync::{mpsc, watch},
+    time::sleep_until,
+};
+
+use crate::recording::Recording;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Playback {
+    /// Number of updates played so far, i.e. the index of the next update
+    pub position: usize,
+    /// Incremented on every seek, clients have to resend the latest data of all subscriptions then
+    pub seek_count: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum PlaybackCommand {
+    TogglePause,
+    Seek { time: Duration },
+}
+
+/// Time in the recording, advancing in real time unless paused
+struct Clock {
+    reference: Instant,
+    time_at_reference: Duration,
+    is_paused: bool,
+}
+
+impl Clock {
+    fn now(&self) -> Duration {
+        if self.is_paused {
+            self.time_at_reference
+        } else {
+            self.time_at_reference + self.reference.elapsed()
+        }
+    }
+
+    fn set(&mut self, time: Duration) {
+        self.reference = Instant::now();
+        self.time_at_reference = time;
+    }
+
+    fn instant_of(&self, time: Duration) -> Instant {
+        self.reference + time.saturating_sub(self.time_at_reference)
+    }
+}
+
+/// Advances `playback` with the original timing of the recording until `commands` is closed,
+/// playback starts paused to give clients time to connect and subscribe
+pub async fn play(
+    recording: Arc<Recording>,
+    playback: watch::Sender<Playback>,
+    mut commands: mpsc::Receiver<PlaybackCommand>,
+) {
+    let mut clock = Clock {
+        reference: Instant::now(),
+        time_at_reference: Duration::ZERO,
+        is_paused: true,
+    };
+    loop {
+        let position = playback.borrow().position;
+        let deadline = recording
+            .updates
+            .get(position)
+            .filter(|_| !clock.is_paused)
+            .map(|update| clock.instant_of(update.time));
+        select! {
+            _ = sleep_until(deadline.unwrap_or_else(Instant::now).into()), if deadline.is_some() => {
+                let position = recording.position_at(clock.now());
+                playback.send_modify(|playback| playback.position = position);
+                if position == recording.updates.len() {
+                    info!("end of recording");
+                }
+            }
+            command = commands.recv() => match command {
+                Some(PlaybackCommand::TogglePause) => {
+                    let now = clock.now();
+                    clock.set(now);
+                    clock.is_paused = !clock.is_paused;
+                    if clock.is_paused {
+                        info!("paused at {:.3}s", now.as_secs_f32());
+                    } else {
+                        info!("resumed at {:.3}s", now.as_secs_f32());
+                    }
+                }
+                Some(PlaybackCommand::Seek { time }) => {
+                    let time = time.min(recording.duration());
+                    clock.set(time);
+                    let position = recording.position_at(time);
+                    playback.send_modify(|playback| {
+                        playback.position = position;
+                        playback.seek_count += 1;
+                    });
+                    info!(
+                        "jumped to {:.3}s (update {position}/{})",
+                        time.as_secs_f32(),
+                        recording.updates.len()
+                    );
+                }
+                None => break,
+            }
+        }
+    }
+}
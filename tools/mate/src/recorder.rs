@@ -0,0 +1,278 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+    time::Instant,
+};
+
+use color_eyre::{
+    eyre::{bail, WrapErr},
+    Result,
+};
+use communication::{
+    client::{CyclerOutput, Output},
+    messages::{
+        BinaryOutputsResponse, BinaryResponse, Fields, Format, OutputsRequest, ParametersRequest,
+        ParametersResponse, Request, TextualDataOrBinaryReference, TextualOutputsResponse,
+        TextualResponse, UpdatePolicy,
+    },
+};
+use futures_util::{SinkExt, StreamExt};
+use log::{info, warn};
+use tokio::{net::TcpStream, select, signal::ctrl_c};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+use crate::recording::{Data, Header, RecordingWriter, Subscription, Update};
+
+const OUTPUT_FIELDS_ID: usize = 0;
+const PARAMETER_FIELDS_ID: usize = 1;
+const FIRST_SUBSCRIPTION_ID: usize = 2;
+
+type Connection = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+pub fn output_subscription(output: CyclerOutput, format: Format) -> Subscription {
+    let path = match output.output {
+        Output::Main { path } => format!("main_outputs.{path}"),
+        Output::Additional { path } => format!("additional_outputs.{path}"),
+    };
+    Subscription::Output {
+        cycler_instance: output.cycler.to_string(),
+        path,
+        format,
+    }
+}
+
+/// Records all updates of `subscriptions` into `recording_path` until interrupted
+pub async fn record(
+    address: &str,
+    subscriptions: Vec<Subscription>,
+    recording_path: impl AsRef<Path>,
+) -> Result<()> {
+    let url = format!("ws://{address}:1337");
+    let (mut connection, _) = connect_async(&url)
+        .await
+        .wrap_err_with(|| format!("failed to connect to {url}"))?;
+    info!("connected to {url}");
+
+    send(
+        &mut connection,
+        Request::Outputs(OutputsRequest::GetFields {
+            id: OUTPUT_FIELDS_ID,
+        }),
+    )
+    .await?;
+    send(
+        &mut connection,
+        Request::Parameters(ParametersRequest::GetFields {
+            id: PARAMETER_FIELDS_ID,
+        }),
+    )
+    .await?;
+    let (output_fields, parameter_fields) = receive_fields(&mut connection).await?;
+
+    for (index, subscription) in subscriptions.iter().enumerate() {
+        let id = FIRST_SUBSCRIPTION_ID + index;
+        let request = match subscription.clone() {
+            Subscription::Output {
+                cycler_instance,
+                path,
+                format,
+            } => Request::Outputs(OutputsRequest::Subscribe {
+                id,
+                cycler_instance,
+                path,
+                format,
+                update_policy: UpdatePolicy::EveryCycle,
+            }),
+            Subscription::Parameter { path } => Request::Parameters(ParametersRequest::Subscribe {
+                id,
+                path,
+                patches: false,
+            }),
+        };
+        send(&mut connection, request).await?;
+    }
+
+    let header = Header {
+        output_fields,
+        parameter_fields,
+        subscriptions,
+    };
+    let mut writer = RecordingWriter::create(recording_path, &header)?;
+    let mut recorder = Recorder {
+        header: &header,
+        writer: &mut writer,
+        start: Instant::now(),
+        binary_references_waiting_for_data: HashMap::new(),
+        binary_data_waiting_for_references: HashMap::new(),
+        number_of_updates: 0,
+    };
+    info!("recording, press Ctrl+C to stop");
+
+    loop {
+        let message = select! {
+            message = connection.next() => message,
+            _ = ctrl_c() => break,
+        };
+        match message {
+            Some(Ok(Message::Text(message))) => {
+                let response = serde_json::from_str(&message)
+                    .wrap_err("failed to deserialize textual response")?;
+                recorder.handle_textual_response(response)?;
+            }
+            Some(Ok(Message::Binary(message))) => {
+                let response = bincode::deserialize(&message)
+                    .wrap_err("failed to deserialize binary response")?;
+                recorder.handle_binary_response(response)?;
+            }
+            Some(Ok(Message::Close(frame))) => {
+                warn!("connection closed: {frame:?}");
+                break;
+            }
+            Some(Ok(_)) => {}
+            Some(Err(error)) => {
+                warn!("connection failed: {error}");
+                break;
+            }
+            None => break,
+        }
+    }
+
+    info!("recorded {} updates", recorder.number_of_updates);
+    writer.flush()
+}
+
+async fn send(connection: &mut Connection, request: Request) -> Result<()> {
+    let message = serde_json::to_string(&request).wrap_err("failed to serialize request")?;
+    connection
+        .send(Message::Text(message))
+        .await
+        .wrap_err("failed to send request")
+}
+
+async fn receive_fields(connection: &mut Connection) -> Result<(Fields, BTreeSet<String>)> {
+    let mut output_fields = None;
+    let mut parameter_fields = None;
+    while output_fields.is_none() || parameter_fields.is_none() {
+        let message = match connection.next().await {
+            Some(message) => message.wrap_err("failed to receive fields")?,
+            None => bail!("connection closed before receiving fields"),
+        };
+        let Message::Text(message) = message else {
+            continue;
+        };
+        match serde_json::from_str(&message).wrap_err("failed to deserialize textual response")? {
+            TextualResponse::Outputs(TextualOutputsResponse::GetFields { fields, .. }) => {
+                output_fields = Some(fields);
+            }
+            TextualResponse::Parameters(ParametersResponse::GetFields { fields, .. }) => {
+                parameter_fields = Some(fields);
+            }
+            _ => {}
+        }
+    }
+    Ok((output_fields.unwrap(), parameter_fields.unwrap()))
+}
+
+struct Recorder<'a> {
+    header: &'a Header,
+    writer: &'a mut RecordingWriter,
+    start: Instant,
+    binary_references_waiting_for_data: HashMap<usize, Update>,
+    binary_data_waiting_for_references: HashMap<usize, Vec<u8>>,
+    number_of_updates: usize,
+}
+
+impl Recorder<'_> {
+    fn handle_textual_response(&mut self, response: TextualResponse) -> Result<()> {
+        match response {
+            TextualResponse::Outputs(TextualOutputsResponse::Subscribe { id, result })
+            | TextualResponse::Parameters(ParametersResponse::Subscribe { id, result }) => {
+                if let Err(reason) = result {
+                    warn!(
+                        "failed to subscribe {:?}: {reason}",
+                        self.header.subscriptions[id - FIRST_SUBSCRIPTION_ID]
+                    );
+                }
+            }
+            TextualResponse::Outputs(TextualOutputsResponse::SubscribedData { items }) => {
+                let time = self.start.elapsed();
+                for (id, item) in items {
+                    let subscription = id - FIRST_SUBSCRIPTION_ID;
+                    match item {
+                        TextualDataOrBinaryReference::TextualData { data } => {
+                            self.write(Update {
+                                time,
+                                subscription,
+                                data: Data::from_value(&data)?,
+                            })?;
+                        }
+                        TextualDataOrBinaryReference::BinaryReference { reference_id } => {
+                            match self
+                                .binary_data_waiting_for_references
+                                .remove(&reference_id)
+                            {
+                                Some(data) => self.write(Update {
+                                    time,
+                                    subscription,
+                                    data: Data::Binary { data },
+                                })?,
+                                None => {
+                                    self.binary_references_waiting_for_data.insert(
+                                        reference_id,
+                                        Update {
+                                            time,
+                                            subscription,
+                                            data: Data::Binary { data: Vec::new() },
+                                        },
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            TextualResponse::Parameters(ParametersResponse::SubscribedData {
+                subscription_id,
+                data,
+            }) => {
+                self.write(Update {
+                    time: self.start.elapsed(),
+                    subscription: subscription_id - FIRST_SUBSCRIPTION_ID,
+                    data: Data::from_value(&data)?,
+                })?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_binary_response(&mut self, response: BinaryResponse) -> Result<()> {
+        let BinaryResponse::Outputs(BinaryOutputsResponse::SubscribedData { referenced_items }) =
+            response
+        else {
+            return Ok(());
+        };
+        for (reference_id, data) in referenced_items {
+            match self
+                .binary_references_waiting_for_data
+                .remove(&reference_id)
+            {
+                Some(update) => self.write(Update {
+                    data: Data::Binary { data },
+                    ..update
+                })?,
+                None => {
+                    self.binary_data_waiting_for_references
+                        .insert(reference_id, data);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, update: Update) -> Result<()> {
+        self.writer.write(&update)?;
+        self.number_of_updates += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::{BufReader, BufWriter, ErrorKind, Write},
+    path::Path,
+    time::Duration,
+};
+
+use bincode::{deserialize_from, serialize_into, ErrorKind as BincodeErrorKind};
+use color_eyre::{eyre::WrapErr, Result};
+use communication::messages::{CyclerInstance, Fields, Format, Path as HierarchyPath};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Subscription {
+    Output {
+        cycler_instance: CyclerInstance,
+        path: HierarchyPath,
+        format: Format,
+    },
+    Parameter {
+        path: HierarchyPath,
+    },
+}
+
+/// Written once at the beginning of a recording, describes what the recorded server offered
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Header {
+    pub output_fields: Fields,
+    pub parameter_fields: BTreeSet<HierarchyPath>,
+    pub subscriptions: Vec<Subscription>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum Data {
+    /// Bincode does not support self-describing values, textual data is therefore kept as JSON
+    Textual {
+        json: String,
+    },
+    Binary {
+        data: Vec<u8>,
+    },
+}
+
+impl Data {
+    pub fn from_value(value: &Value) -> Result<Self> {
+        Ok(Self::Textual {
+            json: serde_json::to_string(value).wrap_err("failed to serialize textual data")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Update {
+    /// Time since the start of the recording
+    pub time: Duration,
+    /// Index into the subscriptions of the header
+    pub subscription: usize,
+    pub data: Data,
+}
+
+pub struct RecordingWriter {
+    writer: BufWriter<File>,
+}
+
+impl RecordingWriter {
+    pub fn create(path: impl AsRef<Path>, header: &Header) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::create(path).wrap_err_with(|| format!("failed to create {path:?}"))?;
+        let mut writer = BufWriter::new(file);
+        serialize_into(&mut writer, header).wrap_err("failed to write recording header")?;
+        Ok(Self { writer })
+    }
+
+    pub fn write(&mut self, update: &Update) -> Result<()> {
+        serialize_into(&mut self.writer, update).wrap_err("failed to write update")
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush().wrap_err("failed to flush recording")
+    }
+}
+
+pub struct Recording {
+    pub header: Header,
+    pub updates: Vec<Update>,
+}
+
+impl Recording {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).wrap_err_with(|| format!("failed to open {path:?}"))?;
+        let mut reader = BufReader::new(file);
+        let header = deserialize_from(&mut reader).wrap_err("failed to read recording header")?;
+        let mut updates = Vec::new();
+        loop {
+            match deserialize_from(&mut reader) {
+                Ok(update) => updates.push(update),
+                Err(error) => match *error {
+                    BincodeErrorKind::Io(error) if error.kind() == ErrorKind::UnexpectedEof => {
+                        break
+                    }
+                    error => {
+                        warn!(
+                            "ignoring remainder of recording after {} updates: {error}",
+                            updates.len()
+                        );
+                        break;
+                    }
+                },
+            }
+        }
+        // binary data arrives separately from its reference, which may reorder updates slightly
+        updates.sort_by_key(|update: &Update| update.time);
+        Ok(Self { header, updates })
+    }
+
+    /// Number of updates that happened up to and including `time`
+    pub fn position_at(&self, time: Duration) -> usize {
+        self.updates.partition_point(|update| update.time <= time)
+    }
+
+    /// The most recent update of `subscription` among the first `position` updates
+    pub fn latest_update(&self, subscription: usize, position: usize) -> Option<&Update> {
+        self.updates[..position]
+            .iter()
+            .rev()
+            .find(|update| update.subscription == subscription)
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.updates
+            .last()
+            .map(|update| update.time)
+            .unwrap_or_default()
+    }
+}
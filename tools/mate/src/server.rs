@@ -0,0 +1,461 @@
+use std::{collections::HashMap, ops::Range, sync::Arc};
+
+use color_eyre::{eyre::WrapErr, Result};
+use communication::messages::{
+    BinaryOutputsResponse, BinaryResponse, InjectionsRequest, InjectionsResponse, OutputsRequest,
+    ParametersRequest, ParametersResponse, Request, TextualDataOrBinaryReference,
+    TextualOutputsResponse, TextualResponse,
+};
+use futures_util::{SinkExt, StreamExt};
+use log::{error, info};
+use serde_json::Value;
+use tokio::{
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    select, spawn,
+    sync::watch,
+};
+use tokio_tungstenite::{accept_async, tungstenite::Message};
+
+use crate::{
+    player::Playback,
+    recording::{Data, Recording, Subscription, Update},
+};
+
+const READ_ONLY_REASON: &str = "recordings are read-only";
+
+/// Accepts twix connections and serves the recording to them as if it was a robot
+pub async fn serve(
+    addresses: impl ToSocketAddrs,
+    recording: Arc<Recording>,
+    playback: watch::Receiver<Playback>,
+) -> Result<()> {
+    let listener = TcpListener::bind(addresses)
+        .await
+        .wrap_err("failed to bind TCP listener")?;
+    loop {
+        let (stream, peer_address) = listener
+            .accept()
+            .await
+            .wrap_err("failed to accept connection")?;
+        info!("{peer_address} connected");
+        let recording = recording.clone();
+        let playback = playback.clone();
+        spawn(async move {
+            if let Err(error) = connection(stream, recording, playback).await {
+                error!("connection {peer_address} failed: {error:?}");
+            }
+            info!("{peer_address} disconnected");
+        });
+    }
+}
+
+async fn connection(
+    stream: TcpStream,
+    recording: Arc<Recording>,
+    mut playback: watch::Receiver<Playback>,
+) -> Result<()> {
+    let websocket_stream = accept_async(stream)
+        .await
+        .wrap_err("failed to accept WebSocket connection")?;
+    let (mut writer, mut reader) = websocket_stream.split();
+    let mut last_playback = *playback.borrow_and_update();
+    let mut client = Client {
+        recording: &recording,
+        output_subscriptions: HashMap::new(),
+        parameter_subscriptions: HashMap::new(),
+        next_binary_reference_id: 0,
+        messages: Vec::new(),
+    };
+
+    loop {
+        select! {
+            message = reader.next() => match message {
+                Some(Ok(Message::Text(message))) => {
+                    let request = serde_json::from_str(&message)
+                        .wrap_err("failed to deserialize request")?;
+                    client.handle_request(request, last_playback.position)?;
+                }
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_)) => {}
+                Some(Err(error)) => return Err(error).wrap_err("failed to read WebSocket message"),
+            },
+            changed = playback.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                let current_playback = *playback.borrow_and_update();
+                if current_playback.seek_count != last_playback.seek_count
+                    || current_playback.position < last_playback.position
+                {
+                    client.send_latest_updates(current_playback.position)?;
+                } else {
+                    client.send_updates(last_playback.position..current_playback.position)?;
+                }
+                last_playback = current_playback;
+            }
+        }
+        for message in client.messages.drain(..) {
+            writer
+                .send(message)
+                .await
+                .wrap_err("failed to write WebSocket message")?;
+        }
+    }
+    Ok(())
+}
+
+struct Client<'a> {
+    recording: &'a Recording,
+    /// Maps subscription IDs of the client to subscriptions of the recording
+    output_subscriptions: HashMap<usize, usize>,
+    parameter_subscriptions: HashMap<usize, usize>,
+    next_binary_reference_id: usize,
+    /// Messages to be sent to the client
+    messages: Vec<Message>,
+}
+
+impl Client<'_> {
+    fn handle_request(&mut self, request: Request, position: usize) -> Result<()> {
+        match request {
+            Request::Outputs(request) => self.handle_outputs_request(request, position),
+            Request::Parameters(request) => self.handle_parameters_request(request, position),
+            Request::Injections(request) => self.handle_injections_request(request),
+        }
+    }
+
+    fn handle_outputs_request(&mut self, request: OutputsRequest, position: usize) -> Result<()> {
+        match request {
+            OutputsRequest::GetFields { id } => {
+                self.send_textual(TextualResponse::Outputs(
+                    TextualOutputsResponse::GetFields {
+                        id,
+                        fields: self.recording.header.output_fields.clone(),
+                    },
+                ))?;
+            }
+            OutputsRequest::GetNext {
+                id,
+                cycler_instance,
+                path,
+                format,
+            } => {
+                let subscription = Subscription::Output {
+                    cycler_instance,
+                    path,
+                    format,
+                };
+                let update = self
+                    .find_subscription(&subscription)
+                    .and_then(|index| {
+                        self.recording
+                            .latest_update(index, position)
+                            .ok_or_else(|| format!("no data recorded yet for {subscription:?}"))
+                    })
+                    .cloned();
+                match update {
+                    Ok(update) => match update.data {
+                        Data::Textual { json } => {
+                            self.send_textual(TextualResponse::Outputs(
+                                TextualOutputsResponse::GetNext {
+                                    id,
+                                    result: Ok(TextualDataOrBinaryReference::TextualData {
+                                        data: parse_json(&json)?,
+                                    }),
+                                },
+                            ))?;
+                        }
+                        Data::Binary { data } => {
+                            let reference_id = self.next_binary_reference_id();
+                            self.send_textual(TextualResponse::Outputs(
+                                TextualOutputsResponse::GetNext {
+                                    id,
+                                    result: Ok(TextualDataOrBinaryReference::BinaryReference {
+                                        reference_id,
+                                    }),
+                                },
+                            ))?;
+                            self.send_binary(BinaryResponse::Outputs(
+                                BinaryOutputsResponse::GetNext { reference_id, data },
+                            ))?;
+                        }
+                    },
+                    Err(reason) => {
+                        self.send_textual(TextualResponse::Outputs(
+                            TextualOutputsResponse::GetNext {
+                                id,
+                                result: Err(reason),
+                            },
+                        ))?;
+                    }
+                }
+            }
+            // update policies are ignored, replays are served locally where bandwidth is plenty
+            OutputsRequest::Subscribe {
+                id,
+                cycler_instance,
+                path,
+                format,
+                ..
+            } => {
+                let result = self.find_subscription(&Subscription::Output {
+                    cycler_instance,
+                    path,
+                    format,
+                });
+                self.send_textual(TextualResponse::Outputs(
+                    TextualOutputsResponse::Subscribe {
+                        id,
+                        result: result.clone().map(|_| ()),
+                    },
+                ))?;
+                if let Ok(index) = result {
+                    self.output_subscriptions.insert(id, index);
+                    if let Some(update) = self.recording.latest_update(index, position) {
+                        self.send_output_update(update)?;
+                    }
+                }
+            }
+            OutputsRequest::Unsubscribe {
+                id,
+                subscription_id,
+            } => {
+                let result = match self.output_subscriptions.remove(&subscription_id) {
+                    Some(_) => Ok(()),
+                    None => Err(format!(
+                        "never subscribed with subscription id {subscription_id}"
+                    )),
+                };
+                self.send_textual(TextualResponse::Outputs(
+                    TextualOutputsResponse::Unsubscribe { id, result },
+                ))?;
+            }
+            OutputsRequest::UnsubscribeEverything => self.output_subscriptions.clear(),
+        }
+        Ok(())
+    }
+
+    fn handle_parameters_request(
+        &mut self,
+        request: ParametersRequest,
+        position: usize,
+    ) -> Result<()> {
+        let response = match request {
+            ParametersRequest::GetFields { id } => ParametersResponse::GetFields {
+                id,
+                fields: self.recording.header.parameter_fields.clone(),
+            },
+            ParametersRequest::GetCurrent { id, path } => {
+                let subscription = Subscription::Parameter { path };
+                let update = self.find_subscription(&subscription).and_then(|index| {
+                    self.recording
+                        .latest_update(index, position)
+                        .ok_or_else(|| format!("no data recorded yet for {subscription:?}"))
+                });
+                let result = match update {
+                    Ok(update) => Ok(update_to_value(update)?),
+                    Err(reason) => Err(reason),
+                };
+                ParametersResponse::GetCurrent { id, result }
+            }
+            // whole values are always sent, clients accept them regardless of requested patches
+            ParametersRequest::Subscribe { id, path, .. } => {
+                let result = self.find_subscription(&Subscription::Parameter { path });
+                self.send_textual(TextualResponse::Parameters(ParametersResponse::Subscribe {
+                    id,
+                    result: result.clone().map(|_| ()),
+                }))?;
+                if let Ok(index) = result {
+                    self.parameter_subscriptions.insert(id, index);
+                    if let Some(update) = self.recording.latest_update(index, position) {
+                        self.send_parameter_update(update)?;
+                    }
+                }
+                return Ok(());
+            }
+            ParametersRequest::Unsubscribe {
+                id,
+                subscription_id,
+            } => {
+                let result = match self.parameter_subscriptions.remove(&subscription_id) {
+                    Some(_) => Ok(()),
+                    None => Err(format!(
+                        "never subscribed with subscription id {subscription_id}"
+                    )),
+                };
+                ParametersResponse::Unsubscribe { id, result }
+            }
+            ParametersRequest::UnsubscribeEverything => {
+                self.parameter_subscriptions.clear();
+                return Ok(());
+            }
+            ParametersRequest::Update { id, .. } => ParametersResponse::Update {
+                id,
+                result: Err(READ_ONLY_REASON.to_string()),
+            },
+            ParametersRequest::LoadFromDisk { id } => ParametersResponse::LoadFromDisk {
+                id,
+                result: Err(READ_ONLY_REASON.to_string()),
+            },
+            ParametersRequest::StoreToDisk { id, .. } => ParametersResponse::StoreToDisk {
+                id,
+                result: Err(READ_ONLY_REASON.to_string()),
+            },
+        };
+        self.send_textual(TextualResponse::Parameters(response))
+    }
+
+    fn handle_injections_request(&mut self, request: InjectionsRequest) -> Result<()> {
+        let response = match request {
+            InjectionsRequest::Set { id, .. } => InjectionsResponse::Set {
+                id,
+                result: Err(READ_ONLY_REASON.to_string()),
+            },
+            InjectionsRequest::Unset { id, .. } => InjectionsResponse::Unset {
+                id,
+                result: Err(READ_ONLY_REASON.to_string()),
+            },
+            InjectionsRequest::UnsetEverything => return Ok(()),
+        };
+        self.send_textual(TextualResponse::Injections(response))
+    }
+
+    fn find_subscription(&self, subscription: &Subscription) -> Result<usize, String> {
+        self.recording
+            .header
+            .subscriptions
+            .iter()
+            .position(|recorded_subscription| recorded_subscription == subscription)
+            .ok_or_else(|| format!("{subscription:?} was not recorded"))
+    }
+
+    fn send_updates(&mut self, positions: Range<usize>) -> Result<()> {
+        let recording = self.recording;
+        for update in &recording.updates[positions] {
+            match recording.header.subscriptions[update.subscription] {
+                Subscription::Output { .. } => self.send_output_update(update)?,
+                Subscription::Parameter { .. } => self.send_parameter_update(update)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends the most recent data of every subscription, e.g. after seeking
+    fn send_latest_updates(&mut self, position: usize) -> Result<()> {
+        let recording = self.recording;
+        let mut subscriptions: Vec<_> = self
+            .output_subscriptions
+            .values()
+            .chain(self.parameter_subscriptions.values())
+            .copied()
+            .collect();
+        subscriptions.sort();
+        subscriptions.dedup();
+        for subscription in subscriptions {
+            if let Some(update) = recording.latest_update(subscription, position) {
+                match recording.header.subscriptions[subscription] {
+                    Subscription::Output { .. } => self.send_output_update(update)?,
+                    Subscription::Parameter { .. } => self.send_parameter_update(update)?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn send_output_update(&mut self, update: &Update) -> Result<()> {
+        let subscription_ids: Vec<_> = self
+            .output_subscriptions
+            .iter()
+            .filter(|(_, subscription)| **subscription == update.subscription)
+            .map(|(subscription_id, _)| *subscription_id)
+            .collect();
+        if subscription_ids.is_empty() {
+            return Ok(());
+        }
+        match &update.data {
+            Data::Textual { json } => {
+                let data = parse_json(json)?;
+                let items = subscription_ids
+                    .into_iter()
+                    .map(|subscription_id| {
+                        (
+                            subscription_id,
+                            TextualDataOrBinaryReference::TextualData { data: data.clone() },
+                        )
+                    })
+                    .collect();
+                self.send_textual(TextualResponse::Outputs(
+                    TextualOutputsResponse::SubscribedData { items },
+                ))
+            }
+            Data::Binary { data } => {
+                let mut items = HashMap::new();
+                let mut referenced_items = HashMap::new();
+                for subscription_id in subscription_ids {
+                    let reference_id = self.next_binary_reference_id();
+                    items.insert(
+                        subscription_id,
+                        TextualDataOrBinaryReference::BinaryReference { reference_id },
+                    );
+                    referenced_items.insert(reference_id, data.clone());
+                }
+                self.send_textual(TextualResponse::Outputs(
+                    TextualOutputsResponse::SubscribedData { items },
+                ))?;
+                self.send_binary(BinaryResponse::Outputs(
+                    BinaryOutputsResponse::SubscribedData { referenced_items },
+                ))
+            }
+        }
+    }
+
+    fn send_parameter_update(&mut self, update: &Update) -> Result<()> {
+        let subscription_ids: Vec<_> = self
+            .parameter_subscriptions
+            .iter()
+            .filter(|(_, subscription)| **subscription == update.subscription)
+            .map(|(subscription_id, _)| *subscription_id)
+            .collect();
+        if subscription_ids.is_empty() {
+            return Ok(());
+        }
+        let data = update_to_value(update)?;
+        for subscription_id in subscription_ids {
+            self.send_textual(TextualResponse::Parameters(
+                ParametersResponse::SubscribedData {
+                    subscription_id,
+                    data: data.clone(),
+                },
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn next_binary_reference_id(&mut self) -> usize {
+        let reference_id = self.next_binary_reference_id;
+        self.next_binary_reference_id = self.next_binary_reference_id.wrapping_add(1);
+        reference_id
+    }
+
+    fn send_textual(&mut self, response: TextualResponse) -> Result<()> {
+        let message = serde_json::to_string(&response).wrap_err("failed to serialize JSON")?;
+        self.messages.push(Message::Text(message));
+        Ok(())
+    }
+
+    fn send_binary(&mut self, response: BinaryResponse) -> Result<()> {
+        let message = bincode::serialize(&response).wrap_err("failed to serialize Bincode")?;
+        self.messages.push(Message::Binary(message));
+        Ok(())
+    }
+}
+
+fn update_to_value(update: &Update) -> Result<Value> {
+    match &update.data {
+        Data::Textual { json } => parse_json(json),
+        Data::Binary { .. } => Ok(Value::Null),
+    }
+}
+
+fn parse_json(json: &str) -> Result<Value> {
+    serde_json::from_str(json).wrap_err("failed to parse recorded JSON")
+}
@@ -15,7 +15,7 @@ pub enum Arguments {
         #[arg(required = true)]
         naos: Vec<NaoAddress>,
     },
-    /// Download logs from the NAOs
+    /// Download logs from the NAOs, including the audit log of parameter changes and injections
     Download {
         /// Directory where to store the downloaded logs (will be created if not existing)
         log_directory: PathBuf,
@@ -12,6 +12,7 @@ use gammaray::{gammaray, Arguments as GammarayArguments};
 use hulk::{hulk, Arguments as HulkArguments};
 use location::{location, Arguments as LocationArguments};
 use logs::{logs, Arguments as LogsArguments};
+use motion::{motion, Arguments as MotionArguments};
 use ping::{ping, Arguments as PingArguments};
 use player_number::{player_number, Arguments as PlayerNumberArguments};
 use post_game::{post_game, Arguments as PostGameArguments};
@@ -34,6 +35,7 @@ mod gammaray;
 mod hulk;
 mod location;
 mod logs;
+mod motion;
 mod parsers;
 mod ping;
 mod player_number;
@@ -98,6 +100,9 @@ async fn main() -> Result<()> {
         Command::Logs(arguments) => logs(arguments)
             .await
             .wrap_err("failed to execute logs command")?,
+        Command::Motion(arguments) => motion(arguments)
+            .await
+            .wrap_err("failed to execute motion command")?,
         Command::Ping(arguments) => ping(arguments).await,
         Command::Playernumber(arguments) => player_number(arguments, &repository?)
             .await
@@ -175,6 +180,9 @@ enum Command {
     /// Logging on the NAO
     #[command(subcommand)]
     Logs(LogsArguments),
+    /// Convert motion files
+    #[command(subcommand)]
+    Motion(MotionArguments),
     /// Change player numbers of the NAOs in local parameters
     Playernumber(PlayerNumberArguments),
     /// Ping NAOs
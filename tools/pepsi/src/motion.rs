@@ -0,0 +1,32 @@
+use std::{fs::File, path::PathBuf};
+
+use clap::Subcommand;
+use color_eyre::{eyre::WrapErr, Result};
+use motionfile::MotionFile;
+use serde_json::to_writer_pretty;
+
+#[derive(Subcommand)]
+pub enum Arguments {
+    /// Convert a .motion2 keyframe file into a motion file
+    Convert {
+        /// The .motion2 file to convert
+        #[arg(required = true)]
+        input: PathBuf,
+        /// The motion file to write (defaults to the input with .json extension)
+        output: Option<PathBuf>,
+    },
+}
+
+pub async fn motion(arguments: Arguments) -> Result<()> {
+    match arguments {
+        Arguments::Convert { input, output } => {
+            let motion_file = MotionFile::from_motion2_path(&input)?;
+            let output = output.unwrap_or_else(|| input.with_extension("json"));
+            let file = File::create(&output)
+                .wrap_err_with(|| format!("failed to create motion file {output:?}"))?;
+            to_writer_pretty(file, &motion_file)
+                .wrap_err_with(|| format!("failed to write motion file {output:?}"))?;
+        }
+    }
+    Ok(())
+}
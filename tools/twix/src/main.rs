@@ -29,8 +29,9 @@ use log::error;
 use nao::Nao;
 use panel::Panel;
 use panels::{
-    BehaviorSimulatorPanel, ImagePanel, ImageSegmentsPanel, LookAtPanel, ManualCalibrationPanel,
-    MapPanel, ParameterPanel, PlotPanel, RemotePanel, TextPanel, VisionTunerPanel,
+    BehaviorSimulatorPanel, DecisionTracePanel, ImagePanel, ImageSegmentsPanel, LookAtPanel,
+    ManualCalibrationPanel, MapPanel, ParameterPanel, PlotPanel, RemotePanel, TextPanel,
+    VisionTunerPanel,
 };
 use repository::{get_repository_root, Repository};
 use serde_json::{from_str, to_string, Value};
@@ -49,6 +50,7 @@ mod players_value_buffer;
 mod repository_parameters;
 mod selectable_panel_macro;
 mod twix_painter;
+mod update_policy_edit;
 mod value_buffer;
 mod visuals;
 
@@ -130,6 +132,7 @@ impl ReachableNaos {
 
 impl_selectable_panel!(
     BehaviorSimulatorPanel,
+    DecisionTracePanel,
     ImagePanel,
     ImageSegmentsPanel,
     LookAtPanel,
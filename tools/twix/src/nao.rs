@@ -2,7 +2,7 @@ use std::{collections::BTreeSet, sync::Mutex};
 
 use communication::{
     client::{Communication, ConnectionStatus, CyclerOutput},
-    messages::{Fields, Path},
+    messages::{Fields, Path, UpdatePolicy},
 };
 
 use serde_json::Value;
@@ -58,13 +58,25 @@ impl Nao {
     }
 
     pub fn subscribe_output(&self, output: CyclerOutput) -> ValueBuffer {
+        self.subscribe_output_with_update_policy(output, UpdatePolicy::EveryCycle)
+    }
+
+    pub fn subscribe_output_with_update_policy(
+        &self,
+        output: CyclerOutput,
+        update_policy: UpdatePolicy,
+    ) -> ValueBuffer {
         let _guard = self.runtime.enter();
-        ValueBuffer::output(self.communication.clone(), output)
+        ValueBuffer::output(self.communication.clone(), output, update_policy)
     }
 
-    pub fn subscribe_image(&self, output: CyclerOutput) -> ImageBuffer {
+    pub fn subscribe_image(
+        &self,
+        output: CyclerOutput,
+        update_policy: UpdatePolicy,
+    ) -> ImageBuffer {
         let _guard = self.runtime.enter();
-        ImageBuffer::new(self.communication.clone(), output)
+        ImageBuffer::new(self.communication.clone(), output, update_policy)
     }
 
     pub fn subscribe_parameter(&self, path: &str) -> ValueBuffer {
@@ -0,0 +1,64 @@
+use std::{str::FromStr, sync::Arc};
+
+use communication::client::CyclerOutput;
+use eframe::{
+    egui::{Grid, Response, RichText, ScrollArea, Ui, Widget},
+    epaint::Color32,
+};
+use serde_json::Value;
+use types::decision_trace::{ActionOutcome, DecisionTrace};
+
+use crate::{nao::Nao, panel::Panel, value_buffer::ValueBuffer};
+
+pub struct DecisionTracePanel {
+    decision_trace_buffer: ValueBuffer,
+}
+
+impl Panel for DecisionTracePanel {
+    const NAME: &'static str = "Decision Trace";
+
+    fn new(nao: Arc<Nao>, _value: Option<&Value>) -> Self {
+        let decision_trace_buffer = nao.subscribe_output(
+            CyclerOutput::from_str("Control.additional_outputs.decision_trace")
+                .expect("Failed to subscribe to additional_outputs.decision_trace"),
+        );
+        Self {
+            decision_trace_buffer,
+        }
+    }
+}
+
+impl Widget for &mut DecisionTracePanel {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let decision_trace: DecisionTrace = match self.decision_trace_buffer.parse_latest() {
+            Ok(decision_trace) => decision_trace,
+            Err(error) => return ui.label(format!("{error:#?}")),
+        };
+        ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                Grid::new("decision_trace")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Action");
+                        ui.strong("Outcome");
+                        ui.end_row();
+                        for decision in decision_trace {
+                            ui.label(format!("{:?}", decision.action));
+                            match decision.outcome {
+                                ActionOutcome::Selected => {
+                                    ui.label(RichText::new("Selected").color(Color32::GREEN))
+                                }
+                                ActionOutcome::Rejected { rejection } => {
+                                    ui.label(format!("{rejection:?}"))
+                                }
+                            };
+                            ui.end_row();
+                        }
+                    })
+            })
+            .inner
+            .response
+    }
+}
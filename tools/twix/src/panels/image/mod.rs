@@ -1,7 +1,10 @@
 use std::{str::FromStr, sync::Arc};
 
 use color_eyre::{eyre::eyre, Result};
-use communication::client::{Cycler, CyclerOutput, Output};
+use communication::{
+    client::{Cycler, CyclerOutput, Output},
+    messages::UpdatePolicy,
+};
 use eframe::{
     egui::{ComboBox, Response, TextureOptions, Ui, Widget},
     emath::Rect,
@@ -17,6 +20,7 @@ use crate::{
     nao::Nao,
     panel::Panel,
     twix_painter::{CoordinateSystem, TwixPainter},
+    update_policy_edit::UpdatePolicyEdit,
 };
 
 use self::{cycler_selector::VisionCyclerSelector, overlay::Overlays};
@@ -50,6 +54,7 @@ pub struct ImagePanel {
     cycler_selector: VisionCyclerSelector,
     overlays: Overlays,
     image_kind: ImageKind,
+    update_policy: UpdatePolicy,
 }
 
 impl Panel for ImagePanel {
@@ -76,11 +81,15 @@ impl Panel for ImagePanel {
             .and_then(|value| value.get("image_kind"))
             .and_then(|value| from_value(value.clone()).ok())
             .unwrap_or(ImageKind::YCbCr422);
+        let update_policy = value
+            .and_then(|value| value.get("update_policy"))
+            .and_then(|value| from_value(value.clone()).ok())
+            .unwrap_or_default();
         let output = CyclerOutput {
             cycler,
             output: image_kind.as_output(),
         };
-        let image_buffer = nao.subscribe_image(output);
+        let image_buffer = nao.subscribe_image(output, update_policy);
         let cycler_selector = VisionCyclerSelector::new(cycler);
         let overlays = Overlays::new(
             nao.clone(),
@@ -93,6 +102,7 @@ impl Panel for ImagePanel {
             cycler_selector,
             overlays,
             image_kind,
+            update_policy,
         }
     }
 
@@ -105,6 +115,7 @@ impl Panel for ImagePanel {
             "cycler": cycler.to_string(),
             "overlays": overlays,
             "image_kind": image_kind,
+            "update_policy": self.update_policy,
         })
     }
 }
@@ -117,7 +128,7 @@ impl Widget for &mut ImagePanel {
                     cycler: self.cycler_selector.selected_cycler(),
                     output: self.image_kind.as_output(),
                 };
-                self.image_buffer = self.nao.subscribe_image(output);
+                self.image_buffer = self.nao.subscribe_image(output, self.update_policy);
                 self.overlays
                     .update_cycler(self.cycler_selector.selected_cycler());
             }
@@ -143,10 +154,24 @@ impl Widget for &mut ImagePanel {
                     cycler: self.cycler_selector.selected_cycler(),
                     output: self.image_kind.as_output(),
                 };
-                self.image_buffer = self.nao.subscribe_image(output);
+                self.image_buffer = self.nao.subscribe_image(output, self.update_policy);
                 self.overlays
                     .update_cycler(self.cycler_selector.selected_cycler());
             }
+            let update_policy_id = ui.id().with("update_policy");
+            if ui
+                .add(UpdatePolicyEdit::new(
+                    update_policy_id,
+                    &mut self.update_policy,
+                ))
+                .changed()
+            {
+                let output = CyclerOutput {
+                    cycler: self.cycler_selector.selected_cycler(),
+                    output: self.image_kind.as_output(),
+                };
+                self.image_buffer = self.nao.subscribe_image(output, self.update_policy);
+            }
             self.overlays
                 .combo_box(ui, self.cycler_selector.selected_cycler());
         });
@@ -1,6 +1,7 @@
 use color_eyre::Result;
 use communication::client::{Cycler, CyclerOutput, Output};
 use eframe::epaint::{Color32, Stroke};
+use spl_network_messages::Team;
 use types::detected_robots::BoundingBox;
 
 use crate::{
@@ -28,7 +29,11 @@ impl Overlay for RobotDetection {
     fn paint(&self, painter: &TwixPainter) -> Result<()> {
         let boxes: Vec<BoundingBox> = self.boxes.require_latest()?;
         for robot_box in &boxes {
-            let color = Color32::RED;
+            let color = match robot_box.team {
+                Team::Hulks => Color32::GREEN,
+                Team::Opponent => Color32::RED,
+                Team::Uncertain => Color32::YELLOW,
+            };
             let line_stroke = Stroke::new(2.0, color);
             painter.rect_stroke(
                 robot_box.center - robot_box.size / 2.0,
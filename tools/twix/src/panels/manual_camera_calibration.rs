@@ -1,9 +1,10 @@
 use color_eyre::eyre::Context;
+use communication::client::CyclerOutput;
 use eframe::egui::{Response, Slider, Ui, Widget};
 use log::{error, info};
 use nalgebra::Vector3;
 use serde_json::Value;
-use std::{ops::RangeInclusive, sync::Arc};
+use std::{ops::RangeInclusive, str::FromStr, sync::Arc};
 use tokio::sync::mpsc;
 
 use crate::{
@@ -20,42 +21,62 @@ struct CameraParameterSubscriptions<DeserializedValueType> {
     value_buffer: ValueBuffer,
     value: DeserializedValueType,
     update_notify_receiver: mpsc::Receiver<()>,
+    calibration_result_key: String,
 }
 
 pub struct ManualCalibrationPanel {
     nao: Arc<Nao>,
     repository_parameters: RepositoryParameters,
-    extrinsic_rotation_subscriptions: [CameraParameterSubscriptions<Option<SubscribedType>>; 2],
+    rotation_subscriptions: [CameraParameterSubscriptions<Option<SubscribedType>>; 3],
+    calibration_result: ValueBuffer,
 }
 
-const CAMERA_KEY_BASE: &str = "camera_matrix_parameters.vision_";
-const ROTATIONS: &str = ".extrinsic_rotations";
-
 impl Panel for ManualCalibrationPanel {
     const NAME: &'static str = "Manual Calibration";
 
     fn new(nao: Arc<Nao>, _value: Option<&Value>) -> Self {
-        let extrinsic_rotation_subscriptions = ["Top", "Bottom"].map(|name| {
-            let path = CAMERA_KEY_BASE.to_owned() + name.to_lowercase().as_str() + ROTATIONS;
-
+        let rotation_subscriptions = [
+            (
+                "Top Camera",
+                "camera_matrix_parameters.vision_top.extrinsic_rotations",
+                "extrinsic_rotations_top",
+            ),
+            (
+                "Bottom Camera",
+                "camera_matrix_parameters.vision_bottom.extrinsic_rotations",
+                "extrinsic_rotations_bottom",
+            ),
+            (
+                "Robot",
+                "camera_matrix_parameters.correction_in_robot",
+                "correction_in_robot",
+            ),
+        ]
+        .map(|(label, path, calibration_result_key)| {
             let (update_notify_sender, update_notify_receiver) = mpsc::channel(1);
-            let value_buffer = subscribe(nao.clone(), &path, update_notify_sender).unwrap();
+            let value_buffer = subscribe(nao.clone(), path, update_notify_sender).unwrap();
 
             info!("Subscribing to path {}", path);
 
             CameraParameterSubscriptions {
-                human_friendly_label: name.to_string(),
-                path,
+                human_friendly_label: label.to_string(),
+                path: path.to_string(),
                 value_buffer,
                 value: None,
                 update_notify_receiver,
+                calibration_result_key: calibration_result_key.to_string(),
             }
         });
+        let calibration_result = nao.subscribe_output(
+            CyclerOutput::from_str("Control.additional_outputs.camera_calibration.result")
+                .expect("Failed to subscribe to additional_outputs.camera_calibration.result"),
+        );
 
         Self {
             nao,
             repository_parameters: RepositoryParameters::try_new().unwrap(),
-            extrinsic_rotation_subscriptions,
+            rotation_subscriptions,
+            calibration_result,
         }
     }
 }
@@ -65,6 +86,7 @@ fn add_extrinsic_calibration_ui_components(
     nao: Arc<Nao>,
     repository_parameters: &RepositoryParameters,
     extrinsic_rotations_subscription: &mut CameraParameterSubscriptions<Option<SubscribedType>>,
+    calibration_result: &ValueBuffer,
 ) {
     let extrinsic_rotations_buffer = &extrinsic_rotations_subscription.value_buffer;
     let mut extrinsic_rotations_option = &mut extrinsic_rotations_subscription.value;
@@ -74,6 +96,15 @@ fn add_extrinsic_calibration_ui_components(
         &mut extrinsic_rotations_subscription.update_notify_receiver;
 
     let extrinsic_maximum_degrees = 15.0;
+    let calibrated_extrinsic_rotations = calibration_result.get_latest().ok().and_then(|result| {
+        serde_json::from_value::<SubscribedType>(
+            result
+                .get(&extrinsic_rotations_subscription.calibration_result_key)?
+                .clone(),
+        )
+        .ok()
+    });
+    let mut changed = false;
 
     ui.horizontal(|ui| {
         match extrinsic_rotations_buffer.get_latest() {
@@ -91,7 +122,7 @@ fn add_extrinsic_calibration_ui_components(
             }
         }
 
-        ui.label(format!("{label:#} Camera"));
+        ui.label(label);
 
         add_save_button(
             ui,
@@ -103,12 +134,18 @@ fn add_extrinsic_calibration_ui_components(
             nao.clone(),
             repository_parameters,
         );
+
+        ui.add_enabled_ui(calibrated_extrinsic_rotations.is_some(), |ui| {
+            if ui.button("Use Automatic Calibration").clicked() {
+                *extrinsic_rotations_option = calibrated_extrinsic_rotations;
+                changed = true;
+            }
+        });
     });
 
     ui.style_mut().spacing.slider_width = ui.available_size().x - 100.0;
-    let mut changed = false;
     ui.label(format!(
-        "Extrinsic Rotations [{}°, {}°]",
+        "Rotations [{}°, {}°]",
         -extrinsic_maximum_degrees, extrinsic_maximum_degrees
     ));
     match &mut extrinsic_rotations_option {
@@ -147,12 +184,13 @@ fn add_extrinsic_calibration_ui_components(
 impl Widget for &mut ManualCalibrationPanel {
     fn ui(self, ui: &mut Ui) -> Response {
         ui.vertical(|ui| {
-            for extrinsic_rotation_subscription in &mut self.extrinsic_rotation_subscriptions {
+            for rotation_subscription in &mut self.rotation_subscriptions {
                 add_extrinsic_calibration_ui_components(
                     ui,
                     self.nao.clone(),
                     &self.repository_parameters,
-                    extrinsic_rotation_subscription,
+                    rotation_subscription,
+                    &self.calibration_result,
                 );
 
                 ui.separator();
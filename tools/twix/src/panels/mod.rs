@@ -1,4 +1,5 @@
 mod behavior_simulator;
+mod decision_trace;
 mod image;
 mod image_segments;
 mod look_at;
@@ -12,6 +13,7 @@ mod vision_tuner;
 
 pub use self::behavior_simulator::BehaviorSimulatorPanel;
 pub use self::image::ImagePanel;
+pub use decision_trace::DecisionTracePanel;
 pub use image_segments::ImageSegmentsPanel;
 pub use look_at::LookAtPanel;
 pub use manual_camera_calibration::ManualCalibrationPanel;
@@ -11,12 +11,15 @@ use eframe::{
 use log::{error, info};
 
 use color_eyre::eyre::{eyre, Result, WrapErr};
-use communication::client::CyclerOutput;
+use communication::{client::CyclerOutput, messages::UpdatePolicy};
 use mlua::{Function, Lua, LuaSerdeExt};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, to_string_pretty, Value};
 
-use crate::{completion_edit::CompletionEdit, nao::Nao, panel::Panel, value_buffer::ValueBuffer};
+use crate::{
+    completion_edit::CompletionEdit, nao::Nao, panel::Panel, update_policy_edit::UpdatePolicyEdit,
+    value_buffer::ValueBuffer,
+};
 
 const DEFAULT_LINE_COLORS: &[Color32] = &[
     Color32::from_rgb(31, 119, 180),
@@ -34,6 +37,8 @@ const DEFAULT_LINE_COLORS: &[Color32] = &[
 #[derive(Serialize, Deserialize)]
 struct LineData {
     output_key: String,
+    #[serde(default)]
+    update_policy: UpdatePolicy,
     #[serde(skip)]
     value_buffer: Option<ValueBuffer>,
     color: Color32,
@@ -70,6 +75,7 @@ impl LineData {
 
         let mut line_data = Self {
             output_key: String::new(),
+            update_policy: UpdatePolicy::EveryCycle,
             value_buffer: None,
             color,
             lua,
@@ -123,6 +129,20 @@ impl LineData {
                 self.subscribe_key(nao.clone(), buffer_size);
             }
             ui.color_edit_button_srgba(&mut self.color);
+            let update_policy_id = ui.id().with("update_policy").with(id);
+            if ui
+                .add(UpdatePolicyEdit::new(
+                    update_policy_id,
+                    &mut self.update_policy,
+                ))
+                .changed()
+            {
+                info!(
+                    "Resubscribing {} with {:?}",
+                    self.output_key, self.update_policy
+                );
+                self.subscribe_key(nao.clone(), buffer_size);
+            }
             let id_source = ui.id().with("conversion_collapse").with(id);
             CollapsingHeader::new("Conversion Function")
                 .id_source(id_source)
@@ -175,7 +195,7 @@ impl LineData {
     fn subscribe_key(&mut self, nao: Arc<Nao>, buffer_size: usize) {
         self.value_buffer = match CyclerOutput::from_str(&self.output_key) {
             Ok(output) => {
-                let buffer = nao.subscribe_output(output);
+                let buffer = nao.subscribe_output_with_update_policy(output, self.update_policy);
                 buffer.reserve(buffer_size);
                 Some(buffer)
             }
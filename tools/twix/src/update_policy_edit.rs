@@ -0,0 +1,90 @@
+use std::{hash::Hash, mem::discriminant, time::Duration};
+
+use communication::messages::UpdatePolicy;
+use eframe::egui::{ComboBox, DragValue, Id, Response, Ui, Widget};
+
+const DEFAULT_POLICIES: [UpdatePolicy; 3] = [
+    UpdatePolicy::EveryCycle,
+    UpdatePolicy::EveryNthCycle { n: 10 },
+    UpdatePolicy::MaximumRate {
+        minimum_interval: Duration::from_millis(100),
+    },
+];
+
+/// Selects how often the robot sends a subscribed output, the response is marked as changed
+/// once a new policy should be subscribed
+pub struct UpdatePolicyEdit<'policy> {
+    id: Id,
+    update_policy: &'policy mut UpdatePolicy,
+}
+
+impl<'policy> UpdatePolicyEdit<'policy> {
+    pub fn new(id_source: impl Hash, update_policy: &'policy mut UpdatePolicy) -> Self {
+        Self {
+            id: Id::new(id_source),
+            update_policy,
+        }
+    }
+}
+
+impl Widget for UpdatePolicyEdit<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let update_policy = self.update_policy;
+        let mut policy_changed = false;
+        let mut response = ui
+            .horizontal(|ui| {
+                ComboBox::from_id_source(self.id)
+                    .selected_text(label(update_policy))
+                    .show_ui(ui, |ui| {
+                        for policy in DEFAULT_POLICIES {
+                            let is_selected = discriminant(update_policy) == discriminant(&policy);
+                            if ui.selectable_label(is_selected, label(&policy)).clicked()
+                                && !is_selected
+                            {
+                                *update_policy = policy;
+                                policy_changed = true;
+                            }
+                        }
+                    });
+                match update_policy {
+                    UpdatePolicy::EveryCycle => {}
+                    UpdatePolicy::EveryNthCycle { n } => {
+                        let response =
+                            ui.add(DragValue::new(n).clamp_range(1..=1000).prefix("n: "));
+                        policy_changed |= is_edit_finished(&response);
+                    }
+                    UpdatePolicy::MaximumRate { minimum_interval } => {
+                        let mut rate = 1.0 / minimum_interval.as_secs_f32();
+                        let response = ui.add(
+                            DragValue::new(&mut rate)
+                                .clamp_range(0.1..=100.0)
+                                .speed(0.1)
+                                .suffix(" Hz"),
+                        );
+                        if response.changed() {
+                            *minimum_interval = Duration::from_secs_f32(1.0 / rate);
+                        }
+                        policy_changed |= is_edit_finished(&response);
+                    }
+                }
+            })
+            .response;
+        if policy_changed {
+            response.mark_changed();
+        }
+        response
+    }
+}
+
+fn label(update_policy: &UpdatePolicy) -> &'static str {
+    match update_policy {
+        UpdatePolicy::EveryCycle => "Every cycle",
+        UpdatePolicy::EveryNthCycle { .. } => "Every nth cycle",
+        UpdatePolicy::MaximumRate { .. } => "Maximum rate",
+    }
+}
+
+/// Resubscribing on every intermediate value while dragging would flood the robot with requests
+fn is_edit_finished(response: &Response) -> bool {
+    response.drag_released() || (response.changed() && !response.dragged())
+}
@@ -6,7 +6,7 @@ use color_eyre::{
 };
 use communication::{
     client::{Communication, CyclerOutput, SubscriberMessage},
-    messages::Format,
+    messages::{Format, UpdatePolicy},
 };
 use log::error;
 use serde::Deserialize;
@@ -46,11 +46,15 @@ pub struct ValueBuffer {
 }
 
 impl ValueBuffer {
-    pub fn output(communication: Communication, output: CyclerOutput) -> Self {
+    pub fn output(
+        communication: Communication,
+        output: CyclerOutput,
+        update_policy: UpdatePolicy,
+    ) -> Self {
         let (command_sender, command_receiver) = mpsc::channel(10);
         spawn(async move {
             let (uuid, receiver) = communication
-                .subscribe_output(output.clone(), Format::Textual)
+                .subscribe_output(output.clone(), Format::Textual, update_policy)
                 .await;
             value_buffer(receiver, command_receiver, communication.clone(), None).await;
             communication.unsubscribe_output(uuid).await;